@@ -0,0 +1,76 @@
+//! ΔC cost per turn: fresh parser (window scored from scratch) vs incremental state
+//!
+//! Run with: cargo bench --bench dc_per_turn
+
+use std::time::Instant;
+use soul0::core::{DcParser, DcState};
+use soul0::types::{ConversationWindow, Turn, WindowPolicy};
+
+/// Window sizes, smallest first
+const SIZES: [usize; 3] = [20, 100, 400];
+
+/// Most the incremental cost may grow from the smallest to the largest window
+/// (scoring is flat; copying the result's per-pair details is not)
+const MAX_GROWTH: f64 = 4.0;
+
+/// Alternating two-speaker transcript
+fn transcript(size: usize) -> Vec<(&'static str, String)> {
+    let lines = [
+        "How is the garden doing this week?",
+        "The roses are blooming and the tomatoes need water every day",
+        "Did you see the match yesterday? I think we played badly",
+        "Maybe, but the weather was terrible and the pitch was wet",
+        "Ik denk dat we morgen naar het strand kunnen als het mooi weer is",
+        "Anyway, my car broke down again on the way to work!",
+    ];
+    (0..size)
+        .map(|i| {
+            let speaker = if i % 2 == 0 { "A" } else { "B" };
+            (speaker, format!("{} ({})", lines[i % lines.len()], i))
+        })
+        .collect()
+}
+
+/// Milliseconds per turn for adding `turns` turns to a window of `size` turns
+fn per_turn_ms(size: usize, turns: usize, fresh: bool) -> f64 {
+    let parser = DcParser::new();
+    let mut state = DcState::default();
+    let mut window = ConversationWindow::with_policy(WindowPolicy::turns(size));
+    let lines = transcript(size + turns);
+    
+    // Fill the window first, then time the steady state
+    for (speaker, text) in &lines[..size] {
+        window.add_turn(Turn::new(*speaker, text.as_str(), 0.1));
+        parser.calculate_with(&window, &mut state);
+    }
+    
+    let start = Instant::now();
+    for (speaker, text) in &lines[size..] {
+        window.add_turn(Turn::new(*speaker, text.as_str(), 0.1));
+        let result = if fresh {
+            parser.calculate(&window)
+        } else {
+            parser.calculate_with(&window, &mut state)
+        };
+        assert!(result.is_known());
+    }
+    start.elapsed().as_secs_f64() * 1000.0 / turns as f64
+}
+
+fn main() {
+    println!("ΔC per turn (ms), steady-state window");
+    println!("{:>8} {:>12} {:>12}", "turns", "fresh", "incremental");
+    let mut incremental = Vec::new();
+    for size in SIZES {
+        let fresh = per_turn_ms(size, 50, true);
+        // Best of three runs, so a busy machine does not fail the check
+        let warm = (0..3).map(|_| per_turn_ms(size, 500, false)).fold(f64::MAX, f64::min);
+        println!("{:>8} {:>12.3} {:>12.3}  ({:.1}x)", size, fresh, warm, fresh / warm);
+        incremental.push(warm);
+    }
+    
+    // Flat: 20x the window may not cost more than a few times as much per turn
+    let growth = incremental[SIZES.len() - 1] / incremental[0];
+    println!("incremental growth {}→{} turns: {:.2}x", SIZES[0], SIZES[SIZES.len() - 1], growth);
+    assert!(growth < MAX_GROWTH, "incremental ΔC grows with the window ({:.2}x)", growth);
+}
@@ -0,0 +1,66 @@
+//! r-parser throughput: per-signal regexes vs single pass vs parallel batch
+//!
+//! Run with: cargo bench --bench r_throughput
+
+use std::time::Instant;
+use soul0::core::RParser;
+
+/// Synthetic transcript corpus (EN + NL, short and long turns)
+fn corpus(size: usize) -> Vec<String> {
+    let lines = [
+        "The sky is blue. Silence. Breathing happens.",
+        "I always think I should do better than everyone else immediately! It's their fault, not mine!",
+        "Ik vind dat iedereen altijd te veel praat, vroeger was het beter.",
+        "I'm not sure what to think about it all, but I'll say it anyway: we were going to do it.",
+        "Stilte. Ademhalen. Ruimte.",
+        "We need to talk about what happened yesterday, because nobody ever listens to me and it is important right now.",
+        "Dat is m'n fout, in het verleden had ik het anders dan nu gedaan, maar morgen ga ik het beter doen.",
+        "Blue sky. Wind. Leaves falling.",
+    ];
+    (0..size)
+        .map(|i| {
+            // Vary length so the corpus is not just eight repeated strings
+            let a = lines[i % lines.len()];
+            let b = lines[(i * 7 + 3) % lines.len()];
+            if i % 3 == 0 { format!("{} {}", a, b) } else { a.to_string() }
+        })
+        .collect()
+}
+
+fn report(label: &str, texts: usize, secs: f64, baseline: Option<f64>) {
+    let rate = texts as f64 / secs;
+    match baseline {
+        Some(base) => println!("{:<28} {:>10.0} texts/s  ({:.1}x)", label, rate, rate / base),
+        None => println!("{:<28} {:>10.0} texts/s", label, rate),
+    }
+}
+
+fn main() {
+    let parser = RParser::new();
+    let owned = corpus(50_000);
+    let texts: Vec<&str> = owned.iter().map(String::as_str).collect();
+    
+    // Warm up lazy statics and check both paths agree
+    for text in texts.iter().take(1_000) {
+        assert_eq!(parser.parse(text).value.to_bits(), parser.parse_regex(text).value.to_bits());
+    }
+    
+    println!("r-parser throughput over {} texts", texts.len());
+    
+    let start = Instant::now();
+    let regex_sum: f64 = texts.iter().map(|t| parser.parse_regex(t).value).sum();
+    let regex_secs = start.elapsed().as_secs_f64();
+    let regex_rate = texts.len() as f64 / regex_secs;
+    report("regex per signal (old)", texts.len(), regex_secs, None);
+    
+    let start = Instant::now();
+    let single_sum: f64 = texts.iter().map(|t| parser.parse(t).value).sum();
+    report("single pass", texts.len(), start.elapsed().as_secs_f64(), Some(regex_rate));
+    
+    let start = Instant::now();
+    let batch_sum: f64 = parser.parse_many(&texts).iter().map(|r| r.value).sum();
+    report("single pass, parse_many", texts.len(), start.elapsed().as_secs_f64(), Some(regex_rate));
+    
+    assert_eq!(regex_sum.to_bits(), single_sum.to_bits());
+    assert_eq!(single_sum.to_bits(), batch_sum.to_bits());
+}
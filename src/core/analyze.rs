@@ -0,0 +1,297 @@
+//! Offline transcript analysis
+//!
+//! Replays an imported transcript through the duo pipeline: r per turn,
+//! ΔC over the window scales, the Facelock engine, proofs and snapshots.
+//! Everything runs on transcript time: each turn is stamped with a fixed
+//! base `Instant` plus its offset, so window limits, reply latency and the
+//! 8 s stability rule see the original pacing rather than how fast the
+//! file is read. `TranscriptRunner` does the same one turn at a time (used
+//! by replay). Untimed turns are spaced `UNTIMED_TURN_SPACING_MS` apart.
+
+use std::time::{Duration, Instant};
+use sha2::{Sha256, Digest};
+use crate::core::{Scorers, DcScorerState, FacelockEngine, ProofGenerator, SnapshotGenerator};
+use crate::types::{
+    Transcript, TranscriptReport, TurnReport, Turn, MultiScaleWindow, WindowPolicy,
+    FacelockState, Snapshot, StateOutput, DcResult, ImportReason, UNTIMED_TURN_SPACING_MS,
+};
+use crate::{CONFIDENCE_MIN_LOCK, STABILITY_DURATION_MS};
+
+/// How far the transcript clock runs ahead of the wall clock
+const CLOCK_LEAD: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Pipeline settings for an analysis run
+#[derive(Debug, Clone, Copy)]
+pub struct AnalyzeOptions {
+    /// Primary window policy
+    pub window: WindowPolicy,
+    /// Merge same-speaker turns within this gap into one logical turn
+    pub merge_gap: Option<Duration>,
+    /// Minimum turn confidence to start or sustain a lock
+    pub min_confidence: f64,
+}
+
+impl Default for AnalyzeOptions {
+    fn default() -> Self {
+        Self {
+            window: WindowPolicy::standard(),
+            merge_gap: None,
+            min_confidence: CONFIDENCE_MIN_LOCK,
+        }
+    }
+}
+
+/// Report plus the snapshots taken at each proof
+#[derive(Debug, Clone)]
+pub struct TranscriptAnalysis {
+    /// Per-turn report
+    pub report: TranscriptReport,
+    /// Snapshots, one per generated proof
+    pub snapshots: Vec<Snapshot>,
+}
+
+/// Run a transcript through r / ΔC / Facelock / proof on transcript time
+pub fn analyze_transcript(
+    transcript: &Transcript,
+    scorers: &Scorers,
+    options: &AnalyzeOptions,
+    sign_fn: impl Fn(&[u8]) -> [u8; 64],
+) -> TranscriptAnalysis {
+    let mut runner = TranscriptRunner::new(scorers, options, transcript_session_id(transcript));
+    let offsets = turn_offsets(transcript);
+    let mut turns = Vec::with_capacity(transcript.turns.len());
+    let mut snapshots = Vec::new();
+    
+    for (turn, &offset) in transcript.turns.iter().zip(&offsets) {
+        let step = runner.step(&turn.speaker, &turn.text, Duration::from_millis(offset), &sign_fn);
+        turns.push(step.report);
+        snapshots.extend(step.snapshot);
+    }
+    
+    // Time in LOCKED runs from a locked turn until the next turn
+    let locked_ms = turns.windows(2)
+        .filter(|w| w[0].state == FacelockState::Locked)
+        .map(|w| w[1].offset_ms - w[0].offset_ms)
+        .sum();
+    
+    let report = TranscriptReport {
+        import: ImportReason::R400_IMPORT_COMPLETE,
+        format: transcript.format,
+        speakers: transcript.speakers(),
+        duration_ms: offsets.last().copied().unwrap_or(0),
+        timed: transcript.is_timed(),
+        proof_count: turns.iter().filter(|t| t.proof.is_some()).count(),
+        locked_ms,
+        turns,
+    };
+    TranscriptAnalysis { report, snapshots }
+}
+
+/// Everything one runner step produced
+#[derive(Debug, Clone)]
+pub struct TurnStep {
+    /// Per-turn report
+    pub report: TurnReport,
+    /// Engine output (smoothed r, state, stability)
+    pub output: StateOutput,
+    /// ΔC of the primary window
+    pub dc: DcResult,
+    /// Snapshot taken with a proof at this turn
+    pub snapshot: Option<Snapshot>,
+}
+
+/// The duo pipeline fed one turn at a time on a transcript clock
+///
+/// The clock starts a day ahead of the wall clock, so time spent processing
+/// (or sleeping between replayed turns) never ages the window's turns.
+#[derive(Debug)]
+pub struct TranscriptRunner {
+    scorers: Scorers,
+    engine: FacelockEngine,
+    windows: MultiScaleWindow,
+    dc_state: DcScorerState,
+    proof_gen: ProofGenerator,
+    snap_gen: SnapshotGenerator,
+    session_id: [u8; 16],
+    base: Instant,
+    observers: Vec<String>,
+    proof_generated: bool,
+    turn_count: usize,
+}
+
+impl TranscriptRunner {
+    /// Create a runner with fresh engine and windows
+    pub fn new(scorers: &Scorers, options: &AnalyzeOptions, session_id: [u8; 16]) -> Self {
+        let mut engine = FacelockEngine::with_min_confidence(options.min_confidence);
+        engine.set_bell(false);
+        let mut windows = MultiScaleWindow::with_primary(options.window);
+        windows.set_merge_gap(options.merge_gap);
+        Self {
+            scorers: scorers.clone(),
+            engine,
+            windows,
+            dc_state: DcScorerState::default(),
+            proof_gen: ProofGenerator::new_random(),
+            snap_gen: SnapshotGenerator::with_scorer(scorers.r.clone()),
+            session_id,
+            base: Instant::now() + CLOCK_LEAD,
+            observers: Vec::new(),
+            proof_generated: false,
+            turn_count: 0,
+        }
+    }
+    
+    /// Feed one turn at `offset` from the start; offsets must not decrease
+    pub fn step(
+        &mut self,
+        speaker: &str,
+        text: &str,
+        offset: Duration,
+        sign_fn: impl Fn(&[u8]) -> [u8; 64],
+    ) -> TurnStep {
+        let at = self.base + offset;
+        self.turn_count += 1;
+        if !self.observers.iter().any(|o| o == speaker) {
+            self.observers.push(speaker.to_string());
+        }
+        
+        let r_value = self.scorers.r.parse(text);
+        self.windows.add_turn(Turn::new(speaker, text, r_value.value).with_timestamp(at));
+        let dc_result = self.scorers.dc.calculate_with(self.windows.primary(), &mut self.dc_state);
+        
+        // Same combined metric as duo mode
+        let effective_r = dc_result.value.map_or(r_value.value, |dc| r_value.value.max(dc));
+        let output = self.engine.update_at(effective_r, r_value.confidence, at);
+        
+        let mut proof_hex = None;
+        let mut snapshot = None;
+        if output.state == FacelockState::Locked
+            && output.stable_ms >= STABILITY_DURATION_MS
+            && !self.proof_generated
+            && dc_result.is_known()
+        {
+            let result = self.proof_gen.generate(
+                self.session_id,
+                output.state,
+                output.stable_ms as f64 / 1000.0,
+                output.r,
+                &dc_result,
+                self.windows.primary(),
+                sign_fn,
+            );
+            if let Some(proof) = result.proof {
+                self.proof_generated = true;
+                proof_hex = Some(proof.to_hex());
+                snapshot = self.snap_gen.generate(&proof, self.windows.longest(), self.observers.clone()).snapshot;
+            }
+        }
+        if output.state != FacelockState::Locked {
+            self.proof_generated = false;
+        }
+        
+        let report = TurnReport {
+            turn: self.turn_count,
+            offset_ms: offset.as_millis() as u64,
+            speaker: speaker.to_string(),
+            text: text.to_string(),
+            r: r_value.value,
+            confidence: r_value.confidence,
+            dc: dc_result.value,
+            dc_reason: dc_result.reason,
+            state: output.state,
+            reason: output.reason,
+            stable_ms: output.stable_ms,
+            proof: proof_hex,
+        };
+        TurnStep { report, output, dc: dc_result, snapshot }
+    }
+}
+
+/// Offset of every turn; untimed turns follow the previous one after a fixed spacing
+pub(crate) fn turn_offsets(transcript: &Transcript) -> Vec<u64> {
+    let mut offsets = Vec::with_capacity(transcript.turns.len());
+    let mut prev: Option<u64> = None;
+    for turn in &transcript.turns {
+        let at = match (turn.offset_ms, prev) {
+            (Some(at), Some(p)) => at.max(p),
+            (Some(at), None) => at,
+            (None, Some(p)) => p + UNTIMED_TURN_SPACING_MS,
+            (None, None) => 0,
+        };
+        offsets.push(at);
+        prev = Some(at);
+    }
+    offsets
+}
+
+/// Session ID derived from the transcript content (same file, same ID)
+fn transcript_session_id(transcript: &Transcript) -> [u8; 16] {
+    let mut hasher = Sha256::new();
+    for turn in &transcript.turns {
+        hasher.update(turn.speaker.as_bytes());
+        hasher.update([0]);
+        hasher.update(turn.text.as_bytes());
+        hasher.update([0]);
+    }
+    let hash: [u8; 32] = hasher.finalize().into();
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&hash[0..16]);
+    id
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::import_transcript;
+    
+    fn sign(data: &[u8]) -> [u8; 64] {
+        let hash: [u8; 32] = Sha256::digest(data).into();
+        let mut sig = [0u8; 64];
+        sig[0..32].copy_from_slice(&hash);
+        sig
+    }
+    
+    #[test]
+    fn test_untimed_turns_are_spaced() {
+        let transcript = import_transcript("[00:00:00] A: one\nB: two\n[00:00:30] A: three\nB: four").unwrap();
+        assert_eq!(turn_offsets(&transcript), vec![0, 3000, 30_000, 33_000]);
+    }
+    
+    #[test]
+    fn test_locks_on_transcript_time() {
+        let text = "[10:00:00] Anna: The sky is blue today\n\
+                    [10:00:04] Ben: The sky is blue and clear today\n\
+                    [10:00:08] Anna: The sky is blue and clear today\n\
+                    [10:00:12] Ben: The sky is blue and clear today\n\
+                    [10:00:16] Anna: The sky is blue and clear today\n\
+                    [10:00:20] Ben: The sky is blue and clear today";
+        let transcript = import_transcript(text).unwrap();
+        let analysis = analyze_transcript(&transcript, &Scorers::default(), &AnalyzeOptions::default(), sign);
+        let report = &analysis.report;
+        
+        // Runs instantly, but the 20 s of transcript time reach LOCKED
+        assert_eq!(report.turns.len(), 6);
+        assert_eq!(report.duration_ms, 20_000);
+        assert_eq!(report.final_state(), FacelockState::Locked);
+        assert_eq!(report.proof_count, 1);
+        assert_eq!(analysis.snapshots.len(), 1);
+        assert!(report.turns.iter().any(|t| t.proof.is_some() && t.stable_ms >= STABILITY_DURATION_MS));
+    }
+    
+    #[test]
+    fn test_window_prunes_by_transcript_time() {
+        let text = "[10:00:00] Anna: Shall we talk about the garden\n\
+                    [10:00:05] Ben: Yes the garden needs work\n\
+                    [11:00:00] Anna: Different topic entirely now";
+        let transcript = import_transcript(text).unwrap();
+        let analysis = analyze_transcript(&transcript, &Scorers::default(), &AnalyzeOptions::default(), sign);
+        
+        assert!(analysis.report.turns[1].dc.is_some());
+        assert_eq!(analysis.report.turns[2].dc, None);
+        assert_eq!(analysis.report.turns[2].dc_reason, crate::types::DcReason::R013_DC_UNKNOWN_TIMEOUT);
+    }
+}
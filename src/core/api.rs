@@ -5,11 +5,13 @@
 //! - GET /session/{id} - Get session status
 //! - GET /session/{id}/proof - Get latest proof
 //! - GET /session/{id}/snapshot - Get latest snapshot
+//! - GET /snapshots - Query stored snapshots (session, observer, since, until, theme, blind_spot, limit;
+//!   50 by default, at most 500)
 //! - WS /ws/{id} - Live updates
 //! - GET /health - Health check
 
 use axum::{
-    extract::{Path, State, WebSocketUpgrade, ws::{Message, WebSocket}},
+    extract::{Path, Query, State, WebSocketUpgrade, ws::{Message, WebSocket}},
     http::StatusCode,
     response::{IntoResponse, Json},
     routing::{get, post},
@@ -18,10 +20,20 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use std::time::Instant;
+use tokio::sync::{broadcast, mpsc, oneshot, RwLock};
 
-use crate::core::{RParser, DcParser, FacelockEngine, ProofGenerator, SnapshotGenerator};
-use crate::types::{Turn, ConversationWindow, FacelockState};
+use crate::core::{RScorer, DcScorer, DcScorerState, Scorers, FacelockEngine, min_confidence_from, ProofGenerator, SnapshotGenerator, AuditLog, SnapshotStore, DirStore};
+use crate::types::{Snapshot, SnapshotQuery, SnapshotReason, AuditConfig, merge_gap_from_secs, Turn, StateOutput, DcResult, MultiScaleWindow, WindowPolicy, WindowScale, ScaleDc, FacelockState, SentenceR, PairContribution, DcTimeline, SpeakerPairDc, SpeakerCoherence, TimingStats};
+
+/// Most turns a client-chosen window scale may keep
+pub const MAX_WINDOW_TURNS: usize = 500;
+
+/// Snapshots `GET /snapshots` returns when the query sets no `limit`
+pub const DEFAULT_SNAPSHOT_LIMIT: usize = 50;
+
+/// Most snapshots `GET /snapshots` returns, whatever the `limit`
+pub const MAX_SNAPSHOT_LIMIT: usize = 500;
 
 /// Session state
 #[derive(Debug)]
@@ -29,15 +41,92 @@ pub struct Session {
     pub id: String,
     pub session_bytes: [u8; 16],
     pub engine: FacelockEngine,
-    pub window: ConversationWindow,
-    pub r_parser: RParser,
-    pub dc_parser: DcParser,
+    /// Windows at every scale; the primary one drives state and proofs
+    pub windows: MultiScaleWindow,
+    pub r_parser: Arc<dyn RScorer>,
+    pub dc_parser: Arc<dyn DcScorer>,
     pub proof_gen: ProofGenerator,
     pub snap_gen: SnapshotGenerator,
     pub observers: Vec<String>,
     pub last_proof: Option<Vec<u8>>,
-    pub last_snapshot_path: Option<String>,
+    pub last_snapshot_id: Option<String>,
     pub update_tx: broadcast::Sender<SessionUpdate>,
+    /// ΔC after every turn
+    pub dc_timeline: DcTimeline,
+    /// ΔC per scale after the last turn (served to status polls)
+    pub dc_scales: Vec<ScaleDc>,
+    /// Incremental ΔC state per scale
+    pub dc_states: Vec<DcScorerState>,
+    /// Audit records and snapshot saves, written in order by the session's writer task
+    pub io: mpsc::UnboundedSender<SessionIo>,
+}
+
+/// File I/O of one session, done by its writer task rather than under the sessions lock
+#[derive(Debug)]
+pub enum SessionIo {
+    /// Input turn and what it produced
+    Turn {
+        speaker: String,
+        text: String,
+        at: Instant,
+        output: StateOutput,
+        dc: Box<DcResult>,
+    },
+    /// Proof generated at the last turn
+    Proof(String),
+    /// Snapshot to save; the saved location (None on failure) goes back on `saved`
+    Snapshot {
+        snapshot: Box<Snapshot>,
+        saved: oneshot::Sender<Option<String>>,
+    },
+    /// Signals once everything sent before it is written
+    Flush(oneshot::Sender<()>),
+}
+
+/// Start a session's writer task: I/O runs on the blocking pool, one item at a time
+fn spawn_session_io(mut audit: Option<AuditLog>, store: Arc<dyn SnapshotStore>) -> mpsc::UnboundedSender<SessionIo> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<SessionIo>();
+    tokio::spawn(async move {
+        while let Some(io) = rx.recv().await {
+            let store = store.clone();
+            let done = tokio::task::spawn_blocking(move || {
+                write_session_io(&mut audit, store.as_ref(), io);
+                audit
+            });
+            match done.await {
+                Ok(log) => audit = log,
+                Err(_) => return,
+            }
+        }
+    });
+    tx
+}
+
+/// Write one item; on an audit failure stop recording the session
+fn write_session_io(audit: &mut Option<AuditLog>, store: &dyn SnapshotStore, io: SessionIo) {
+    let result = match io {
+        SessionIo::Turn { speaker, text, at, output, dc } => audit.as_mut().map(|log| {
+            log.record_turn(&speaker, &text, at)?;
+            log.record_state(&output)?;
+            log.record_dc(&dc)
+        }),
+        SessionIo::Proof(proof_hex) => audit.as_mut().map(|log| log.record_proof(&proof_hex)),
+        SessionIo::Snapshot { snapshot, saved } => {
+            let path = store.save(&snapshot).ok();
+            let result = audit.as_mut().map(|log| log.record_snapshot(&snapshot.id, path.as_deref()));
+            let _ = saved.send(path);
+            result
+        }
+        SessionIo::Flush(done) => {
+            let _ = done.send(());
+            None
+        }
+    };
+    if let Some(Err(e)) = result {
+        if let Some(log) = audit.take() {
+            eprintln!("Audit log {} failed, recording stopped: {}", log.path(), e);
+        }
+    }
 }
 
 /// Live update message
@@ -54,13 +143,28 @@ pub struct SessionUpdate {
 /// App state
 pub struct AppState {
     pub sessions: RwLock<HashMap<String, Session>>,
-    pub snapshot_dir: String,
+    /// Where snapshots are saved and queried
+    pub store: Arc<dyn SnapshotStore>,
+    /// Scorers handed to every new session
+    pub scorers: Scorers,
+    /// Directory for per-session audit logs (None = no recording)
+    pub audit_dir: Option<String>,
 }
 
 /// Create new session request
 #[derive(Debug, Deserialize)]
 pub struct NewSessionRequest {
     pub observers: Option<Vec<String>>,
+    /// Minimum turn confidence to start or sustain a lock (0.0-1.0, 400 otherwise)
+    pub min_confidence: Option<f64>,
+    /// Merge consecutive same-speaker turns within this many seconds
+    pub merge_gap_secs: Option<f64>,
+    /// Which turns the window keeps (default: last 30 s, 10 per speaker);
+    /// must set a limit, and is capped at `MAX_WINDOW_TURNS` turns
+    pub window: Option<WindowPolicy>,
+    /// All window scales, primary first (overrides `window`; default: short, 5 min, session),
+    /// limited like `window`
+    pub scales: Option<Vec<WindowScale>>,
 }
 
 /// Create new session response
@@ -82,6 +186,9 @@ pub struct SessionStatusResponse {
     pub observers: Vec<String>,
     pub proof_available: bool,
     pub snapshot_available: bool,
+    /// ΔC per window scale, primary first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dc_scales: Vec<ScaleDc>,
 }
 
 /// Add turn request
@@ -100,6 +207,24 @@ pub struct AddTurnResponse {
     pub stable_ms: u64,
     pub proof_generated: bool,
     pub snapshot_generated: bool,
+    /// Per-sentence r (sentence-level aggregation only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sentences: Vec<SentenceR>,
+    /// Per-pair ΔC breakdown of the current window
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dc_pairs: Vec<PairContribution>,
+    /// ΔC per speaker pair
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dc_matrix: Vec<SpeakerPairDc>,
+    /// ΔC of each speaker with the group
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub speaker_coherence: Vec<SpeakerCoherence>,
+    /// Reply latency and silences in the current window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<TimingStats>,
+    /// ΔC per window scale, primary first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dc_scales: Vec<ScaleDc>,
 }
 
 /// Health response
@@ -120,9 +245,26 @@ pub struct ProofResponse {
 
 /// Create the API router
 pub fn create_router(snapshot_dir: String) -> Router {
+    create_router_with_scorers(snapshot_dir, Scorers::default())
+}
+
+/// Create the API router with custom r/ΔC scorers
+pub fn create_router_with_scorers(snapshot_dir: String, scorers: Scorers) -> Router {
+    create_router_with_audit(snapshot_dir, scorers, None)
+}
+
+/// Create the API router, recording every session to `<audit_dir>/<session_id>.ndjson`
+pub fn create_router_with_audit(snapshot_dir: String, scorers: Scorers, audit_dir: Option<String>) -> Router {
+    create_router_with_store(Arc::new(DirStore::new(snapshot_dir)), scorers, audit_dir)
+}
+
+/// Create the API router on a snapshot store
+pub fn create_router_with_store(store: Arc<dyn SnapshotStore>, scorers: Scorers, audit_dir: Option<String>) -> Router {
     let state = Arc::new(AppState {
         sessions: RwLock::new(HashMap::new()),
-        snapshot_dir,
+        store,
+        scorers,
+        audit_dir,
     });
     
     Router::new()
@@ -132,6 +274,8 @@ pub fn create_router(snapshot_dir: String) -> Router {
         .route("/session/:id/turn", post(add_turn))
         .route("/session/:id/proof", get(get_proof))
         .route("/session/:id/snapshot", get(get_snapshot))
+        .route("/session/:id/timeline", get(get_timeline))
+        .route("/snapshots", get(query_snapshots))
         .route("/ws/:id", get(websocket_handler))
         .with_state(state)
 }
@@ -154,20 +298,60 @@ async fn create_session(
     let session_id = generate_session_id();
     let session_bytes = generate_session_bytes();
     let (tx, _) = broadcast::channel(100);
+    let mut windows = match req.scales {
+        Some(scales) => MultiScaleWindow::with_scales(scales.into_iter()
+            .map(|scale| Ok(WindowScale::new(scale.name, client_policy(scale.policy)?)))
+            .collect::<Result<_, StatusCode>>()?),
+        None => MultiScaleWindow::with_primary(match req.window {
+            Some(policy) => client_policy(policy)?,
+            None => WindowPolicy::standard(),
+        }),
+    };
+    let merge_gap = req.merge_gap_secs
+        .map(merge_gap_from_secs)
+        .transpose()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    windows.set_merge_gap(merge_gap);
+    let engine = req.min_confidence
+        .map(min_confidence_from)
+        .transpose()
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+        .map(FacelockEngine::with_min_confidence)
+        .unwrap_or_default();
+    
+    let audit = match state.audit_dir {
+        Some(ref dir) => {
+            let config = AuditConfig {
+                window: windows.primary().policy(),
+                merge_gap_ms: merge_gap.map(|gap| gap.as_millis() as u64),
+                min_confidence: engine.min_confidence(),
+                scorers: state.scorers.config.clone(),
+            };
+            let path = std::path::Path::new(dir).join(format!("{}.ndjson", session_id));
+            let log = AuditLog::create(path, &session_id, "api", config)
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            Some(log)
+        }
+        None => None,
+    };
     
     let session = Session {
         id: session_id.clone(),
         session_bytes,
-        engine: FacelockEngine::new(),
-        window: ConversationWindow::new(),
-        r_parser: RParser::new(),
-        dc_parser: DcParser::new(),
+        engine,
+        windows,
+        r_parser: state.scorers.r.clone(),
+        dc_parser: state.scorers.dc.clone(),
         proof_gen: ProofGenerator::new_random(),
-        snap_gen: SnapshotGenerator::new(),
+        snap_gen: SnapshotGenerator::with_scorer(state.scorers.r.clone()),
         observers: req.observers.unwrap_or_default(),
         last_proof: None,
-        last_snapshot_path: None,
+        last_snapshot_id: None,
         update_tx: tx,
+        dc_timeline: DcTimeline::new(),
+        dc_scales: Vec::new(),
+        dc_states: Vec::new(),
+        io: spawn_session_io(audit, state.store.clone()),
     };
     
     let mut sessions = state.sessions.write().await;
@@ -179,6 +363,14 @@ async fn create_session(
     }))
 }
 
+/// A window policy from a client: 400 if it sets no limit, count limits capped
+fn client_policy(policy: WindowPolicy) -> Result<WindowPolicy, StatusCode> {
+    if policy.is_unbounded() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(policy.capped(MAX_WINDOW_TURNS))
+}
+
 /// Get session status
 async fn get_session(
     State(state): State<Arc<AppState>>,
@@ -187,7 +379,18 @@ async fn get_session(
     let sessions = state.sessions.read().await;
     let session = sessions.get(&id).ok_or(StatusCode::NOT_FOUND)?;
     
-    let dc_result = session.dc_parser.calculate(&session.window);
+    // ΔC only changes between turns when a scale's time limit empties its window
+    let mut dc_scales = if session.dc_scales.is_empty() {
+        session.dc_parser.calculate_scales(&session.windows, &mut Vec::new())
+    } else {
+        session.dc_scales.clone()
+    };
+    for (scale, (_, window)) in dc_scales.iter_mut().zip(session.windows.scales()) {
+        if window.timed_out() {
+            scale.dc = session.dc_parser.calculate(window);
+        }
+    }
+    let dc_result = dc_scales[0].dc.clone();
     let output = session.engine.current_output();
     
     Ok(Json(SessionStatusResponse {
@@ -196,13 +399,24 @@ async fn get_session(
         r: output.r,
         dc: dc_result.value,
         stable_ms: output.stable_ms,
-        turn_count: session.window.len(),
+        turn_count: session.windows.primary().len(),
         observers: session.observers.clone(),
         proof_available: session.last_proof.is_some(),
-        snapshot_available: session.last_snapshot_path.is_some(),
+        snapshot_available: session.last_snapshot_id.is_some(),
+        dc_scales,
     }))
 }
 
+/// Get ΔC after every turn of the session
+async fn get_timeline(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<DcTimeline>, StatusCode> {
+    let sessions = state.sessions.read().await;
+    let session = sessions.get(&id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(session.dc_timeline.clone()))
+}
+
 /// Add turn to session
 async fn add_turn(
     State(state): State<Arc<AppState>>,
@@ -217,13 +431,16 @@ async fn add_turn(
         session.observers.push(req.speaker.clone());
     }
     
-    // Parse and add turn
+    // Parse and add turn (one timestamp for window, engine and audit log)
+    let now = Instant::now();
     let r_value = session.r_parser.parse(&req.text);
-    let turn = Turn::new(&req.speaker, &req.text, r_value.value);
-    session.window.add_turn(turn);
+    let turn = Turn::new(&req.speaker, &req.text, r_value.value).with_timestamp(now);
+    session.windows.add_turn(turn);
     
-    // Calculate ΔC
-    let dc_result = session.dc_parser.calculate(&session.window);
+    // Calculate ΔC at every scale; the primary one drives the engine
+    session.dc_scales = session.dc_parser.calculate_scales(&session.windows, &mut session.dc_states);
+    let dc_result = session.dc_scales[0].dc.clone();
+    session.dc_timeline.record(req.speaker.clone(), &dc_result);
     
     // Update engine
     let effective_r = if let Some(dc) = dc_result.value {
@@ -231,11 +448,18 @@ async fn add_turn(
     } else {
         r_value.value
     };
-    let output = session.engine.update(effective_r);
+    let output = session.engine.update_at(effective_r, r_value.confidence, now);
+    let _ = session.io.send(SessionIo::Turn {
+        speaker: req.speaker.clone(),
+        text: req.text.clone(),
+        at: now,
+        output: output.clone(),
+        dc: Box::new(dc_result.clone()),
+    });
     
     // Check for proof generation
     let mut proof_generated = false;
-    let mut snapshot_generated = false;
+    let mut snapshot_saved = None;
     
     if output.state == FacelockState::Locked 
         && output.stable_ms >= 8000 
@@ -248,26 +472,27 @@ async fn add_turn(
             output.stable_ms as f64 / 1000.0,
             output.r,
             &dc_result,
-            &session.window,
+            session.windows.primary(),
             mock_sign,
         );
         
         if let Some(proof) = proof_result.proof {
             session.last_proof = Some(proof.to_bytes().to_vec());
             proof_generated = true;
+            let _ = session.io.send(SessionIo::Proof(proof.to_hex()));
             
-            // Generate snapshot
+            // Generate snapshot (from the longest scale)
             let snap_result = session.snap_gen.generate(
                 &proof,
-                &session.window,
+                session.windows.longest(),
                 session.observers.clone(),
             );
             
             if let Some(snapshot) = snap_result.snapshot {
-                if let Ok(path) = crate::core::save_snapshot(&snapshot, &state.snapshot_dir) {
-                    session.last_snapshot_path = Some(path);
-                    snapshot_generated = true;
-                }
+                let (saved, on_saved) = oneshot::channel();
+                let snapshot_id = snapshot.id.clone();
+                let _ = session.io.send(SessionIo::Snapshot { snapshot: Box::new(snapshot), saved });
+                snapshot_saved = Some((snapshot_id, session.last_proof.clone(), on_saved));
             }
         }
     }
@@ -278,7 +503,7 @@ async fn add_turn(
         dc: dc_result.value,
         state: format!("{:?}", output.state),
         stable_ms: output.stable_ms,
-        turn_count: session.window.len(),
+        turn_count: session.windows.primary().len(),
         proof_available: session.last_proof.is_some(),
     };
     let _ = session.update_tx.send(update);
@@ -286,7 +511,25 @@ async fn add_turn(
     // Reset proof on DRIFT
     if output.state != FacelockState::Locked {
         session.last_proof = None;
-        session.last_snapshot_path = None;
+        session.last_snapshot_id = None;
+    }
+    let dc_scales = session.dc_scales.clone();
+    let (flush, flushed) = oneshot::channel();
+    let _ = session.io.send(SessionIo::Flush(flush));
+    drop(sessions);
+    
+    // Wait for the writes outside the lock; the snapshot becomes the session's
+    // unless a later turn already replaced or reset the proof
+    let _ = flushed.await;
+    let mut snapshot_generated = false;
+    if let Some((snapshot_id, proof, on_saved)) = snapshot_saved {
+        if let Ok(Some(_)) = on_saved.await {
+            snapshot_generated = true;
+            let mut sessions = state.sessions.write().await;
+            if let Some(session) = sessions.get_mut(&id).filter(|s| s.last_proof == proof) {
+                session.last_snapshot_id = Some(snapshot_id);
+            }
+        }
     }
     
     Ok(Json(AddTurnResponse {
@@ -296,6 +539,12 @@ async fn add_turn(
         stable_ms: output.stable_ms,
         proof_generated,
         snapshot_generated,
+        sentences: r_value.sentences,
+        dc_pairs: dc_result.pairs,
+        dc_matrix: dc_result.matrix,
+        speaker_coherence: dc_result.speaker_coherence,
+        timing: dc_result.timing,
+        dc_scales,
     }))
 }
 
@@ -321,14 +570,35 @@ async fn get_proof(
 async fn get_snapshot(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
-) -> Result<impl IntoResponse, StatusCode> {
+) -> Result<Json<Snapshot>, StatusCode> {
     let sessions = state.sessions.read().await;
     let session = sessions.get(&id).ok_or(StatusCode::NOT_FOUND)?;
     
-    let path = session.last_snapshot_path.as_ref().ok_or(StatusCode::NOT_FOUND)?;
-    let content = std::fs::read_to_string(path).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
-    Ok((StatusCode::OK, [("content-type", "application/json")], content))
+    let snapshot_id = session.last_snapshot_id.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    state.store.load(snapshot_id).map(Json).map_err(store_status)
+}
+
+/// Query stored snapshots, newest first (`DEFAULT_SNAPSHOT_LIMIT` unless the
+/// query sets a limit, never more than `MAX_SNAPSHOT_LIMIT`)
+async fn query_snapshots(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SnapshotQuery>,
+) -> Result<Json<Vec<Snapshot>>, StatusCode> {
+    let limit = query.limit.unwrap_or(DEFAULT_SNAPSHOT_LIMIT).min(MAX_SNAPSHOT_LIMIT);
+    let query = SnapshotQuery { limit: Some(limit), ..query };
+    let store = state.store.clone();
+    tokio::task::spawn_blocking(move || store.query(&query))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map(Json)
+        .map_err(store_status)
+}
+
+fn store_status(reason: SnapshotReason) -> StatusCode {
+    match reason {
+        SnapshotReason::R306_SNAPSHOT_NOT_FOUND => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
 }
 
 /// WebSocket handler for live updates
@@ -387,7 +657,7 @@ fn mock_sign(data: &[u8]) -> [u8; 64] {
     let h1: [u8; 32] = hasher.finalize().into();
     
     let mut hasher = Sha256::new();
-    hasher.update(&h1);
+    hasher.update(h1);
     let h2: [u8; 32] = hasher.finalize().into();
     
     let mut sig = [0u8; 64];
@@ -398,7 +668,40 @@ fn mock_sign(data: &[u8]) -> [u8; 64] {
 
 /// Run the API server
 pub async fn run_server(addr: &str, snapshot_dir: String) -> Result<(), Box<dyn std::error::Error>> {
-    let router = create_router(snapshot_dir);
+    run_server_with_scorers(addr, snapshot_dir, Scorers::default()).await
+}
+
+/// Run the API server with custom r/ΔC scorers
+pub async fn run_server_with_scorers(
+    addr: &str,
+    snapshot_dir: String,
+    scorers: Scorers,
+) -> Result<(), Box<dyn std::error::Error>> {
+    run_server_with_audit(addr, snapshot_dir, scorers, None).await
+}
+
+/// Run the API server, optionally recording every session to an audit log
+pub async fn run_server_with_audit(
+    addr: &str,
+    snapshot_dir: String,
+    scorers: Scorers,
+    audit_dir: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    run_server_with_store(addr, Arc::new(DirStore::new(snapshot_dir)), scorers, audit_dir).await
+}
+
+/// Run the API server on a snapshot store
+pub async fn run_server_with_store(
+    addr: &str,
+    store: Arc<dyn SnapshotStore>,
+    scorers: Scorers,
+    audit_dir: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🜂 Snapshots in {}", store.location());
+    if let Some(ref dir) = audit_dir {
+        println!("🜂 Recording sessions to {}", dir);
+    }
+    let router = create_router_with_store(store, scorers, audit_dir);
     let listener = tokio::net::TcpListener::bind(addr).await?;
     println!("🜂 PhaseLock API running on {}", addr);
     println!("  POST /session/new      - Create session");
@@ -406,6 +709,8 @@ pub async fn run_server(addr: &str, snapshot_dir: String) -> Result<(), Box<dyn
     println!("  POST /session/:id/turn - Add turn");
     println!("  GET  /session/:id/proof - Get proof");
     println!("  GET  /session/:id/snapshot - Get snapshot");
+    println!("  GET  /session/:id/timeline - ΔC per turn");
+    println!("  GET  /snapshots        - Query snapshots");
     println!("  WS   /ws/:id           - Live updates");
     println!("  GET  /health           - Health check");
     axum::serve(listener, router).await?;
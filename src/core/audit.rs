@@ -0,0 +1,227 @@
+//! NDJSON audit log writer and reader
+//!
+//! `AuditLog` appends one event per line and flushes after every line, so a
+//! log is complete up to the last turn even if the process dies. Turn
+//! offsets are measured from the moment the log was created; the caller
+//! passes the same `Instant` it stamps the turn and the engine update with.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+use crate::types::{
+    AuditConfig, AuditEvent, AuditRecording, AuditReason, RecordedTurn, DcResult, StateOutput,
+};
+
+/// Append-only audit log for one session
+#[derive(Debug)]
+pub struct AuditLog {
+    writer: BufWriter<File>,
+    path: String,
+    start: Instant,
+    seq: u64,
+}
+
+impl AuditLog {
+    /// Create the log file (and its directory) and write the session header
+    pub fn create(
+        path: impl AsRef<Path>,
+        session_id: &str,
+        mode: &str,
+        config: AuditConfig,
+    ) -> io::Result<Self> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut log = Self {
+            writer: BufWriter::new(File::create(path)?),
+            path: path.to_string_lossy().into_owned(),
+            start: Instant::now(),
+            seq: 0,
+        };
+        log.write(&AuditEvent::Session {
+            session_id: session_id.to_string(),
+            mode: mode.to_string(),
+            version: crate::VERSION.to_string(),
+            started_unix_ms: chrono::Utc::now().timestamp_millis(),
+            config: Box::new(config),
+        })?;
+        Ok(log)
+    }
+    
+    /// Path of the log file
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    
+    /// Sequence number of the last recorded turn (0 before the first)
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+    
+    /// Record an input turn received at `at`; returns its sequence number
+    pub fn record_turn(&mut self, speaker: &str, text: &str, at: Instant) -> io::Result<u64> {
+        self.seq += 1;
+        self.write(&AuditEvent::Turn {
+            seq: self.seq,
+            offset_us: at.saturating_duration_since(self.start).as_micros() as u64,
+            speaker: speaker.to_string(),
+            text: text.to_string(),
+        })?;
+        Ok(self.seq)
+    }
+    
+    /// Record the engine output for the last turn
+    pub fn record_state(&mut self, output: &StateOutput) -> io::Result<()> {
+        self.write(&AuditEvent::State { seq: self.seq, output: Box::new(output.clone()) })
+    }
+    
+    /// Record the primary-window ΔC for the last turn
+    pub fn record_dc(&mut self, dc: &DcResult) -> io::Result<()> {
+        self.write(&AuditEvent::Dc { seq: self.seq, dc: Box::new(dc.clone()) })
+    }
+    
+    /// Record a proof generated at the last turn
+    pub fn record_proof(&mut self, proof_hex: &str) -> io::Result<()> {
+        self.write(&AuditEvent::Proof { seq: self.seq, proof_hex: proof_hex.to_string() })
+    }
+    
+    /// Record a snapshot generated at the last turn
+    pub fn record_snapshot(&mut self, snapshot_id: &str, path: Option<&str>) -> io::Result<()> {
+        self.write(&AuditEvent::Snapshot {
+            seq: self.seq,
+            snapshot_id: snapshot_id.to_string(),
+            path: path.map(str::to_string),
+        })
+    }
+    
+    fn write(&mut self, event: &AuditEvent) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, event)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+}
+
+/// Read an audit log from disk
+pub fn read_audit_log(path: &str) -> Result<AuditRecording, AuditReason> {
+    let text = std::fs::read_to_string(path).map_err(|_| AuditReason::R500_AUDIT_READ_ERROR)?;
+    parse_audit_log(&text)
+}
+
+/// Parse NDJSON audit events into turns with their recorded outputs
+pub fn parse_audit_log(text: &str) -> Result<AuditRecording, AuditReason> {
+    let mut events = text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str::<AuditEvent>(line).map_err(|_| AuditReason::R501_AUDIT_INVALID_EVENT));
+    
+    let mut recording = match events.next().transpose()? {
+        Some(AuditEvent::Session { session_id, mode, version, started_unix_ms, config }) => AuditRecording {
+            session_id,
+            mode,
+            version,
+            started_unix_ms,
+            config: *config,
+            turns: Vec::new(),
+        },
+        _ => return Err(AuditReason::R502_AUDIT_NO_SESSION),
+    };
+    
+    for event in events {
+        match event? {
+            AuditEvent::Session { .. } => return Err(AuditReason::R501_AUDIT_INVALID_EVENT),
+            AuditEvent::Turn { seq, offset_us, speaker, text } => recording.turns.push(RecordedTurn {
+                seq,
+                offset_us,
+                speaker,
+                text,
+                state: None,
+                dc: None,
+                proof: false,
+            }),
+            AuditEvent::State { seq, output } => recorded_turn(&mut recording, seq)?.state = Some(*output),
+            AuditEvent::Dc { seq, dc } => recorded_turn(&mut recording, seq)?.dc = Some(*dc),
+            AuditEvent::Proof { seq, .. } => recorded_turn(&mut recording, seq)?.proof = true,
+            AuditEvent::Snapshot { seq, .. } => {
+                recorded_turn(&mut recording, seq)?;
+            }
+        }
+    }
+    Ok(recording)
+}
+
+/// Output events must follow the turn they belong to
+fn recorded_turn(recording: &mut AuditRecording, seq: u64) -> Result<&mut RecordedTurn, AuditReason> {
+    recording.turns.last_mut()
+        .filter(|t| t.seq == seq)
+        .ok_or(AuditReason::R501_AUDIT_INVALID_EVENT)
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::types::{
+        FacelockState, ReasonCode, WindowPolicy, DcReason, ScorerConfig, RAggregation, ThematicModel,
+    };
+    
+    fn config() -> AuditConfig {
+        AuditConfig {
+            window: WindowPolicy::standard(),
+            merge_gap_ms: None,
+            min_confidence: crate::CONFIDENCE_MIN_LOCK,
+            scorers: Some(ScorerConfig {
+                r_aggregation: RAggregation::TopK(2),
+                thematic_model: ThematicModel::Jaccard,
+                corpus: Some("corpus.txt".to_string()),
+                corpus_sha256: Some("00ff".to_string()),
+                ..ScorerConfig::default()
+            }),
+        }
+    }
+    
+    #[test]
+    fn test_log_round_trip() {
+        let path = std::env::temp_dir().join("soul0_audit").join("round_trip.ndjson");
+        let mut log = AuditLog::create(&path, "abc", "duo", config()).unwrap();
+        let start = Instant::now();
+        
+        log.record_turn("A", "The sky is blue", start + Duration::from_millis(1500)).unwrap();
+        log.record_state(&StateOutput::new(0.1, FacelockState::Approaching, 0, ReasonCode::R001_NOT_ALIGNED)).unwrap();
+        log.record_dc(&DcResult::unknown(DcReason::R012_DC_UNKNOWN_INSUFFICIENT_TURNS)).unwrap();
+        log.record_turn("B", "Yes very blue", start + Duration::from_millis(4000)).unwrap();
+        log.record_proof("00ff").unwrap();
+        log.record_snapshot("snap", None).unwrap();
+        
+        let recording = read_audit_log(path.to_str().unwrap()).unwrap();
+        assert_eq!(recording.session_id, "abc");
+        assert_eq!(recording.mode, "duo");
+        assert_eq!(recording.config, config());
+        assert_eq!(recording.turns.len(), 2);
+        assert!(recording.turns[0].offset_us >= 1_500_000);
+        assert_eq!(recording.turns[0].state.as_ref().map(|s| s.state), Some(FacelockState::Approaching));
+        assert!(recording.turns[0].dc.is_some());
+        assert!(recording.turns[1].proof);
+        assert!(recording.turns[1].state.is_none());
+    }
+    
+    #[test]
+    fn test_log_needs_session_header() {
+        let turn = r#"{"event":"turn","seq":1,"offset_us":0,"speaker":"A","text":"hi"}"#;
+        assert_eq!(parse_audit_log(turn).unwrap_err(), AuditReason::R502_AUDIT_NO_SESSION);
+        assert_eq!(parse_audit_log("").unwrap_err(), AuditReason::R502_AUDIT_NO_SESSION);
+        assert_eq!(parse_audit_log("not json").unwrap_err(), AuditReason::R501_AUDIT_INVALID_EVENT);
+    }
+    
+    #[test]
+    fn test_output_without_turn_is_rejected() {
+        let header = r#"{"event":"session","session_id":"x","mode":"api","version":"0","started_unix_ms":0,
+            "config":{"window":{},"min_confidence":0.5}}"#.replace('\n', "");
+        let text = format!("{}\n{}", header, r#"{"event":"proof","seq":3,"proof_hex":"00"}"#);
+        assert_eq!(parse_audit_log(&text).unwrap_err(), AuditReason::R501_AUDIT_INVALID_EVENT);
+    }
+}
@@ -0,0 +1,140 @@
+//! Turn-taking balance: does one speaker dominate the conversation?
+//!
+//! Three parts, each 0.0 (even) to 1.0 (one-sided):
+//! - word share: how far the most talkative speaker is above an even split
+//! - length asymmetry: 1 - shortest / longest mean turn length per speaker
+//! - interruptions: rapid alternation, the share of speaker changes that
+//!   came within `RAPID_REPLY_MS` of the previous turn (measured from turn
+//!   timing; 0.0 when no speaker change has a timestamp)
+
+use crate::core::timing::RAPID_REPLY_MS;
+use crate::types::TurnBalance;
+
+/// Imbalance from which one speaker counts as dominating (snapshot blind spot)
+pub const DOMINANCE_THRESHOLD: f64 = 0.5;
+
+/// Measure turn-taking balance over the window
+///
+/// `turns` are (speaker, text), oldest first; `gaps[i]` is the gap before
+/// turn `i` in milliseconds (see `timing::turn_gaps`), None if unknown.
+pub fn turn_balance(turns: &[(&str, &str)], gaps: &[Option<u64>]) -> TurnBalance {
+    // Words and turns per speaker, in order of first turn
+    let mut speakers: Vec<(&str, usize, usize)> = Vec::new();
+    for (speaker, text) in turns {
+        let words = text.split_whitespace().count();
+        match speakers.iter_mut().find(|(s, _, _)| s == speaker) {
+            Some((_, w, t)) => {
+                *w += words;
+                *t += 1;
+            }
+            None => speakers.push((speaker, words, 1)),
+        }
+    }
+    
+    // Speaker changes with a known gap, and how many came within RAPID_REPLY_MS
+    let gaps: Vec<u64> = (1..turns.len())
+        .filter(|&i| turns[i].0 != turns[i - 1].0)
+        .filter_map(|i| gaps.get(i).copied().flatten())
+        .collect();
+    let rapid = gaps.iter().filter(|&&gap| gap < RAPID_REPLY_MS).count();
+    balance_from_counts(&speakers, gaps.len(), rapid)
+}
+
+/// Turn-taking balance from running counts
+///
+/// `speakers` are (speaker, words, turns) in order of their first turn;
+/// `exchanges` counts speaker changes with a known gap, `rapid` those
+/// within `RAPID_REPLY_MS`.
+pub fn balance_from_counts(speakers: &[(&str, usize, usize)], exchanges: usize, rapid: usize) -> TurnBalance {
+    let total_words: usize = speakers.iter().map(|(_, w, _)| w).sum();
+    if speakers.len() < 2 || total_words == 0 {
+        return TurnBalance::default();
+    }
+    
+    let k = speakers.len() as f64;
+    let (dominant, dominant_words, _) = speakers.iter()
+        .max_by_key(|(_, w, _)| *w)
+        .copied()
+        .unwrap();
+    let dominant_share = dominant_words as f64 / total_words as f64;
+    let word_share = ((dominant_share - 1.0 / k) / (1.0 - 1.0 / k)).clamp(0.0, 1.0);
+    
+    let mean_lengths: Vec<f64> = speakers.iter()
+        .map(|(_, w, t)| *w as f64 / *t as f64)
+        .collect();
+    let longest = mean_lengths.iter().cloned().fold(0.0, f64::max);
+    let shortest = mean_lengths.iter().cloned().fold(f64::MAX, f64::min);
+    let length_asymmetry = if longest > 0.0 { 1.0 - shortest / longest } else { 0.0 };
+    
+    let interruptions = if exchanges == 0 {
+        0.0
+    } else {
+        rapid as f64 / exchanges as f64
+    };
+    
+    TurnBalance {
+        word_share,
+        length_asymmetry,
+        interruptions,
+        dominant: Some(dominant.to_string()),
+        dominant_share,
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_even_conversation_is_balanced() {
+        let turns = [
+            ("A", "The garden looks lovely today"),
+            ("B", "The roses are blooming early"),
+            ("A", "We should sit outside later"),
+            ("B", "Yes, after dinner sounds good"),
+        ];
+        let balance = turn_balance(&turns, &[None; 4]);
+        assert!(balance.imbalance() < 0.1, "got {:?}", balance);
+    }
+    
+    #[test]
+    fn test_one_speaker_dominates() {
+        let long = "I have been thinking about the project and the deadline and everything that still needs to happen before Friday";
+        let mut turns = Vec::new();
+        for _ in 0..5 {
+            turns.push(("A", long));
+            turns.push(("B", "Ok"));
+        }
+        let balance = turn_balance(&turns, &[]);
+        
+        assert_eq!(balance.dominant.as_deref(), Some("A"));
+        assert!(balance.dominant_share > 0.9);
+        assert!(balance.length_asymmetry > 0.9);
+        assert!(balance.imbalance() >= DOMINANCE_THRESHOLD);
+    }
+    
+    #[test]
+    fn test_rapid_alternation_and_single_speaker() {
+        let turns = [
+            ("A", "I was going to say that"),
+            ("B", "Wait, listen to me first"),
+            ("A", "But the point is"),
+            ("A", "that we agreed on it"),
+            ("B", "No, the point is money"),
+        ];
+        // B cuts in at once, A takes time to answer, B cuts in again
+        let gaps = [None, Some(300), Some(4_000), Some(200), Some(500)];
+        let balance = turn_balance(&turns, &gaps);
+        assert!((balance.interruptions - 2.0 / 3.0).abs() < 1e-9);
+        
+        // Trailing punctuation alone is no interruption, and no timing means none measured
+        let trailing = [("A", "I was going to-"), ("B", "Sure…"), ("A", "Well...")];
+        assert_eq!(turn_balance(&trailing, &[None; 3]).interruptions, 0.0);
+        
+        assert_eq!(turn_balance(&[("A", "Hello there")], &[None]), TurnBalance::default());
+    }
+}
@@ -0,0 +1,537 @@
+//! Snapshot compaction: roll aging snapshots into daily, weekly or monthly summaries
+//!
+//! Each snapshot goes to the coarsest period whose age it has reached, and a
+//! period is only rolled up once all of it is past that age. A summary keeps
+//! the averages, recurring themes, blind spots that stopped appearing and
+//! simple trends, plus the IDs and proof hashes of the snapshots it replaced.
+//! Later runs fold daily and weekly summaries into weekly and monthly ones as
+//! those age too; summaries keep theme and blind-spot counts so that merged
+//! averages, themes and resolved blind spots come out as if the snapshots had
+//! been rolled up together. Snapshots and summaries are read and written
+//! through `SnapshotStore`, so a directory and a SQLite database compact alike.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use crate::core::store::SnapshotStore;
+use crate::types::{
+    Snapshot, SnapshotQuery, SnapshotReason, CompactionSummary, CompactionPeriod, CompactionPolicy, CompactionReport,
+    BlindSpotCategory,
+};
+
+/// Subdirectory of a `DirStore` directory that holds summaries
+pub const COMPACTED_DIR: &str = "compacted";
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Change between the first and second half of a period that counts as a trend
+const TREND_MIN_CHANGE: f64 = 0.02;
+
+/// Share of snapshots a blind spot must appear in to count as persistent
+const PERSISTENT_SHARE: f64 = 0.75;
+
+/// Coarsest first: a snapshot old enough for a month never ends up in a day
+const PERIODS: [CompactionPeriod; 3] = [CompactionPeriod::Monthly, CompactionPeriod::Weekly, CompactionPeriod::Daily];
+
+/// Start and end (exclusive, Unix seconds) of the UTC period containing `timestamp`
+pub fn period_bounds(period: CompactionPeriod, timestamp: i64) -> (i64, i64) {
+    let date = DateTime::<Utc>::from_timestamp(timestamp, 0).unwrap_or_default().date_naive();
+    let (start, end) = match period {
+        CompactionPeriod::Daily => (date, date + chrono::Days::new(1)),
+        CompactionPeriod::Weekly => {
+            let monday = date - chrono::Days::new(date.weekday().num_days_from_monday() as u64);
+            (monday, monday + chrono::Days::new(7))
+        }
+        CompactionPeriod::Monthly => {
+            let first = date.with_day(1).unwrap_or(date);
+            (first, first + chrono::Months::new(1))
+        }
+    };
+    (unix(start), unix(end))
+}
+
+fn unix(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0).map_or(0, |t| t.and_utc().timestamp())
+}
+
+/// A summary to write: what it covers and what it replaces
+#[derive(Debug, Clone)]
+pub struct Rollup {
+    /// The summary of the whole period
+    pub summary: CompactionSummary,
+    /// Snapshots rolled up directly (IDs)
+    pub snapshots: Vec<String>,
+    /// Indices of the older, finer summaries folded in
+    pub merged: Vec<usize>,
+}
+
+/// Group the snapshots and older summaries that are old enough into summaries
+/// (oldest period first)
+///
+/// A daily or weekly summary whose week or month has reached its age is
+/// folded into that coarser summary, together with any snapshots or summaries
+/// of the same period. A week that straddles two months goes to the month it
+/// starts in. Summaries that stay where they are are not returned.
+pub fn compact_snapshots(
+    snapshots: &[Snapshot],
+    summaries: &[CompactionSummary],
+    policy: &CompactionPolicy,
+    now_unix: i64,
+) -> Vec<Rollup> {
+    // The coarsest period at least as coarse as `finest` that is old enough at `timestamp`
+    let target = |finest: CompactionPeriod, timestamp: i64| {
+        PERIODS.iter().filter(|&&p| p as u8 >= finest as u8).find_map(|&period| {
+            let days = policy.after_days(period)?;
+            let (start, end) = period_bounds(period, timestamp);
+            (now_unix - end >= days as i64 * SECS_PER_DAY).then_some((period, start, end))
+        })
+    };
+    
+    let mut groups: BTreeMap<(i64, u8), Group> = BTreeMap::new();
+    for snapshot in snapshots {
+        if let Some(period) = target(CompactionPeriod::Daily, snapshot.timestamp_unix) {
+            let group = Group::entry(&mut groups, period);
+            group.parts.push(Part::snapshot(snapshot));
+            group.snapshots.push(snapshot.id.clone());
+        }
+    }
+    for (i, summary) in summaries.iter().enumerate() {
+        if let Some(period) = target(summary.period, summary.period_start) {
+            let group = Group::entry(&mut groups, period);
+            group.parts.push(Part::summary(summary));
+            group.merged.push(i);
+        }
+    }
+    
+    groups.into_values()
+        // A summary alone in its own period is already done
+        .filter(|g| {
+            !g.snapshots.is_empty() || g.merged.len() > 1 || g.merged.iter().any(|&i| summaries[i].period != g.period)
+        })
+        .map(|g| Rollup {
+            summary: match g.parts.as_slice() {
+                // Same snapshots, coarser period: only the bounds change
+                [_] if g.snapshots.is_empty() => CompactionSummary {
+                    period: g.period,
+                    period_start: g.start,
+                    period_end: g.end,
+                    ..summaries[g.merged[0]].clone()
+                },
+                _ => summarize_parts(g.period, g.start, g.end, g.parts),
+            },
+            snapshots: g.snapshots,
+            merged: g.merged,
+        })
+        .collect()
+}
+
+/// What goes into one period's summary
+struct Group {
+    period: CompactionPeriod,
+    start: i64,
+    end: i64,
+    parts: Vec<Part>,
+    snapshots: Vec<String>,
+    merged: Vec<usize>,
+}
+
+impl Group {
+    fn entry(groups: &mut BTreeMap<(i64, u8), Group>, (period, start, end): (CompactionPeriod, i64, i64)) -> &mut Group {
+        groups.entry((start, period as u8)).or_insert_with(|| Group {
+            period,
+            start,
+            end,
+            parts: Vec::new(),
+            snapshots: Vec::new(),
+            merged: Vec::new(),
+        })
+    }
+}
+
+/// Summarize the snapshots of one period
+pub fn summarize(period: CompactionPeriod, start: i64, end: i64, snapshots: Vec<&Snapshot>) -> CompactionSummary {
+    summarize_parts(period, start, end, snapshots.into_iter().map(Part::snapshot).collect())
+}
+
+/// What a summary needs of a snapshot or of an older summary it absorbs
+#[derive(Debug, Clone)]
+struct Part {
+    /// Snapshot time or summary period start
+    at: i64,
+    count: u32,
+    sum_r: f64,
+    sum_dc: f64,
+    theme_counts: BTreeMap<String, u32>,
+    blind_spot_counts: Vec<(BlindSpotCategory, u32)>,
+    final_blind_spots: Vec<BlindSpotCategory>,
+    snapshot_ids: Vec<String>,
+    proof_hashes: Vec<String>,
+}
+
+impl Part {
+    fn snapshot(snapshot: &Snapshot) -> Self {
+        let mut spots: Vec<BlindSpotCategory> = Vec::new();
+        for spot in &snapshot.blind_spots {
+            if !spots.contains(&spot.category) {
+                spots.push(spot.category);
+            }
+        }
+        Self {
+            at: snapshot.timestamp_unix,
+            count: 1,
+            sum_r: snapshot.r_final,
+            sum_dc: snapshot.dc_final,
+            theme_counts: snapshot.seen.themes.iter().map(|t| (t.clone(), 1)).collect(),
+            blind_spot_counts: spots.iter().map(|&c| (c, 1)).collect(),
+            final_blind_spots: spots,
+            snapshot_ids: vec![snapshot.id.clone()],
+            proof_hashes: vec![snapshot.proof_hash_hex()],
+        }
+    }
+    
+    fn summary(summary: &CompactionSummary) -> Self {
+        let count = summary.snapshot_count;
+        Self {
+            at: summary.period_start,
+            count,
+            sum_r: summary.avg_r * count as f64,
+            sum_dc: summary.avg_dc * count as f64,
+            theme_counts: summary.theme_counts.clone(),
+            blind_spot_counts: summary.blind_spot_counts.clone(),
+            final_blind_spots: summary.final_blind_spots.clone(),
+            snapshot_ids: summary.snapshot_ids.clone(),
+            proof_hashes: summary.proof_hashes.clone(),
+        }
+    }
+}
+
+/// Summarize snapshots and older summaries of one period
+fn summarize_parts(period: CompactionPeriod, start: i64, end: i64, mut parts: Vec<Part>) -> CompactionSummary {
+    parts.sort_by_key(|p| p.at);
+    
+    let mut theme_counts: BTreeMap<String, u32> = BTreeMap::new();
+    let mut blind_spot_counts: Vec<(BlindSpotCategory, u32)> = Vec::new();
+    for part in &parts {
+        for (theme, count) in &part.theme_counts {
+            *theme_counts.entry(theme.clone()).or_insert(0) += count;
+        }
+        for &(category, count) in &part.blind_spot_counts {
+            match blind_spot_counts.iter_mut().find(|(c, _)| *c == category) {
+                Some((_, total)) => *total += count,
+                None => blind_spot_counts.push((category, count)),
+            }
+        }
+    }
+    let final_blind_spots = parts.last().map(|p| p.final_blind_spots.clone()).unwrap_or_default();
+    
+    CompactionSummary {
+        period,
+        period_start: start,
+        period_end: end,
+        snapshot_count: parts.iter().map(|p| p.count).sum(),
+        avg_r: mean(&parts, |p| p.sum_r),
+        avg_dc: mean(&parts, |p| p.sum_dc),
+        recurring_themes: recurring_themes(&theme_counts),
+        resolved_blind_spots: blind_spot_counts.iter()
+            .filter(|(c, _)| !final_blind_spots.contains(c))
+            .map(|(c, _)| c.description().to_string())
+            .collect(),
+        patterns: patterns(&parts, &blind_spot_counts),
+        snapshot_ids: parts.iter().flat_map(|p| p.snapshot_ids.iter().cloned()).collect(),
+        proof_hashes: parts.iter().flat_map(|p| p.proof_hashes.iter().cloned()).collect(),
+        theme_counts,
+        blind_spot_counts,
+        final_blind_spots,
+    }
+}
+
+type Metric = fn(&Part) -> f64;
+
+/// Mean per snapshot of a summed metric
+fn mean(parts: &[Part], sum: Metric) -> f64 {
+    parts.iter().map(sum).sum::<f64>() / parts.iter().map(|p| p.count).sum::<u32>().max(1) as f64
+}
+
+/// Themes seen in at least two snapshots, most frequent first
+fn recurring_themes(counts: &BTreeMap<String, u32>) -> Vec<String> {
+    let mut recurring: Vec<(&String, u32)> = counts.iter()
+        .map(|(theme, &count)| (theme, count))
+        .filter(|(_, c)| *c >= 2)
+        .collect();
+    recurring.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    recurring.into_iter().map(|(theme, _)| theme.clone()).collect()
+}
+
+/// Trends in r and ΔC, and blind spots that persist through the period
+///
+/// The first half of the snapshots is compared with the second; an older
+/// summary counts wholly to the half it starts in.
+fn patterns(parts: &[Part], blind_spot_counts: &[(BlindSpotCategory, u32)]) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let total: u32 = parts.iter().map(|p| p.count).sum();
+    let mut before = 0;
+    let split = parts.iter()
+        .position(|p| {
+            let late = before >= total / 2;
+            before += p.count;
+            late
+        })
+        .unwrap_or(parts.len());
+    let (early, late) = parts.split_at(split);
+    if !early.is_empty() && !late.is_empty() {
+        for (name, value) in [("r", (|p| p.sum_r) as Metric), ("ΔC", |p| p.sum_dc)] {
+            let (before, after) = (mean(early, value), mean(late, value));
+            if (after - before).abs() >= TREND_MIN_CHANGE {
+                let direction = if after < before { "falling" } else { "rising" };
+                patterns.push(format!("{} {} ({:.3} → {:.3})", name, direction, before, after));
+            }
+        }
+    }
+    
+    if total >= 2 {
+        for &(category, count) in blind_spot_counts {
+            if count as f64 >= total as f64 * PERSISTENT_SHARE {
+                patterns.push(format!(
+                    "Recurring blind spot: {} ({} of {})", category.description(), count, total
+                ));
+            }
+        }
+    }
+    patterns
+}
+
+/// Compact the snapshots in `store`: save summaries, fold older summaries into
+/// coarser ones, and remove what they replace
+///
+/// Only snapshots old enough for the finest period in use are read.
+pub fn compact_store(store: &dyn SnapshotStore, policy: &CompactionPolicy, now_unix: i64, dry_run: bool) -> Result<CompactionReport, SnapshotReason> {
+    let total = store.count(&SnapshotQuery::default())?;
+    let Some(min_days) = PERIODS.iter().filter_map(|&p| policy.after_days(p)).min() else {
+        return Ok(CompactionReport { kept: total, ..Default::default() });
+    };
+    let snapshots = store.query(&SnapshotQuery {
+        until: Some(now_unix - min_days as i64 * SECS_PER_DAY),
+        ..Default::default()
+    })?;
+    let summaries = store.summaries()?;
+    
+    let rollups = compact_snapshots(&snapshots, &summaries, policy, now_unix);
+    let mut report = CompactionReport {
+        summaries: rollups.iter().map(|r| r.summary.clone()).collect(),
+        compacted: rollups.iter().map(|r| r.snapshots.len()).sum(),
+        merged: rollups.iter().map(|r| r.merged.len()).sum(),
+        ..Default::default()
+    };
+    report.kept = total - report.compacted;
+    if dry_run {
+        return Ok(report);
+    }
+    
+    // Save every summary before removing anything it replaces
+    for summary in &report.summaries {
+        report.paths.push(store.save_summary(summary)?);
+    }
+    let written = |s: &CompactionSummary| {
+        report.summaries.iter().any(|w| (w.period, w.period_start) == (s.period, s.period_start))
+    };
+    for rollup in &rollups {
+        for summary in rollup.merged.iter().map(|&i| &summaries[i]).filter(|s| !written(s)) {
+            store.delete_summary(summary.period, summary.period_start)?;
+        }
+        for id in &rollup.snapshots {
+            store.delete(id)?;
+        }
+    }
+    Ok(report)
+}
+
+/// Run `compact_store` on `store` every `every`, as a background server job
+pub fn spawn_compaction_job(store: Arc<dyn SnapshotStore>, policy: CompactionPolicy, every: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            let store = store.clone();
+            let run = tokio::task::spawn_blocking(move || {
+                compact_store(store.as_ref(), &policy, Utc::now().timestamp(), false)
+            });
+            match run.await {
+                Ok(Ok(report)) if report.compacted + report.merged > 0 => println!(
+                    "🜂 Compacted {} snapshots into {} summaries", report.compacted, report.summaries.len()
+                ),
+                Ok(Err(e)) => eprintln!("Snapshot compaction failed: {}", e),
+                _ => {}
+            }
+        }
+    })
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::snapshot::test_snapshot;
+    
+    const DAY: i64 = SECS_PER_DAY;
+    
+    /// 2024-06-03 00:00 UTC, a Monday
+    const JUNE_3: i64 = 1_717_372_800;
+    
+    fn snapshot(id: &str, timestamp_unix: i64, r: f64, dc: f64, themes: &[&str], spots: &[BlindSpotCategory]) -> Snapshot {
+        Snapshot { r_final: r, dc_final: dc, ..test_snapshot(id, timestamp_unix, themes, spots) }
+    }
+    
+    #[test]
+    fn test_period_bounds() {
+        let wednesday = JUNE_3 + 2 * DAY + 3600;
+        assert_eq!(period_bounds(CompactionPeriod::Daily, wednesday), (JUNE_3 + 2 * DAY, JUNE_3 + 3 * DAY));
+        assert_eq!(period_bounds(CompactionPeriod::Weekly, wednesday), (JUNE_3, JUNE_3 + 7 * DAY));
+        assert_eq!(period_bounds(CompactionPeriod::Monthly, wednesday), (JUNE_3 - 2 * DAY, JUNE_3 + 28 * DAY));
+    }
+    
+    #[test]
+    fn test_snapshots_go_to_coarsest_aged_period() {
+        let now = JUNE_3 + 119 * DAY; // 2024-09-30
+        let snapshots = [
+            snapshot("june", JUNE_3 + 2 * DAY, 0.1, 0.1, &[], &[]),
+            snapshot("september", now - 20 * DAY, 0.1, 0.1, &[], &[]),
+            snapshot("young", now - 2 * DAY, 0.1, 0.1, &[], &[]),
+        ];
+        let rollups = compact_snapshots(&snapshots, &[], &CompactionPolicy::standard(), now);
+        let periods: Vec<(CompactionPeriod, &[String])> = rollups.iter()
+            .map(|r| (r.summary.period, r.summary.snapshot_ids.as_slice()))
+            .collect();
+        assert_eq!(periods, vec![
+            (CompactionPeriod::Monthly, &["june".to_string()][..]),
+            (CompactionPeriod::Daily, &["september".to_string()][..]),
+        ]);
+        
+        let off: CompactionPolicy = "off".parse().unwrap();
+        assert!(compact_snapshots(&snapshots, &[], &off, now).is_empty());
+    }
+    
+    #[test]
+    fn test_summary_keeps_themes_trends_and_resolved_blind_spots() {
+        use BlindSpotCategory::*;
+        let snapshots = [
+            snapshot("s1", JUNE_3, 0.20, 0.40, &["work", "family"], &[FutureAbsent, OthersAbsent]),
+            snapshot("s2", JUNE_3 + DAY, 0.18, 0.40, &["work", "work"], &[FutureAbsent, OthersAbsent]),
+            snapshot("s3", JUNE_3 + 2 * DAY, 0.10, 0.41, &["family", "work"], &[OthersAbsent]),
+            snapshot("s4", JUNE_3 + 3 * DAY, 0.08, 0.40, &["travel"], &[OthersAbsent]),
+        ];
+        let summary = summarize(CompactionPeriod::Weekly, JUNE_3, JUNE_3 + 7 * DAY, snapshots.iter().rev().collect());
+        
+        assert_eq!(summary.snapshot_count, 4);
+        assert!((summary.avg_r - 0.14).abs() < 1e-9);
+        assert_eq!(summary.snapshot_ids, ["s1", "s2", "s3", "s4"]);
+        assert_eq!(summary.proof_hashes[0], "ab".repeat(32));
+        assert_eq!(summary.recurring_themes, ["work", "family"]);
+        assert_eq!(summary.resolved_blind_spots, [FutureAbsent.description()]);
+        assert_eq!(summary.patterns, [
+            "r falling (0.190 → 0.090)".to_string(),
+            format!("Recurring blind spot: {} (4 of 4)", OthersAbsent.description()),
+        ]);
+    }
+    
+    #[test]
+    fn test_compact_dir_store_writes_summaries_and_removes_snapshots() {
+        use crate::core::DirStore;
+        let dir = std::env::temp_dir().join("soul0_compaction");
+        let _ = std::fs::remove_dir_all(&dir);
+        let dir = dir.to_str().unwrap();
+        let store = DirStore::new(dir);
+        let now = JUNE_3 + 20 * DAY;
+        for s in [
+            snapshot("old1", JUNE_3, 0.1, 0.3, &["work"], &[]),
+            snapshot("old2", JUNE_3 + 3600, 0.1, 0.3, &["work"], &[]),
+            snapshot("fresh", now - DAY, 0.1, 0.3, &[], &[]),
+        ] {
+            store.save(&s).unwrap();
+        }
+        
+        let dry = compact_store(&store, &CompactionPolicy::standard(), now, true).unwrap();
+        assert_eq!((dry.compacted, dry.kept), (2, 1));
+        assert!(dry.paths.is_empty());
+        assert!(std::path::Path::new(&format!("{}/old1.json", dir)).exists());
+        
+        let report = compact_store(&store, &CompactionPolicy::standard(), now, false).unwrap();
+        assert_eq!(report.paths, [format!("{}/{}/daily_2024-06-03.json", dir, COMPACTED_DIR)]);
+        assert!(!std::path::Path::new(&format!("{}/old1.json", dir)).exists());
+        assert!(std::path::Path::new(&format!("{}/fresh.json", dir)).exists());
+        
+        let written: CompactionSummary = serde_json::from_str(&std::fs::read_to_string(&report.paths[0]).unwrap()).unwrap();
+        assert_eq!(written.snapshot_ids, ["old1", "old2"]);
+        assert_eq!(written.recurring_themes, ["work"]);
+        
+        // Nothing left to compact; a missing directory is not an error
+        assert_eq!(compact_store(&store, &CompactionPolicy::standard(), now, false).unwrap().compacted, 0);
+        let missing = DirStore::new("/nonexistent/soul0");
+        assert_eq!(compact_store(&missing, &CompactionPolicy::standard(), now, false).unwrap().kept, 0);
+    }
+    
+    /// Compact the same snapshots four times as they age
+    fn check_repeated_compaction(store: &dyn SnapshotStore) {
+        use BlindSpotCategory::*;
+        let policy: CompactionPolicy = "daily=1,weekly=10,monthly=40".parse().unwrap();
+        let periods = || -> Vec<(CompactionPeriod, i64)> {
+            store.summaries().unwrap().iter().map(|s| (s.period, s.period_start)).collect()
+        };
+        
+        // Monday and Tuesday roll into two daily summaries
+        for s in [
+            snapshot("s1", JUNE_3 + 3600, 0.20, 0.40, &["work"], &[FutureAbsent, OthersAbsent]),
+            snapshot("s2", JUNE_3 + 7200, 0.20, 0.40, &["work"], &[FutureAbsent]),
+            snapshot("s3", JUNE_3 + DAY, 0.10, 0.30, &["family", "work"], &[]),
+            snapshot("s4", JUNE_3 + DAY + 3600, 0.10, 0.30, &["family"], &[OthersAbsent]),
+        ] {
+            store.save(&s).unwrap();
+        }
+        let first = compact_store(store, &policy, JUNE_3 + 3 * DAY, false).unwrap();
+        assert_eq!((first.compacted, first.merged, first.summaries.len()), (4, 0, 2));
+        assert_eq!(first.summaries[0].resolved_blind_spots, [OthersAbsent.description()]);
+        assert_eq!(first.summaries[1].recurring_themes, ["family"]);
+        assert_eq!(periods(), [(CompactionPeriod::Daily, JUNE_3), (CompactionPeriod::Daily, JUNE_3 + DAY)]);
+        
+        // A late snapshot and both days fold into the week once it has aged
+        store.save(&snapshot("s5", JUNE_3 + 2 * DAY, 0.10, 0.30, &["work"], &[OthersAbsent])).unwrap();
+        let second = compact_store(store, &policy, JUNE_3 + 17 * DAY, false).unwrap();
+        assert_eq!((second.compacted, second.merged, second.kept), (1, 2, 0));
+        let week = &second.summaries[0];
+        assert_eq!((week.period, week.period_start, week.period_end), (CompactionPeriod::Weekly, JUNE_3, JUNE_3 + 7 * DAY));
+        assert_eq!(week.snapshot_count, 5);
+        assert!((week.avg_r - 0.14).abs() < 1e-9 && (week.avg_dc - 0.34).abs() < 1e-9);
+        assert_eq!(week.snapshot_ids, ["s1", "s2", "s3", "s4", "s5"]);
+        assert_eq!(week.proof_hashes.len(), 5);
+        assert_eq!(week.recurring_themes, ["work", "family"]);
+        // Resolved over the whole week: OthersAbsent came back after Monday
+        assert_eq!(week.resolved_blind_spots, [FutureAbsent.description()]);
+        assert_eq!(week.patterns, ["r falling (0.200 → 0.100)", "ΔC falling (0.400 → 0.300)"]);
+        assert_eq!(periods(), [(CompactionPeriod::Weekly, JUNE_3)]);
+        assert_eq!(store.count(&SnapshotQuery::default()).unwrap(), 0);
+        
+        // The week becomes the month, unchanged but for its bounds
+        let third = compact_store(store, &policy, JUNE_3 + 80 * DAY, false).unwrap();
+        assert_eq!((third.compacted, third.merged), (0, 1));
+        let month = &third.summaries[0];
+        assert_eq!((month.period, month.period_start), (CompactionPeriod::Monthly, JUNE_3 - 2 * DAY));
+        assert_eq!(month.snapshot_ids, week.snapshot_ids);
+        assert_eq!(month.resolved_blind_spots, week.resolved_blind_spots);
+        assert_eq!(periods(), [(CompactionPeriod::Monthly, JUNE_3 - 2 * DAY)]);
+        
+        // Nothing left to do
+        let fourth = compact_store(store, &policy, JUNE_3 + 80 * DAY, false).unwrap();
+        assert_eq!((fourth.compacted, fourth.merged, fourth.summaries.len()), (0, 0, 0));
+    }
+    
+    #[test]
+    fn test_repeated_compaction_cascades() {
+        let dir = std::env::temp_dir().join("soul0_compaction_cascade");
+        let _ = std::fs::remove_dir_all(&dir);
+        check_repeated_compaction(&crate::core::DirStore::new(dir.to_str().unwrap()));
+        check_repeated_compaction(&crate::core::SqliteStore::open(":memory:").unwrap());
+    }
+}
@@ -0,0 +1,399 @@
+//! Conflict avoidance: was a disagreement raised and then sidestepped?
+//!
+//! Three lexical cues, EN and NL, each counted at most once per turn:
+//! - topic escape: a disagreement ("I don't agree", "klopt niet") whose reply
+//!   breaks off (`logical_breaks`) and shares no content terms with it
+//! - hedged challenge: a disagreement or pointed question answered with a
+//!   brush-off ("whatever", "forget it", "laat maar"), or a pointed question
+//!   nobody answered before the speaker went on
+//! - dropped judgment: a turn whose r (from the session's `RScorer`) spikes
+//!   on judgment words ("wrong", "stom") that no other speaker picks up
+//!
+//! A reply that pushes back itself is engagement, not avoidance. The markers
+//! leave out everyday softeners ("maybe", "I guess", "prima") and neutral
+//! questions ("why did you", "waarom heb je"), and one cue alone stays below
+//! `CONFLICT_THRESHOLD` (see `ConflictAvoidance::confidence`).
+
+use std::collections::HashSet;
+use crate::core::dc_parser::logical_breaks;
+use crate::core::question::question_kind;
+use crate::core::scorer::RScorer;
+use crate::core::thematic::content_terms;
+use crate::types::{ConflictAvoidance, Turn};
+
+/// Confidence from which the snapshot reports the blind spot (two cues or more;
+/// any value between the single-cue cap and 0.8 separates the labelled set)
+pub const CONFLICT_THRESHOLD: f64 = 0.5;
+
+/// How far above the other turns' mean r a judgment turn must be to count as a spike
+/// (`RParser` puts a judgment-laden sentence at r ≈ 0.04–0.08)
+const JUDGMENT_SPIKE: f64 = 0.03;
+
+/// Pushback and disagreement (matched as whole words or phrases)
+const DISAGREEMENT_MARKERS: &[&str] = &[
+    "i disagree", "i don't agree", "i do not agree", "don't agree", "not true", "that's not true",
+    "that's not fair", "that's not what", "you're wrong", "you are wrong", "that's wrong",
+    "not fair", "that's unfair", "i don't think so", "you always", "you never",
+    "oneens", "niet mee eens", "klopt niet", "niet waar", "je hebt ongelijk", "jij hebt ongelijk",
+    "niet eerlijk", "je doet altijd", "jij altijd", "je altijd", "je nooit", "jij nooit",
+    "dat vind ik niet",
+];
+
+/// Replies that brush the point off rather than respond
+const HEDGE_MARKERS: &[&str] = &[
+    "whatever", "never mind", "nevermind", "let's not", "doesn't matter", "it's nothing",
+    "forget it", "drop it", "let it go", "i don't want to talk about it",
+    "laat maar", "maakt niet uit", "zal wel", "boeit niet", "laat het rusten",
+    "laten we het er niet over hebben",
+];
+
+/// Count the cues of sidestepped conflict in a window (oldest first),
+/// finding judgment spikes with `r_scorer`
+pub fn conflict_avoidance(turns: &[&Turn], r_scorer: &dyn RScorer) -> ConflictAvoidance {
+    let lower: Vec<String> = turns.iter().map(|t| t.text.to_lowercase()).collect();
+    let terms: Vec<HashSet<String>> = turns.iter().map(|t| content_terms(&t.text).into_iter().collect()).collect();
+    let texts: Vec<&str> = turns.iter().map(|t| t.text.as_str()).collect();
+    let judgmental: Vec<bool> = r_scorer.parse_many(&texts).iter().map(|r| r.signals.judgment > 0.0).collect();
+    let disagrees: Vec<bool> = lower.iter().map(|t| has_any(t, DISAGREEMENT_MARKERS)).collect();
+    let r_sum: f64 = turns.iter().map(|t| t.r).sum();
+    
+    let mut result = ConflictAvoidance::default();
+    for (i, turn) in turns.iter().enumerate() {
+        let reply = (i + 1..turns.len()).find(|&j| turns[j].speaker != turn.speaker);
+        let engaged = reply.is_some_and(|j| disagrees[j]);
+        let shares_terms = |j: usize| !terms[i].is_disjoint(&terms[j]);
+        let pointed_question = question_kind(&turn.text).is_some() && (disagrees[i] || judgmental[i]);
+        
+        if disagrees[i] && !engaged {
+            if let Some(j) = reply {
+                // A hedge is the clearer cue ("laat maar" also reads as a "maar" break)
+                if has_any(&lower[j], HEDGE_MARKERS) {
+                    result.hedged_challenges += 1;
+                    continue;
+                }
+                if logical_breaks(&turn.text, &turns[j].text) > 0.0 && !shares_terms(j) {
+                    result.topic_escapes += 1;
+                    continue;
+                }
+            }
+        }
+        
+        // A pointed question the speaker had to move on from
+        if pointed_question && reply.is_none() && turns[i + 1..].iter().any(|t| t.speaker == turn.speaker) {
+            result.hedged_challenges += 1;
+            continue;
+        }
+        
+        // Judgment spike that no one else returns to
+        if judgmental[i] && !engaged && turns.len() > 1 {
+            let others_mean = (r_sum - turn.r) / (turns.len() - 1) as f64;
+            let picked_up = (i + 1..turns.len()).any(|j| turns[j].speaker != turn.speaker && shares_terms(j));
+            if turn.r >= others_mean + JUDGMENT_SPIKE && reply.is_some() && !picked_up {
+                result.dropped_judgments += 1;
+            }
+        }
+    }
+    result
+}
+
+/// Does lowercase text contain any of the phrases as whole words?
+fn has_any(text: &str, phrases: &[&str]) -> bool {
+    phrases.iter().any(|phrase| {
+        text.match_indices(phrase).any(|(at, _)| {
+            let before = text[..at].chars().next_back();
+            let after = text[at + phrase.len()..].chars().next();
+            !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+        })
+    })
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::RParser;
+    use crate::types::{RSignals, RValue};
+    
+    fn detect(turns: &[(&str, &str, f64)]) -> ConflictAvoidance {
+        detect_with(turns, &RParser::new())
+    }
+    
+    fn detect_with(turns: &[(&str, &str, f64)], r_scorer: &dyn RScorer) -> ConflictAvoidance {
+        let turns: Vec<Turn> = turns.iter().map(|(s, t, r)| Turn::new(*s, *t, *r)).collect();
+        conflict_avoidance(&turns.iter().collect::<Vec<_>>(), r_scorer)
+    }
+    
+    /// Scorer that finds no judgment words at all
+    #[derive(Debug)]
+    struct NoJudgment;
+    
+    impl RScorer for NoJudgment {
+        fn parse(&self, text: &str) -> RValue {
+            RValue::new(0.0, RSignals::zero(), 1.0, text.split_whitespace().count())
+        }
+    }
+    
+    #[test]
+    fn test_disagreement_met_with_topic_change() {
+        let result = detect(&[
+            ("A", "I don't agree with how you handled the budget meeting.", 0.12),
+            ("B", "Anyway, did you see the football match yesterday?", 0.05),
+            ("A", "Yes, it was a good game.", 0.05),
+        ]);
+        assert_eq!(result.topic_escapes, 1);
+        // One cue alone is not enough to report
+        assert!(result.confidence() < CONFLICT_THRESHOLD);
+    }
+    
+    #[test]
+    fn test_repeated_escapes_pass_threshold() {
+        let result = detect(&[
+            ("A", "I don't agree with how you handled the budget meeting.", 0.12),
+            ("B", "Anyway, did you see the football match yesterday?", 0.05),
+            ("A", "That's not fair, you always decide the budget alone.", 0.15),
+            ("B", "Whatever. Shall we order pizza tonight?", 0.05),
+        ]);
+        assert_eq!(result.cues(), 2);
+        assert!(result.confidence() >= CONFLICT_THRESHOLD);
+    }
+    
+    #[test]
+    fn test_dutch_challenge_hedged() {
+        let result = detect(&[
+            ("A", "Dat klopt niet, jij hebt de afspraak vergeten.", 0.15),
+            ("B", "Laat maar, het maakt niet uit.", 0.05),
+            ("A", "Waarom doe je altijd alsof het niets is?", 0.2),
+            ("A", "Goed, we eten om zes uur.", 0.05),
+        ]);
+        assert_eq!(result.hedged_challenges, 2);
+        assert!(result.confidence() > 0.5);
+    }
+    
+    #[test]
+    fn test_dropped_judgment_alone_stays_below_threshold() {
+        let turns = [
+            ("A", "That plan was stupid and the whole thing was wrong.", 0.45),
+            ("B", "The weather looks nice for a walk.", 0.02),
+            ("A", "Sure, let us walk to the park.", 0.03),
+        ];
+        let result = detect(&turns);
+        assert_eq!(result, ConflictAvoidance { dropped_judgments: 1, ..Default::default() });
+        assert!(result.confidence() < CONFLICT_THRESHOLD);
+        
+        // Judgment comes from the scorer the session was given
+        assert_eq!(detect_with(&turns, &NoJudgment), ConflictAvoidance::default());
+    }
+    
+    #[test]
+    fn test_engaged_disagreement_is_not_avoidance() {
+        // B pushes back, and the calm exchange has no cues at all
+        let argued = detect(&[
+            ("A", "I don't agree with the new schedule.", 0.12),
+            ("B", "That's not fair, the schedule was agreed last week.", 0.14),
+        ]);
+        assert_eq!(argued, ConflictAvoidance::default());
+        
+        let calm = detect(&[
+            ("A", "De lucht is blauw vandaag.", 0.02),
+            ("B", "Ja, heel helder en rustig.", 0.02),
+        ]);
+        assert_eq!(calm.confidence(), 0.0);
+    }
+    
+    #[test]
+    fn test_benign_questions_and_answers() {
+        let exchanges: [&[(&str, &str, f64)]; 4] = [
+            &[
+                ("A", "Why did you choose the blue one?", 0.05),
+                ("B", "I guess it matched the curtains.", 0.04),
+            ],
+            &[
+                ("A", "Why didn't you take the train? Maybe it was late.", 0.05),
+                ("B", "Sort of, it was fine but kind of slow.", 0.04),
+                ("A", "That's not bad for a Monday.", 0.05),
+            ],
+            &[
+                ("A", "Waarom heb je de rode gekozen?", 0.05),
+                ("B", "Misschien omdat hij prima bij de bank past.", 0.04),
+            ],
+            &[
+                ("A", "Waarom deed je de ramen open?", 0.05),
+                ("B", "Geen idee, het is goed zo. We zien wel.", 0.04),
+                ("A", "Dat is niet erg, het is warm.", 0.05),
+            ],
+        ];
+        for turns in exchanges {
+            assert_eq!(detect(turns), ConflictAvoidance::default(), "{:?}", turns);
+        }
+    }
+    
+    #[test]
+    fn test_markers_match_whole_words() {
+        assert!(has_any("well, i disagree.", DISAGREEMENT_MARKERS));
+        assert!(!has_any("forget items on the list", HEDGE_MARKERS));
+        assert!(has_any("forget it.", HEDGE_MARKERS));
+        assert!(!has_any("fine, maybe.", HEDGE_MARKERS));
+    }
+    
+    /// Hand-labelled conversations: was a disagreement sidestepped?
+    const LABELLED: &[(bool, &[(&str, &str)])] = &[
+        // Sidestepped
+        (true, &[
+            ("A", "I don't agree with how you handled the budget meeting."),
+            ("B", "Anyway, did you see the football match yesterday?"),
+            ("A", "That's not fair, you always decide the budget alone."),
+            ("B", "Whatever. Shall we order pizza tonight?"),
+        ]),
+        (true, &[
+            ("A", "You never listen when I talk about my job."),
+            ("B", "Forget it, what's for dinner?"),
+            ("A", "You always change the subject, why?"),
+            ("A", "Fine, pasta then."),
+        ]),
+        (true, &[
+            ("A", "That was a stupid decision and you know it was wrong."),
+            ("B", "Let's not. The weather is nice today."),
+            ("A", "I disagree with the whole plan."),
+            ("B", "Anyway, did you feed the cat?"),
+        ]),
+        (true, &[
+            ("A", "I don't think so, you promised to call my mother."),
+            ("B", "Doesn't matter. Is the car back from the garage?"),
+            ("A", "Yes, it's fixed."),
+            ("A", "You never call her, you always forget."),
+            ("B", "Drop it."),
+        ]),
+        (true, &[
+            ("A", "You're wrong about the money, I paid the rent."),
+            ("B", "Never mind. How was work?"),
+            ("A", "Work was busy."),
+        ]),
+        (true, &[
+            ("A", "Dat klopt niet, jij hebt de afspraak vergeten."),
+            ("B", "Laat maar, het maakt niet uit."),
+            ("A", "Waarom doe je altijd alsof het niets is?"),
+            ("A", "Goed, we eten om zes uur."),
+        ]),
+        (true, &[
+            ("A", "Dat is niet eerlijk, jij hebt de boodschappen weer vergeten."),
+            ("B", "Trouwens, heb je het weerbericht voor morgen gezien?"),
+            ("A", "Dat klopt niet, je doet altijd alsof het niets is."),
+            ("B", "Laat maar, dan gaan we fietsen."),
+        ]),
+        (true, &[
+            ("A", "Ik ben het daar niet mee eens, je hebt ongelijk over het geld."),
+            ("B", "Zal wel. Zullen we koffie halen?"),
+            ("A", "Je doet altijd alsof ik er niet ben."),
+            ("B", "Boeit niet. Wat eten we vanavond?"),
+        ]),
+        (true, &[
+            ("A", "Dat was een stomme keuze en het was gewoon fout."),
+            ("B", "Het weer is mooi vandaag."),
+            ("A", "Ja, heel zonnig."),
+        ]),
+        // Not sidestepped
+        (false, &[
+            ("A", "Why did you choose the blue one?"),
+            ("B", "I guess it matched the curtains."),
+        ]),
+        (false, &[
+            ("A", "I don't agree with the new schedule."),
+            ("B", "That's not fair, the schedule was agreed last week."),
+            ("A", "You're wrong, it changed on Monday."),
+            ("B", "I disagree, the mail on Monday said the opposite."),
+        ]),
+        (false, &[
+            ("A", "That movie was terrible and the plot was stupid."),
+            ("B", "The plot was stupid indeed, the movie dragged on."),
+        ]),
+        (false, &[
+            ("A", "I don't agree that the garden needs new plants."),
+            ("B", "But the plants in the garden died in winter."),
+        ]),
+        (false, &[
+            ("A", "You always leave the lights on, why?"),
+            ("B", "Whatever, I'll switch the lights off now."),
+            ("A", "Thanks, that helps."),
+        ]),
+        (false, &[
+            ("A", "That's not true, the shop closes at six."),
+            ("B", "Anyway, let's go now then."),
+            ("A", "Good idea, I'll get my coat."),
+        ]),
+        (false, &[
+            ("A", "The food there was awful and simply wrong."),
+            ("B", "Let's try the new place on Friday."),
+            ("A", "Sure, Friday works."),
+        ]),
+        (false, &[
+            ("A", "Waarom heb je de rode gekozen?"),
+            ("B", "Misschien omdat hij prima bij de bank past."),
+        ]),
+        (false, &[
+            ("A", "Dat klopt niet, de trein ging om acht uur."),
+            ("B", "Niet waar, de trein ging om negen uur."),
+        ]),
+        (false, &[
+            ("A", "De lucht is blauw vandaag."),
+            ("B", "Ja, heel helder en rustig."),
+        ]),
+        (false, &[
+            ("A", "Je laat altijd de deur open, waarom?"),
+            ("B", "Laat maar, ik doe hem nu dicht."),
+            ("A", "Dank je, fijn."),
+        ]),
+        (false, &[
+            ("A", "Dat is niet waar, de bakker is morgen open."),
+            ("B", "Trouwens, zullen we dan samen gaan?"),
+            ("A", "Ja, goed plan."),
+        ]),
+    ];
+    
+    /// Share of the conversations matching `filter` that are labelled
+    /// sidestepped, Laplace-smoothed
+    fn smoothed_share(counted: &[(bool, ConflictAvoidance)], filter: impl Fn(&ConflictAvoidance) -> bool) -> f64 {
+        let matching: Vec<bool> = counted.iter().filter(|(_, c)| filter(c)).map(|(label, _)| *label).collect();
+        let sidestepped = matching.iter().filter(|&&label| label).count();
+        (sidestepped as f64 + 1.0) / (matching.len() as f64 + 2.0)
+    }
+    
+    #[test]
+    fn test_calibration_on_labelled_set() {
+        use crate::types::{
+            CONFLICT_MAX_CONFIDENCE, CONFLICT_SINGLE_CUE_MAX, CONFLICT_WEIGHT_DROPPED,
+            CONFLICT_WEIGHT_HEDGED, CONFLICT_WEIGHT_TOPIC_ESCAPE,
+        };
+        
+        let parser = RParser::new();
+        let counted: Vec<(bool, ConflictAvoidance)> = LABELLED.iter().map(|(label, turns)| {
+            let turns: Vec<Turn> = turns.iter().map(|(s, t)| Turn::new(*s, *t, parser.quick_parse(t))).collect();
+            (*label, conflict_avoidance(&turns.iter().collect::<Vec<_>>(), &parser))
+        }).collect();
+        
+        // Weights and caps are what the labelled set gives
+        let weight = |p: f64| -(1.0 - p).ln();
+        let fitted = [
+            (CONFLICT_WEIGHT_TOPIC_ESCAPE, weight(smoothed_share(&counted, |c| c.topic_escapes > 0))),
+            (CONFLICT_WEIGHT_HEDGED, weight(smoothed_share(&counted, |c| c.hedged_challenges > 0))),
+            (CONFLICT_WEIGHT_DROPPED, weight(smoothed_share(&counted, |c| c.dropped_judgments > 0))),
+            (CONFLICT_SINGLE_CUE_MAX, smoothed_share(&counted, |c| c.cues() == 1)),
+            (CONFLICT_MAX_CONFIDENCE, smoothed_share(&counted, |c| c.cues() >= 2)),
+        ];
+        for (constant, value) in fitted {
+            assert!((constant - value).abs() < 0.005, "constant {constant}, fitted {value:.3}");
+        }
+        
+        // The threshold flags no calm or engaged conversation
+        for (label, c) in &counted {
+            if !label {
+                assert!(c.confidence() < CONFLICT_THRESHOLD, "{c:?}");
+            }
+        }
+        // and every sidestepped one except the two with a single cue
+        let missed = counted.iter().filter(|(label, c)| *label && c.confidence() < CONFLICT_THRESHOLD).count();
+        assert_eq!(missed, 2);
+    }
+}
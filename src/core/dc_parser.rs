@@ -2,245 +2,1194 @@
 //!
 //! Based on LLD v1.0 with 5 signals and TURN_MODEL semantics.
 //! ΔC = UNKNOWN when conversation structure is invalid.
+//! Thematic drift uses TF-IDF cosine by default (see `thematic`); the original
+//! word-overlap heuristic stays available as `ThematicModel::Jaccard`.
+//! Emotional volatility is the valence/arousal swing between paired turns
+//! (see `sentiment`). Q&A mismatch detects questions without a '?' (see
+//! `question`) and scores how relevant the next turns are as an answer.
+//! Reference decay follows every referent through the window (see `referents`).
+//! With more than two speakers, ΔC is also computed per speaker pair (a
+//! coherence matrix) and per speaker, and `GroupAggregation` decides how
+//! the group's ΔC is formed from them.
+//! Turn-taking balance (see `balance`) is a sixth signal on top of the five
+//! LLD weights, so `with_balance_weight(0.0)` gives the five-signal ΔC.
+//! Function-word style matching (see `style`) takes a share of the thematic
+//! weight: speakers in sync on a changing topic are not scored as incoherent.
+//! Turn timing (see `timing`) is always reported and only counts towards ΔC
+//! with a non-zero `with_timing_weight`.
+//!
+//! A `DcState` keeps every turn of one window as it was scored when it
+//! entered: features, TF-IDF vector, the signals of the pair it closes, and
+//! running sums per window, speaker and speaker pair. `calculate_with` brings
+//! it in step with the window, so a new turn is extracted and paired once
+//! and, apart from TF-IDF, ΔC costs the same however long the window is.
+//! TF-IDF vectors, the context each reply is compared with and the answer
+//! scores that use them follow the current window: with IDF from the window,
+//! every turn in or out re-weights them all, which costs a pass over the
+//! window. `calculate` scores a window from an empty state.
 
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Instant;
+use aho_corasick::AhoCorasick;
+use lazy_static::lazy_static;
+use crate::core::balance::balance_from_counts;
+use crate::core::question::{QuestionKind, question_kind};
+use crate::core::referents::{referents, ReferentTracker};
+use crate::core::sentiment::{Sentiment, score_sentiment};
+use crate::core::style::{StyleProfile, lsm, style_match};
+use crate::core::timing::{gap_ms, TimingTracker};
+use crate::core::thematic::{
+    ThematicModel, IdfCorpus, TermVector, RunningCentroid, content_terms, tfidf_vector, cosine,
+};
 use crate::types::{
-    ConversationWindow, TurnPair,
-    DcSignals, DcResult, DcReason,
+    ConversationWindow, Turn, DcSignals, DcResult, DcReason, TurnSentiment, TurnBalance,
+    PairContribution, GroupAggregation, SpeakerPairDc, SpeakerCoherence, DcWeights, StyleMatch,
+    StyleCategoryMatch,
 };
 
-/// ΔC Parser for measuring coherence between speakers
+/// Emotional intensity markers (counted, non-overlapping per marker); raise arousal
+const EMOTIONAL_MARKERS: &[&str] = &[
+    "!", "?!", "...", 
+    "wow", "amazing", "terrible", "hate", "love", "angry",
+    "happy", "sad", "excited", "frustrated", "annoyed",
+    "wauw", "geweldig", "verschrikkelijk", "haat", "boos",
+    "blij", "verdrietig", "gefrustreerd",
+];
+
+/// Transition words signalling an abrupt switch
+const TRANSITION_WORDS: &[&str] = &[
+    "but", "however", "although", "anyway", "by the way",
+    "speaking of", "that reminds me", "off topic",
+    "maar", "echter", "overigens", "trouwens",
+];
+
+/// Words suggesting a response addresses a question (whole words only)
+const ANSWER_INDICATORS: &[&str] = &[
+    "yes", "no", "ja", "nee", "because", "omdat",
+    "i think", "ik denk", "maybe", "misschien",
+];
+
+/// How many replies by other speakers after a question may answer it
+const ANSWER_LOOKAHEAD: usize = 2;
+
+/// First seq of an empty `DcState` (turns enter at the front as well)
+const FIRST_SEQ: u64 = 1 << 62;
+
+/// Which list a marker pattern belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MarkerKind {
+    Emotional,
+    Transition,
+    Answer,
+}
+
+lazy_static! {
+    /// All marker lists in one automaton; pattern ids follow `MARKER_KINDS`
+    static ref MARKERS: AhoCorasick = AhoCorasick::new(
+        EMOTIONAL_MARKERS.iter().chain(TRANSITION_WORDS).chain(ANSWER_INDICATORS)
+    ).unwrap();
+    
+    /// Tag per pattern id
+    static ref MARKER_KINDS: Vec<MarkerKind> = EMOTIONAL_MARKERS.iter().map(|_| MarkerKind::Emotional)
+        .chain(TRANSITION_WORDS.iter().map(|_| MarkerKind::Transition))
+        .chain(ANSWER_INDICATORS.iter().map(|_| MarkerKind::Answer))
+        .collect();
+}
+
+/// Everything the signals need from one turn's text, extracted in a single pass
 #[derive(Debug, Default)]
-pub struct DcParser;
+struct TurnFeatures {
+    /// Emotional marker occurrences
+    emotional: usize,
+    /// Lexicon valence/arousal
+    sentiment: Sentiment,
+    /// Distinct transition words contained
+    transitions: usize,
+    /// Contains an answer indicator as a whole word
+    has_answer_indicator: bool,
+    /// Question type, if the turn asks one
+    question: Option<QuestionKind>,
+    /// Whitespace-separated words
+    word_count: usize,
+    /// Lowercased words longer than 2 chars (thematic drift)
+    thematic_words: HashSet<String>,
+    /// Stemmed content terms (TF-IDF thematic drift)
+    terms: Vec<String>,
+    /// Function-word counts (style matching)
+    style: StyleProfile,
+    /// Referents raised or mentioned (reference decay)
+    referents: Vec<(String, String)>,
+}
 
-impl DcParser {
-    /// Create new parser
-    pub fn new() -> Self {
-        Self
+impl TurnFeatures {
+    /// Arousal from the lexicon, or from emotional markers ("wow", "?!") if higher
+    fn arousal(&self) -> f64 {
+        self.sentiment.arousal.max((self.emotional as f64 * 0.2).min(1.0))
     }
     
-    /// Calculate ΔC from conversation window
-    /// Returns DcResult with value or UNKNOWN reason
-    pub fn calculate(&self, window: &ConversationWindow) -> DcResult {
-        // Check preconditions per TURN_MODEL
+    fn extract(text: &str) -> Self {
+        let lower = text.to_lowercase();
         
-        // Need at least 2 turns
-        if window.len() < 2 {
-            return DcResult::unknown(DcReason::R012_DC_UNKNOWN_INSUFFICIENT_TURNS);
-        }
+        let mut emotional = 0;
+        let mut transitions = 0;
+        let mut has_answer_indicator = false;
+        // End of last counted match per pattern (str::matches is non-overlapping)
+        let mut last_end = vec![0usize; MARKER_KINDS.len()];
+        let mut seen = vec![false; MARKER_KINDS.len()];
         
-        // Need at least 2 speakers
-        if window.speaker_count() < 2 {
-            return DcResult::unknown(DcReason::R011_DC_UNKNOWN_SINGLE_SPEAKER);
+        for m in MARKERS.find_overlapping_iter(&lower) {
+            let id = m.pattern().as_usize();
+            match MARKER_KINDS[id] {
+                MarkerKind::Emotional => {
+                    if m.start() >= last_end[id] {
+                        emotional += 1;
+                        last_end[id] = m.end();
+                    }
+                }
+                MarkerKind::Transition => {
+                    if !seen[id] {
+                        transitions += 1;
+                    }
+                }
+                MarkerKind::Answer => {
+                    // "yes" in "yesterday" or "no" in "know" is not an answer
+                    if is_whole_word(&lower, m.start(), m.end()) {
+                        has_answer_indicator = true;
+                    }
+                }
+            }
+            seen[id] = true;
         }
         
-        // Need at least 1 pair
-        let pairs = window.paired_turns();
-        if pairs.is_empty() {
-            return DcResult::unknown(DcReason::R016_DC_UNKNOWN_NO_PAIRS);
+        let words_longer_than = |n: usize| -> HashSet<String> {
+            lower.split_whitespace()
+                .filter(|w| w.len() > n)
+                .map(String::from)
+                .collect()
+        };
+        
+        Self {
+            emotional,
+            sentiment: score_sentiment(text),
+            transitions,
+            has_answer_indicator,
+            question: question_kind(text),
+            word_count: text.split_whitespace().count(),
+            thematic_words: words_longer_than(2),
+            terms: content_terms(text),
+            style: StyleProfile::from_text(text),
+            referents: referents(text),
+        }
+    }
+}
+
+/// A logical turn of the window, scored once when it entered
+#[derive(Debug)]
+struct WindowTurn {
+    /// Id in `ConversationWindow::turn_ids`
+    id: u64,
+    /// Position in the state, growing from the oldest turn to the newest
+    seq: u64,
+    /// Speaker and text are shared with every result's per-turn details
+    speaker: Arc<str>,
+    text: Arc<str>,
+    timestamp: Option<Instant>,
+    features: TurnFeatures,
+    /// TF-IDF vector, weighted by the current window (or the corpus)
+    vector: TermVector,
+    /// Cosine with the centroid of the turns before it
+    context_similarity: f64,
+    /// Best answer relevance among the following turns (questions only)
+    answer_relevance: Option<f64>,
+    /// Replies by other speakers scored as answers so far (questions only)
+    answers: usize,
+    /// Questions this turn answered (seq), with their relevance before
+    answered: Vec<(u64, Option<f64>)>,
+    /// Gap after the previous turn
+    gap_ms: Option<u64>,
+    /// Signals of the pair this turn closes, if the previous turn is by someone else
+    pair: Option<DcSignals>,
+}
+
+impl WindowTurn {
+    fn new(id: u64, seq: u64, turn: &Turn, features: TurnFeatures) -> Self {
+        Self {
+            id,
+            seq,
+            speaker: Arc::from(turn.speaker.as_str()),
+            text: Arc::from(turn.text.as_str()),
+            timestamp: turn.timestamp,
+            features,
+            vector: TermVector::new(),
+            context_similarity: 0.0,
+            answer_relevance: None,
+            answers: 0,
+            answered: Vec::new(),
+            gap_ms: None,
+            pair: None,
         }
+    }
+}
+
+impl Deref for WindowTurn {
+    type Target = TurnFeatures;
+    
+    fn deref(&self) -> &TurnFeatures {
+        &self.features
+    }
+}
+
+/// A pair of turns (first speaker, then the other)
+type FeaturePair<'a> = (&'a WindowTurn, &'a WindowTurn);
+
+/// Exchanges between two speakers in the window
+#[derive(Debug, Default)]
+struct SpeakerPairExchanges {
+    /// Seq of the reply and (first, second) speaker of each exchange, oldest first
+    exchanges: VecDeque<(u64, [String; 2])>,
+    /// Sums of their pair signals
+    sums: DcSignals,
+}
+
+/// One speaker's part of the window
+#[derive(Debug, Default)]
+struct SpeakerShare {
+    /// Their turns (seq), oldest first
+    turns: VecDeque<u64>,
+    words: usize,
+    style: StyleProfile,
+    /// Exchanges they took part in, and the sums of their pair signals
+    exchanges: usize,
+    sums: DcSignals,
+}
+
+/// Incremental ΔC state of one window
+///
+/// Holds the window's logical turns as they were scored when they entered,
+/// the signals of every pair and running sums per window, speaker and
+/// speaker pair. `DcParser::calculate_with` brings it in step with the
+/// window: turns that left are taken out of the sums and only turns that
+/// entered (or changed by merging) are extracted and paired. Keep one state
+/// per window and parser, e.g. per session and window scale.
+#[derive(Debug, Default)]
+pub struct DcState {
+    turns: VecDeque<WindowTurn>,
+    /// Seq of the newest and the oldest turn pushed since the state was empty
+    back_seq: u64,
+    front_seq: u64,
+    /// Document frequencies of the window's terms (IDF without a corpus)
+    doc_freq: IdfCorpus,
+    /// Sum of the window's TF-IDF vectors (up to `thematic_from` until refreshed)
+    context: RunningCentroid,
+    /// First turn whose TF-IDF vector or context changed since the last refresh
+    thematic_from: Option<usize>,
+    /// Questions with fewer than `ANSWER_LOOKAHEAD` answers (seq), oldest first
+    questions: VecDeque<u64>,
+    /// Sums of the pair signals, and the number of pairs
+    sums: DcSignals,
+    pairs: usize,
+    /// Exchanges by speaker pair (names sorted)
+    speaker_pairs: HashMap<[String; 2], SpeakerPairExchanges>,
+    speakers: HashMap<String, SpeakerShare>,
+    referents: ReferentTracker,
+    timing: TimingTracker,
+}
+
+impl DcState {
+    /// Bring the state in step with the window
+    fn sync(&mut self, parser: &DcParser, window: &ConversationWindow) {
+        let ids = window.turn_ids();
+        let turns = window.logical_turns();
+        // Features of turns taken out here, in case they come back unchanged
+        let mut recycled: HashMap<String, TurnFeatures> = HashMap::new();
+        let features = |recycled: &mut HashMap<String, TurnFeatures>, text: &str| {
+            recycled.remove(text).unwrap_or_else(|| TurnFeatures::extract(text))
+        };
         
-        // Calculate signals from pairs
-        let signals = self.calculate_signals(&pairs);
-        let dc_value = signals.weighted_sum().clamp(0.0, 1.0);
+        // Turns that left at the front; a front turn that lost a merged part
+        // has a new id and comes back right before the second one
+        while let Some(front) = self.turns.front() {
+            if ids.iter().take(2).any(|&id| id == front.id) {
+                break;
+            }
+            if let Some(turn) = self.pop_front(parser) {
+                recycled.insert(turn.text.to_string(), turn.features);
+            }
+        }
+        if self.turns.front().is_some_and(|front| ids.get(1) == Some(&front.id)) {
+            let extracted = features(&mut recycled, &turns[0].text);
+            self.push_front(parser, ids[0], &turns[0], extracted);
+        }
         
-        DcResult::success(
-            dc_value,
-            signals,
-            pairs.len(),
-            window.speaker_count(),
-        )
+        // From the first turn that differs on, turns changed (merged) or entered
+        let same = self.turns.iter().zip(ids).take_while(|(turn, &id)| turn.id == id).count();
+        while self.turns.len() > same {
+            if let Some(turn) = self.pop_back(parser) {
+                recycled.insert(turn.text.to_string(), turn.features);
+            }
+        }
+        for (turn, &id) in turns.iter().zip(ids).skip(same) {
+            let extracted = features(&mut recycled, &turn.text);
+            self.push_back(parser, id, turn, extracted);
+        }
+        self.refresh_thematic(parser);
+    }
+    
+    /// Note that the turns from `index` on need new TF-IDF context
+    fn mark_thematic(&mut self, index: usize) {
+        self.thematic_from = Some(self.thematic_from.map_or(index, |from| from.min(index)));
     }
     
-    /// Calculate individual signals from turn pairs
-    fn calculate_signals(&self, pairs: &[TurnPair]) -> DcSignals {
-        if pairs.is_empty() {
-            return DcSignals::zero();
+    /// Bring TF-IDF vectors, contexts and the scores that use them in step
+    /// with the window
+    ///
+    /// With a corpus a vector never changes, so only the contexts from the
+    /// first new turn on are computed. With IDF from the window, every turn
+    /// in or out changed it: all vectors are weighted again, and every pair's
+    /// thematic drift and every answer score follow.
+    fn refresh_thematic(&mut self, parser: &DcParser) {
+        let Some(mut from) = self.thematic_from.take() else {
+            return;
+        };
+        if parser.thematic_model != ThematicModel::TfIdf {
+            return;
+        }
+        let window_idf = parser.corpus.is_none();
+        if window_idf {
+            for turn in self.turns.iter_mut() {
+                turn.vector = tfidf_vector(&turn.terms, &self.doc_freq);
+            }
+            from = 0;
         }
         
-        DcSignals {
-            thematic_drift: self.calc_thematic_drift(pairs),
-            emotional_volatility: self.calc_emotional_volatility(pairs),
-            logical_breaks: self.calc_logical_breaks(pairs),
-            qa_mismatch: self.calc_qa_mismatch(pairs),
-            reference_decay: self.calc_reference_decay(pairs),
+        if from == 0 {
+            self.context = RunningCentroid::default();
+        }
+        for turn in self.turns.iter_mut().skip(from) {
+            turn.context_similarity = self.context.cosine(&turn.vector);
+            self.context.add(&turn.vector);
+        }
+        
+        if window_idf {
+            for at in 0..self.turns.len() {
+                if self.turns[at].answers > 0 {
+                    self.score_answers(parser, at);
+                }
+            }
+        }
+        for index in from.max(1)..self.turns.len() {
+            self.rescore_thematic(parser, index);
         }
     }
     
-    /// Signal 1: Thematic drift (topic consistency)
-    /// Higher = less consistent topics
-    fn calc_thematic_drift(&self, pairs: &[TurnPair]) -> f64 {
-        // Simple heuristic: word overlap between consecutive turns
-        // Low overlap = high drift
+    /// Seq for a turn entering at the front or the back
+    fn next_seq(&mut self, front: bool) -> u64 {
+        if self.turns.is_empty() {
+            self.front_seq = FIRST_SEQ;
+            self.back_seq = FIRST_SEQ;
+        } else if front {
+            self.front_seq -= 1;
+        } else {
+            self.back_seq += 1;
+        }
+        if front { self.front_seq } else { self.back_seq }
+    }
+    
+    /// A new turn at the back: the only one that is extracted, vectorised and paired
+    fn push_back(&mut self, parser: &DcParser, id: u64, turn: &Turn, features: TurnFeatures) {
+        let seq = self.next_seq(false);
+        let mut entry = WindowTurn::new(id, seq, turn, features);
+        if parser.thematic_model == ThematicModel::TfIdf {
+            // Context (and, with window IDF, the weights) follow in `refresh_thematic`
+            self.doc_freq.add_terms(&entry.terms);
+            entry.vector = tfidf_vector(&entry.terms, parser.corpus.as_deref().unwrap_or(&self.doc_freq));
+        }
         
-        let mut total_drift = 0.0;
+        // An answer to every waiting question by someone else
+        for i in 0..self.questions.len() {
+            let Some(at) = position(&self.turns, self.questions[i]) else {
+                continue;
+            };
+            let question = &mut self.turns[at];
+            if question.speaker == entry.speaker {
+                continue;
+            }
+            let relevance = answer_relevance(question, &entry);
+            entry.answered.push((question.seq, question.answer_relevance));
+            question.answer_relevance = Some(question.answer_relevance.map_or(relevance, |r| r.max(relevance)));
+            question.answers += 1;
+            self.rescore_qa(parser, at + 1);
+        }
+        let turns = &self.turns;
+        self.questions.retain(|&seq| {
+            position(turns, seq).is_some_and(|at| turns[at].answers < ANSWER_LOOKAHEAD)
+        });
         
-        for pair in pairs {
-            let text1 = pair.first.text.to_lowercase();
-            let text2 = pair.second.text.to_lowercase();
-            
-            let words1: std::collections::HashSet<&str> = text1
-                .split_whitespace()
-                .filter(|w| w.len() > 2)
-                .collect();
-            
-            let words2: std::collections::HashSet<&str> = text2
-                .split_whitespace()
-                .filter(|w| w.len() > 2)
-                .collect();
-            
-            if words1.is_empty() || words2.is_empty() {
-                total_drift += 0.5; // Neutral if no meaningful words
-            } else {
-                let intersection = words1.intersection(&words2).count();
-                let union = words1.union(&words2).count();
-                let jaccard = intersection as f64 / union as f64;
-                total_drift += 1.0 - jaccard; // Invert: low overlap = high drift
+        if let Some(previous) = self.turns.back() {
+            entry.gap_ms = gap_ms(previous.timestamp, entry.timestamp);
+            if previous.speaker != entry.speaker {
+                entry.pair = Some(parser.pair_signals((previous, &entry)));
+            }
+            if let Some(gap) = entry.gap_ms {
+                self.timing.add(seq, &entry.speaker, gap, entry.pair.is_some());
             }
         }
         
-        (total_drift / pairs.len() as f64).clamp(0.0, 1.0)
+        self.referents.push(seq, &entry.speaker, &entry.referents);
+        self.join_speaker(&entry, false);
+        if entry.question.is_some() {
+            self.questions.push_back(seq);
+        }
+        self.turns.push_back(entry);
+        self.add_pair(self.turns.len() - 1, false);
+        self.mark_thematic(self.turns.len() - 1);
     }
     
-    /// Signal 2: Emotional volatility (sentiment swings)
-    /// Higher = more dramatic sentiment changes
-    fn calc_emotional_volatility(&self, pairs: &[TurnPair]) -> f64 {
-        // Simple heuristic: presence of emotional markers
-        let emotional_markers = [
-            "!", "?!", "...", 
-            "wow", "amazing", "terrible", "hate", "love", "angry",
-            "happy", "sad", "excited", "frustrated", "annoyed",
-            "wauw", "geweldig", "verschrikkelijk", "haat", "boos",
-            "blij", "verdrietig", "gefrustreerd",
-        ];
+    /// A turn at the front, older than all others (the front turn lost a merged part)
+    fn push_front(&mut self, parser: &DcParser, id: u64, turn: &Turn, features: TurnFeatures) {
+        let seq = self.next_seq(true);
+        let mut entry = WindowTurn::new(id, seq, turn, features);
+        if parser.thematic_model == ThematicModel::TfIdf {
+            self.doc_freq.add_terms(&entry.terms);
+            entry.vector = tfidf_vector(&entry.terms, parser.corpus.as_deref().unwrap_or(&self.doc_freq));
+        }
         
-        let mut volatility = 0.0;
+        // Answered by the next replies of others
+        if entry.question.is_some() {
+            for answer in self.turns.iter_mut().filter(|t| t.speaker != entry.speaker).take(ANSWER_LOOKAHEAD) {
+                let relevance = answer_relevance(&entry, answer);
+                answer.answered.push((seq, entry.answer_relevance));
+                entry.answer_relevance = Some(entry.answer_relevance.map_or(relevance, |r| r.max(relevance)));
+                entry.answers += 1;
+            }
+            if entry.answers < ANSWER_LOOKAHEAD {
+                self.questions.push_front(seq);
+            }
+        }
         
-        for pair in pairs {
-            let text1 = pair.first.text.to_lowercase();
-            let text2 = pair.second.text.to_lowercase();
-            
-            let count1: usize = emotional_markers.iter()
-                .map(|m| text1.matches(m).count())
-                .sum();
-            let count2: usize = emotional_markers.iter()
-                .map(|m| text2.matches(m).count())
-                .sum();
-            
-            // Volatility = difference in emotional intensity
-            volatility += (count1 as f64 - count2 as f64).abs() * 0.2;
+        if let Some(next) = self.turns.front_mut() {
+            next.gap_ms = gap_ms(entry.timestamp, next.timestamp);
+            if next.speaker != entry.speaker {
+                next.pair = Some(parser.pair_signals((&entry, next)));
+            }
+            if let Some(gap) = next.gap_ms {
+                self.timing.add(next.seq, &next.speaker, gap, next.pair.is_some());
+            }
         }
         
-        (volatility / pairs.len() as f64).clamp(0.0, 1.0)
+        self.referents.push_front(seq, &entry.speaker, &entry.referents);
+        self.join_speaker(&entry, true);
+        self.turns.push_front(entry);
+        if self.turns.len() > 1 {
+            self.add_pair(1, true);
+        }
+        self.mark_thematic(0);
     }
     
-    /// Signal 3: Logical breaks (abrupt topic switches)
-    /// Higher = more abrupt changes
-    fn calc_logical_breaks(&self, pairs: &[TurnPair]) -> f64 {
-        // Heuristic: check for transition words or complete topic change
-        let transition_words = [
-            "but", "however", "although", "anyway", "by the way",
-            "speaking of", "that reminds me", "off topic",
-            "maar", "echter", "overigens", "trouwens",
-        ];
+    /// The oldest turn leaves, with the pair and gap of the turn after it
+    fn pop_front(&mut self, parser: &DcParser) -> Option<WindowTurn> {
+        let turn = self.turns.pop_front()?;
+        if let Some(next) = self.turns.front_mut() {
+            let (pair, gap) = (next.pair.take(), next.gap_ms.take());
+            let (seq, speaker) = (next.seq, next.speaker.clone());
+            if let Some(gap) = gap {
+                self.timing.remove(seq, gap, pair.is_some());
+            }
+            if let Some(signals) = pair {
+                self.remove_pair([&turn.speaker, &speaker], &signals, true);
+            }
+        }
         
-        let mut breaks = 0.0;
+        if parser.thematic_model == ThematicModel::TfIdf {
+            self.doc_freq.remove_terms(&turn.terms);
+            self.context.remove(&turn.vector);
+        }
+        if self.questions.front() == Some(&turn.seq) {
+            self.questions.pop_front();
+        }
+        self.referents.pop_front(&turn.referents);
+        self.leave_speaker(&turn, true);
+        self.mark_thematic(0);
+        Some(turn)
+    }
+    
+    /// The newest turn leaves, taking back its pair and its answers
+    fn pop_back(&mut self, parser: &DcParser) -> Option<WindowTurn> {
+        let turn = self.turns.pop_back()?;
+        if let Some(signals) = &turn.pair {
+            let previous = self.turns.back().map(|t| t.speaker.to_string()).unwrap_or_default();
+            self.remove_pair([&previous, &turn.speaker], signals, false);
+        }
+        if let Some(gap) = turn.gap_ms {
+            self.timing.remove(turn.seq, gap, turn.pair.is_some());
+        }
         
-        for pair in pairs {
-            let text2 = pair.second.text.to_lowercase();
-            
-            // Check for abrupt transitions
-            for word in &transition_words {
-                if text2.contains(word) {
-                    breaks += 0.3;
-                }
-            }
-            
-            // Check for very short responses (might indicate disconnect)
-            if pair.second.text.split_whitespace().count() <= 2 
-                && pair.first.text.split_whitespace().count() > 10 {
-                breaks += 0.2;
+        if self.questions.back() == Some(&turn.seq) {
+            self.questions.pop_back();
+        }
+        for &(seq, before) in turn.answered.iter().rev() {
+            let Some(at) = position(&self.turns, seq) else {
+                continue;
+            };
+            let question = &mut self.turns[at];
+            question.answer_relevance = before;
+            question.answers -= 1;
+            self.rescore_qa(parser, at + 1);
+            if let Err(i) = self.questions.binary_search(&seq) {
+                self.questions.insert(i, seq);
             }
         }
         
-        (breaks / pairs.len() as f64).clamp(0.0, 1.0)
+        if parser.thematic_model == ThematicModel::TfIdf {
+            self.doc_freq.remove_terms(&turn.terms);
+            self.context.remove(&turn.vector);
+        }
+        self.referents.pop_back(&turn.referents);
+        self.leave_speaker(&turn, false);
+        self.mark_thematic(self.turns.len());
+        Some(turn)
     }
     
-    /// Signal 4: Q&A mismatch (questions without answers)
-    /// Higher = more unanswered questions
-    fn calc_qa_mismatch(&self, pairs: &[TurnPair]) -> f64 {
-        let mut mismatches = 0.0;
+    fn join_speaker(&mut self, turn: &WindowTurn, front: bool) {
+        let share = self.speakers.entry(turn.speaker.to_string()).or_default();
+        if front {
+            share.turns.push_front(turn.seq);
+        } else {
+            share.turns.push_back(turn.seq);
+        }
+        share.words += turn.word_count;
+        share.style.merge(&turn.style);
+    }
+    
+    fn leave_speaker(&mut self, turn: &WindowTurn, front: bool) {
+        let Some(share) = self.speakers.get_mut(&*turn.speaker) else {
+            return;
+        };
+        if front {
+            share.turns.pop_front();
+        } else {
+            share.turns.pop_back();
+        }
+        share.words -= turn.word_count;
+        share.style.unmerge(&turn.style);
+        if share.turns.is_empty() {
+            self.speakers.remove(&*turn.speaker);
+        }
+    }
+    
+    /// Count the pair closed by the turn at `index` (new at the front or the back)
+    fn add_pair(&mut self, index: usize, front: bool) {
+        if index == 0 {
+            return;
+        }
+        let (first, second) = (&self.turns[index - 1], &self.turns[index]);
+        let Some(signals) = second.pair.clone() else {
+            return;
+        };
+        let speakers = [first.speaker.to_string(), second.speaker.to_string()];
+        let exchange = (second.seq, speakers.clone());
         
-        for pair in pairs {
-            let is_question = pair.first.text.contains('?');
-            
-            if is_question {
-                // Check if response seems like an answer
-                let response = pair.second.text.to_lowercase();
-                let answer_indicators = [
-                    "yes", "no", "ja", "nee", "because", "omdat",
-                    "i think", "ik denk", "maybe", "misschien",
-                ];
-                
-                let has_answer = answer_indicators.iter()
-                    .any(|ind| response.contains(ind))
-                    || response.len() > 20; // Long response likely addresses question
-                
-                if !has_answer {
-                    mismatches += 0.5;
+        self.pairs += 1;
+        let exchanges = self.speaker_pairs.entry(pair_key(&speakers)).or_default();
+        if front {
+            exchanges.exchanges.push_front(exchange);
+        } else {
+            exchanges.exchanges.push_back(exchange);
+        }
+        for speaker in &speakers {
+            if let Some(share) = self.speakers.get_mut(speaker) {
+                share.exchanges += 1;
+            }
+        }
+        self.tally(&speakers, &signals, 1.0);
+    }
+    
+    /// Take a pair out of the counts (its oldest or newest exchange)
+    fn remove_pair(&mut self, speakers: [&str; 2], signals: &DcSignals, front: bool) {
+        let speakers = speakers.map(String::from);
+        self.tally(&speakers, signals, -1.0);
+        
+        // Sums start again from zero when nothing is left in them
+        self.pairs -= 1;
+        if self.pairs == 0 {
+            self.sums = DcSignals::zero();
+        }
+        let key = pair_key(&speakers);
+        if let Some(exchanges) = self.speaker_pairs.get_mut(&key) {
+            if front {
+                exchanges.exchanges.pop_front();
+            } else {
+                exchanges.exchanges.pop_back();
+            }
+            if exchanges.exchanges.is_empty() {
+                self.speaker_pairs.remove(&key);
+            }
+        }
+        for speaker in &speakers {
+            if let Some(share) = self.speakers.get_mut(speaker) {
+                share.exchanges -= 1;
+                if share.exchanges == 0 {
+                    share.sums = DcSignals::zero();
                 }
             }
         }
+    }
+    
+    /// Score the question at `at` against the replies counted as its answers
+    fn score_answers(&mut self, parser: &DcParser, at: usize) {
+        let question = &self.turns[at];
+        let relevance = self.turns.iter()
+            .skip(at + 1)
+            .filter(|t| t.speaker != question.speaker)
+            .take(question.answers)
+            .map(|answer| answer_relevance(question, answer))
+            .reduce(f64::max);
+        self.turns[at].answer_relevance = relevance;
+        self.rescore_qa(parser, at + 1);
+    }
+    
+    /// Score the Q&A signal of the pair closed by the turn at `index` again
+    /// (its first turn's answer relevance changed)
+    fn rescore_qa(&mut self, parser: &DcParser, index: usize) {
+        self.rescore_pair(index, |first, second, signals| {
+            signals.qa_mismatch = parser.calc_qa_mismatch((first, second));
+        });
+    }
+    
+    /// Score the thematic drift of the pair closed by the turn at `index` again
+    /// (a vector or its context changed)
+    fn rescore_thematic(&mut self, parser: &DcParser, index: usize) {
+        self.rescore_pair(index, |first, second, signals| {
+            signals.thematic_drift = parser.calc_thematic_drift_tfidf((first, second));
+        });
+    }
+    
+    /// Update the signals of the pair closed by the turn at `index` and the sums with them
+    fn rescore_pair(&mut self, index: usize, rescore: impl FnOnce(&WindowTurn, &WindowTurn, &mut DcSignals)) {
+        if index == 0 || index >= self.turns.len() {
+            return;
+        }
+        let Some(old) = self.turns[index].pair.clone() else {
+            return;
+        };
+        let mut new = old.clone();
+        rescore(&self.turns[index - 1], &self.turns[index], &mut new);
         
-        (mismatches / pairs.len() as f64).clamp(0.0, 1.0)
+        let speakers = [self.turns[index - 1].speaker.to_string(), self.turns[index].speaker.to_string()];
+        let mut change = new.clone();
+        accumulate(&mut change, &old, -1.0);
+        self.tally(&speakers, &change, 1.0);
+        self.turns[index].pair = Some(new);
     }
     
-    /// Signal 5: Reference decay (topics that disappear)
-    /// Higher = more abandoned topics
-    fn calc_reference_decay(&self, pairs: &[TurnPair]) -> f64 {
-        if pairs.len() < 2 {
-            return 0.0;
+    /// Add pair signals (times `sign`) to the window, speaker pair and speaker sums
+    fn tally(&mut self, speakers: &[String; 2], signals: &DcSignals, sign: f64) {
+        accumulate(&mut self.sums, signals, sign);
+        if let Some(exchanges) = self.speaker_pairs.get_mut(&pair_key(speakers)) {
+            accumulate(&mut exchanges.sums, signals, sign);
+        }
+        for speaker in speakers {
+            if let Some(share) = self.speakers.get_mut(speaker) {
+                accumulate(&mut share.sums, signals, sign);
+            }
+        }
+    }
+    
+    /// Speakers in order of their first turn in the window
+    fn speakers_in_order(&self) -> Vec<(&str, &SpeakerShare)> {
+        let mut speakers: Vec<(&str, &SpeakerShare)> = self.speakers.iter()
+            .map(|(name, share)| (name.as_str(), share))
+            .collect();
+        speakers.sort_by_key(|(_, share)| share.turns.front().copied());
+        speakers
+    }
+    
+    /// Turn-taking balance from the running counts
+    fn balance(&self) -> TurnBalance {
+        let counts: Vec<(&str, usize, usize)> = self.speakers_in_order()
+            .into_iter()
+            .map(|(name, share)| (name, share.words, share.turns.len()))
+            .collect();
+        let (exchanges, rapid) = self.timing.replies();
+        balance_from_counts(&counts, exchanges, rapid)
+    }
+}
+
+/// Index of the turn with `seq` (seqs grow from front to back)
+fn position(turns: &VecDeque<WindowTurn>, seq: u64) -> Option<usize> {
+    turns.binary_search_by_key(&seq, |t| t.seq).ok()
+}
+
+/// Speaker pair key, names sorted
+fn pair_key(speakers: &[String; 2]) -> [String; 2] {
+    let mut key = speakers.clone();
+    key.sort();
+    key
+}
+
+/// Add `signals` (times `sign`) to the pair-level fields of `sums`
+fn accumulate(sums: &mut DcSignals, signals: &DcSignals, sign: f64) {
+    sums.thematic_drift += sign * signals.thematic_drift;
+    sums.emotional_volatility += sign * signals.emotional_volatility;
+    sums.logical_breaks += sign * signals.logical_breaks;
+    sums.qa_mismatch += sign * signals.qa_mismatch;
+    sums.style_mismatch += sign * signals.style_mismatch;
+}
+
+/// Mean of the pair-level signals over `pairs` pairs from their sums
+/// (reference decay, balance and timing are window-level, left at 0.0)
+fn mean_signals(sums: &DcSignals, pairs: usize) -> DcSignals {
+    if pairs == 0 {
+        return DcSignals::zero();
+    }
+    
+    let n = pairs as f64;
+    let mean = |sum: f64| (sum / n).clamp(0.0, 1.0);
+    DcSignals {
+        thematic_drift: mean(sums.thematic_drift),
+        emotional_volatility: mean(sums.emotional_volatility),
+        logical_breaks: mean(sums.logical_breaks),
+        qa_mismatch: mean(sums.qa_mismatch),
+        reference_decay: 0.0,
+        turn_imbalance: 0.0,
+        style_mismatch: mean(sums.style_mismatch),
+        timing_irregularity: 0.0,
+    }
+}
+
+/// ΔC Parser for measuring coherence between speakers
+#[derive(Debug, Default)]
+pub struct DcParser {
+    /// How thematic drift is measured
+    thematic_model: ThematicModel,
+    /// Background document frequencies (None = IDF from the window)
+    corpus: Option<Arc<IdfCorpus>>,
+    /// How ΔC is formed when more than two speakers take part
+    group_aggregation: GroupAggregation,
+    /// Balance and style weights on top of the five LLD weights
+    weights: DcWeights,
+}
+
+impl DcParser {
+    /// Create new parser
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Create parser with a specific thematic drift model
+    pub fn with_thematic_model(thematic_model: ThematicModel) -> Self {
+        Self {
+            thematic_model,
+            ..Self::default()
+        }
+    }
+    
+    /// Create parser that takes IDF from a background corpus instead of the window
+    pub fn with_corpus(corpus: Arc<IdfCorpus>) -> Self {
+        Self {
+            corpus: Some(corpus),
+            ..Self::default()
+        }
+    }
+    
+    /// Set background corpus for IDF (None = IDF from the window)
+    pub fn set_corpus(&mut self, corpus: Option<Arc<IdfCorpus>>) {
+        self.corpus = corpus;
+    }
+    
+    /// Get thematic drift model
+    pub fn thematic_model(&self) -> ThematicModel {
+        self.thematic_model
+    }
+    
+    /// Create parser with a specific group aggregation
+    pub fn with_group_aggregation(group_aggregation: GroupAggregation) -> Self {
+        Self {
+            group_aggregation,
+            ..Self::default()
+        }
+    }
+    
+    /// Set group aggregation
+    pub fn set_group_aggregation(&mut self, group_aggregation: GroupAggregation) {
+        self.group_aggregation = group_aggregation;
+    }
+    
+    /// Get group aggregation
+    pub fn group_aggregation(&self) -> GroupAggregation {
+        self.group_aggregation
+    }
+    
+    /// Create parser with a custom turn-taking balance weight (0.0 = five-signal ΔC)
+    pub fn with_balance_weight(weight: f64) -> Self {
+        let mut parser = Self::default();
+        parser.set_balance_weight(weight);
+        parser
+    }
+    
+    /// Set turn-taking balance weight
+    pub fn set_balance_weight(&mut self, weight: f64) {
+        self.weights.balance = weight.max(0.0);
+    }
+    
+    /// Get turn-taking balance weight
+    pub fn balance_weight(&self) -> f64 {
+        self.weights.balance
+    }
+    
+    /// Create parser with a custom style share of the thematic weight (0.0 = topic only)
+    pub fn with_style_share(share: f64) -> Self {
+        let mut parser = Self::default();
+        parser.set_style_share(share);
+        parser
+    }
+    
+    /// Set style share of the thematic weight (clamped to 0.0-1.0)
+    pub fn set_style_share(&mut self, share: f64) {
+        self.weights.style_share = share.clamp(0.0, 1.0);
+    }
+    
+    /// Get style share of the thematic weight
+    pub fn style_share(&self) -> f64 {
+        self.weights.style_share
+    }
+    
+    /// Create parser that folds turn timing into ΔC with this weight
+    pub fn with_timing_weight(weight: f64) -> Self {
+        let mut parser = Self::default();
+        parser.set_timing_weight(weight);
+        parser
+    }
+    
+    /// Set turn-timing weight (0.0 = report only)
+    pub fn set_timing_weight(&mut self, weight: f64) {
+        self.weights.timing = weight.max(0.0);
+    }
+    
+    /// Get turn-timing weight
+    pub fn timing_weight(&self) -> f64 {
+        self.weights.timing
+    }
+    
+    /// Get balance, style and timing weights
+    pub fn weights(&self) -> DcWeights {
+        self.weights
+    }
+    
+    /// Calculate ΔC from conversation window
+    /// Returns DcResult with value or UNKNOWN reason
+    pub fn calculate(&self, window: &ConversationWindow) -> DcResult {
+        self.calculate_with(window, &mut DcState::default())
+    }
+    
+    /// Calculate ΔC, bringing `state` from the last call on this window up to date
+    ///
+    /// Only turns that entered the window since are scored, so the cost of a
+    /// call does not grow with the window (except re-weighting TF-IDF vectors
+    /// when the IDF comes from the window).
+    pub fn calculate_with(&self, window: &ConversationWindow, state: &mut DcState) -> DcResult {
+        // Check preconditions per TURN_MODEL
+        
+        // Window emptied by its time limit
+        if window.timed_out() {
+            return DcResult::unknown(DcReason::R013_DC_UNKNOWN_TIMEOUT);
+        }
+        
+        // Need at least 2 turns
+        if window.len() < 2 {
+            return DcResult::unknown(DcReason::R012_DC_UNKNOWN_INSUFFICIENT_TURNS);
+        }
+        
+        // Need at least 2 speakers
+        if window.speaker_count() < 2 {
+            return DcResult::unknown(DcReason::R011_DC_UNKNOWN_SINGLE_SPEAKER);
+        }
+        
+        // Need at least 1 pair
+        // (logical turns: same-speaker bursts merged if the window merges them)
+        state.sync(self, window);
+        if state.pairs == 0 {
+            return DcResult::unknown(DcReason::R016_DC_UNKNOWN_NO_PAIRS);
         }
         
-        // Track nouns/topics across pairs
-        // If topics from early pairs never appear again, that's decay
+        // Mean pair signals from the running sums; window-level signals on top
+        let mut signals = mean_signals(&state.sums, state.pairs);
+        signals.reference_decay = state.referents.decay().clamp(0.0, 1.0);
+        let balance = state.balance();
+        signals.turn_imbalance = balance.imbalance();
+        let timing = state.timing.stats(|seq| position(&state.turns, seq).unwrap_or(0));
+        signals.timing_irregularity = timing.as_ref().map_or(0.0, |t| t.irregularity());
+        let window_level = |mut pair: DcSignals| {
+            pair.reference_decay = signals.reference_decay;
+            pair.turn_imbalance = signals.turn_imbalance;
+            pair.timing_irregularity = signals.timing_irregularity;
+            pair
+        };
+        
+        let (matrix, speaker_coherence) = self.group_breakdown(state, &window_level);
+        let dc_value = match self.group_aggregation {
+            GroupAggregation::Mean => signals.weighted_sum_with(&self.weights).clamp(0.0, 1.0),
+            GroupAggregation::WorstPair => matrix.iter().map(|p| p.dc).fold(0.0, f64::max),
+            GroupAggregation::Median => median(matrix.iter().map(|p| p.dc).collect()),
+        };
+        
+        let mut result = DcResult::success(
+            dc_value,
+            signals.clone(),
+            state.pairs,
+            window.speaker_count(),
+        );
+        result.turn_sentiment = state.turns.iter()
+            .map(|turn| TurnSentiment {
+                speaker: turn.speaker.clone(),
+                valence: turn.sentiment.valence,
+                arousal: turn.arousal(),
+            })
+            .collect();
+        result.dropped_threads = state.referents.dropped_threads();
+        result.aggregation = self.group_aggregation;
+        result.matrix = matrix;
+        result.speaker_coherence = speaker_coherence;
+        result.balance = Some(balance);
+        result.style = self.window_style(state, &result.matrix);
+        result.timing = timing;
         
-        // Simplified: check if words from first pair appear in last pair
-        let first_words: std::collections::HashSet<_> = pairs.first()
-            .map(|p| {
-                p.first.text.to_lowercase()
-                    .split_whitespace()
-                    .filter(|w| w.len() > 3)
-                    .map(String::from)
-                    .collect::<Vec<_>>()
+        // Each pair's share of ΔC: contributions add up to the weighted sum
+        let n = state.pairs as f64;
+        result.pairs = (1..state.turns.len())
+            .filter_map(|b| {
+                let (first, second) = (&state.turns[b - 1], &state.turns[b]);
+                let signals = window_level(second.pair.clone()?);
+                Some(PairContribution {
+                    first: b - 1,
+                    second: b,
+                    first_speaker: first.speaker.clone(),
+                    second_speaker: second.speaker.clone(),
+                    first_text: first.text.clone(),
+                    second_text: second.text.clone(),
+                    contribution: signals.weighted_sum_with(&self.weights) / n,
+                    latency_ms: second.gap_ms,
+                    signals,
+                })
             })
-            .unwrap_or_default()
-            .into_iter()
             .collect();
+        result
+    }
+    
+    /// Signal values of a pair (reference decay, balance and timing are filled in per window)
+    fn pair_signals(&self, pair: FeaturePair) -> DcSignals {
+        DcSignals {
+            thematic_drift: match self.thematic_model {
+                ThematicModel::TfIdf => self.calc_thematic_drift_tfidf(pair),
+                ThematicModel::Jaccard => self.calc_thematic_drift_jaccard(pair),
+            },
+            emotional_volatility: self.calc_emotional_volatility(pair),
+            logical_breaks: self.calc_logical_breaks(pair),
+            qa_mismatch: self.calc_qa_mismatch(pair),
+            reference_decay: 0.0,
+            turn_imbalance: 0.0,
+            style_mismatch: self.calc_style_mismatch(pair),
+            timing_irregularity: 0.0,
+        }
+    }
+    
+    /// ΔC per speaker pair and per speaker, in order of first appearance
+    fn group_breakdown(
+        &self,
+        state: &DcState,
+        window_level: &dyn Fn(DcSignals) -> DcSignals,
+    ) -> (Vec<SpeakerPairDc>, Vec<SpeakerCoherence>) {
+        let dc_of = |sums: &DcSignals, exchanges: usize| -> (DcSignals, f64) {
+            let signals = window_level(mean_signals(sums, exchanges));
+            let dc = signals.weighted_sum_with(&self.weights).clamp(0.0, 1.0);
+            (signals, dc)
+        };
         
-        let last_words: std::collections::HashSet<_> = pairs.last()
-            .map(|p| {
-                format!("{} {}", p.first.text, p.second.text)
-                    .to_lowercase()
-                    .split_whitespace()
-                    .filter(|w| w.len() > 3)
-                    .map(String::from)
-                    .collect::<Vec<_>>()
+        let mut pairs: Vec<&SpeakerPairExchanges> = state.speaker_pairs.values().collect();
+        pairs.sort_by_key(|p| p.exchanges.front().map(|(seq, _)| *seq));
+        let matrix = pairs.into_iter()
+            .filter_map(|p| {
+                let (_, speakers) = p.exchanges.front()?;
+                let (signals, dc) = dc_of(&p.sums, p.exchanges.len());
+                Some(SpeakerPairDc { speakers: speakers.clone(), dc, exchanges: p.exchanges.len(), signals })
             })
-            .unwrap_or_default()
+            .collect();
+        
+        let speaker_coherence = state.speakers_in_order()
             .into_iter()
+            .filter(|(_, share)| share.exchanges > 0)
+            .map(|(speaker, share)| SpeakerCoherence {
+                speaker: speaker.to_string(),
+                dc: dc_of(&share.sums, share.exchanges).1,
+                exchanges: share.exchanges,
+            })
             .collect();
         
-        if first_words.is_empty() {
-            return 0.0;
+        (matrix, speaker_coherence)
+    }
+    
+    /// LSM between speakers over all their words, averaged over the speaker pairs
+    fn window_style(&self, state: &DcState, matrix: &[SpeakerPairDc]) -> Option<StyleMatch> {
+        let profile = |speaker: &str| state.speakers.get(speaker).map(|share| &share.style);
+        let matches: Vec<StyleMatch> = matrix.iter()
+            .filter_map(|p| style_match(profile(&p.speakers[0])?, profile(&p.speakers[1])?))
+            .collect();
+        let first = matches.first()?;
+        let n = matches.len() as f64;
+        let categories = first.categories.iter()
+            .enumerate()
+            .map(|(i, c)| StyleCategoryMatch {
+                category: c.category.clone(),
+                lsm: matches.iter().map(|m| m.categories[i].lsm).sum::<f64>() / n,
+            })
+            .collect();
+        
+        Some(StyleMatch {
+            lsm: matches.iter().map(|m| m.lsm).sum::<f64>() / n,
+            categories,
+        })
+    }
+    
+    /// Signal 1: Thematic drift (topic consistency), TF-IDF model
+    /// Higher = less consistent topics
+    ///
+    /// A reply is on-theme if it is close to the turn it answers or to the
+    /// conversation so far: drift = 1 - max(pair cosine, context cosine).
+    fn calc_thematic_drift_tfidf(&self, (first, second): FeaturePair) -> f64 {
+        if first.vector.is_empty() || second.vector.is_empty() {
+            return 0.5; // Neutral if no content words
+        }
+        
+        let similarity = cosine(&first.vector, &second.vector)
+            .max(second.context_similarity);
+        1.0 - similarity
+    }
+    
+    /// Original thematic drift: Jaccard overlap of raw words
+    fn calc_thematic_drift_jaccard(&self, (first, second): FeaturePair) -> f64 {
+        // Simple heuristic: word overlap between consecutive turns
+        // Low overlap = high drift
+        let words1 = &first.thematic_words;
+        let words2 = &second.thematic_words;
+        
+        if words1.is_empty() || words2.is_empty() {
+            return 0.5; // Neutral if no meaningful words
         }
         
-        let retained = first_words.intersection(&last_words).count();
-        let decay = 1.0 - (retained as f64 / first_words.len() as f64);
+        let intersection = words1.intersection(words2).count();
+        let union = words1.union(words2).count();
+        let jaccard = intersection as f64 / union as f64;
+        1.0 - jaccard // Invert: low overlap = high drift
+    }
+    
+    /// Signal 2: Emotional volatility (sentiment swings)
+    /// Higher = more dramatic sentiment changes
+    fn calc_emotional_volatility(&self, (first, second): FeaturePair) -> f64 {
+        // Valence swing (max 2.0 from -1 to +1), plus a change in agitation
+        let valence_swing = (first.sentiment.valence - second.sentiment.valence).abs() / 2.0;
+        let arousal_swing = (first.arousal() - second.arousal()).abs();
+        0.8 * valence_swing + 0.2 * arousal_swing
+    }
+    
+    /// Signal 3: Logical breaks (abrupt topic switches)
+    /// Higher = more abrupt changes
+    fn calc_logical_breaks(&self, (first, second): FeaturePair) -> f64 {
+        break_score(first, second)
+    }
+    
+    /// Signal 4: Q&A mismatch (questions without answers)
+    /// Higher = more unanswered questions
+    fn calc_qa_mismatch(&self, (first, _): FeaturePair) -> f64 {
+        if first.question.is_none() {
+            return 0.0;
+        }
         
-        decay.clamp(0.0, 1.0)
+        // Best answer within the lookahead, not only the paired reply
+        let relevance = first.answer_relevance.unwrap_or(0.0);
+        0.5 * (1.0 - relevance)
+    }
+    
+    /// Style mismatch: 1 - function-word LSM of the pair
+    /// Higher = speakers write in a different style
+    fn calc_style_mismatch(&self, (first, second): FeaturePair) -> f64 {
+        lsm(&first.style, &second.style).map_or(0.5, |m| 1.0 - m) // Neutral if no words
+    }
+}
+
+/// Logical-break signal of one exchange, outside any window (snapshot blind spots)
+pub(crate) fn logical_breaks(first: &str, second: &str) -> f64 {
+    break_score(&TurnFeatures::extract(first), &TurnFeatures::extract(second))
+}
+
+/// Transition words in the reply, or a one- or two-word reply to a long turn
+///
+/// Clamped per exchange, so the window mean needs no clamp and pair
+/// contributions add up to ΔC.
+fn break_score(first: &TurnFeatures, second: &TurnFeatures) -> f64 {
+    // Heuristic: check for transition words or complete topic change
+    let mut breaks: f64 = 0.0;
+    
+    // Check for abrupt transitions
+    for _ in 0..second.transitions {
+        breaks += 0.3;
+    }
+    
+    // Check for very short responses (might indicate disconnect)
+    if second.word_count <= 2 && first.word_count > 10 {
+        breaks += 0.2;
+    }
+    
+    breaks.min(1.0)
+}
+
+/// Median of a non-empty list
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
     }
 }
 
+/// Is `text[start..end]` bounded by non-word characters?
+fn is_whole_word(text: &str, start: usize, end: usize) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    !text[..start].chars().next_back().is_some_and(is_word)
+        && !text[end..].chars().next().is_some_and(is_word)
+}
+
+/// How well `answer` addresses the question in `question` (0.0-1.0)
+///
+/// The best of: share of the question's content terms picked up, TF-IDF cosine,
+/// and answer form ("yes"/"no"/"because" to a closed question, a short direct
+/// statement to an open one).
+fn answer_relevance(question: &WindowTurn, answer: &WindowTurn) -> f64 {
+    let asked: HashSet<&String> = question.terms.iter().collect();
+    let overlap = if asked.is_empty() {
+        0.0
+    } else {
+        let picked_up: HashSet<&String> = answer.terms.iter()
+            .filter(|t| asked.contains(t))
+            .collect();
+        picked_up.len() as f64 / asked.len() as f64
+    };
+    
+    let similarity = cosine(&question.vector, &answer.vector);
+    
+    let form = match question.question {
+        Some(QuestionKind::YesNo) if answer.has_answer_indicator => 1.0,
+        Some(QuestionKind::Wh) if answer.has_answer_indicator => 0.6,
+        Some(QuestionKind::Wh) if answer.question.is_none() && (1..=4).contains(&answer.word_count) => 0.4,
+        _ => 0.0,
+    };
+    
+    overlap.max(similarity).max(form).clamp(0.0, 1.0)
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -340,6 +1289,433 @@ mod tests {
         assert!(result.signals.qa_mismatch > 0.0, "Should detect Q&A mismatch");
     }
     
+    #[test]
+    fn test_single_pass_features_match_substring_counts() {
+        let texts = [
+            "Wow!!! I love it... but honestly, I hate it?! Anyway....",
+            "Maar trouwens, ik denk dat het geweldig is! Echter... nee.",
+            "By the way, speaking of butter: however, no, yes, maybe.",
+            "Nothing special here",
+            "I know, yesterday was busy",
+            "",
+        ];
+        
+        for text in &texts {
+            let lower = text.to_lowercase();
+            let emotional: usize = EMOTIONAL_MARKERS.iter().map(|m| lower.matches(m).count()).sum();
+            let transitions = TRANSITION_WORDS.iter().filter(|w| lower.contains(*w)).count();
+            let has_answer = ANSWER_INDICATORS.iter().any(|i| {
+                lower.match_indices(i).any(|(at, _)| is_whole_word(&lower, at, at + i.len()))
+            });
+            
+            let features = TurnFeatures::extract(text);
+            assert_eq!(features.emotional, emotional, "{:?}", text);
+            assert_eq!(features.transitions, transitions, "{:?}", text);
+            assert_eq!(features.has_answer_indicator, has_answer, "{:?}", text);
+        }
+    }
+    
+    #[test]
+    fn test_tfidf_paraphrase_is_not_drift() {
+        let window = make_window(vec![
+            ("A", "The cat sat on the mat.", 0.05),
+            ("B", "A cat was sitting on the mat!", 0.05),
+        ]);
+        
+        let tfidf = DcParser::new().calculate(&window).signals.thematic_drift;
+        let jaccard = DcParser::with_thematic_model(ThematicModel::Jaccard)
+            .calculate(&window).signals.thematic_drift;
+        
+        assert!(tfidf < 0.5, "paraphrase should stay on theme, got {}", tfidf);
+        assert!(tfidf < jaccard, "tfidf {} vs jaccard {}", tfidf, jaccard);
+    }
+    
+    #[test]
+    fn test_tfidf_return_to_earlier_topic() {
+        // B's last reply ignores A's aside but picks up the main thread
+        let window = make_window(vec![
+            ("A", "Our garden needs new tomato plants", 0.05),
+            ("B", "Tomato plants love the sun in the garden", 0.05),
+            ("A", "Did you see the football match", 0.05),
+            ("B", "The tomato plants should go near the garden fence", 0.05),
+        ]);
+        let with_context = DcParser::new().calculate(&window).signals.thematic_drift;
+        
+        let unrelated = make_window(vec![
+            ("A", "Our garden needs new tomato plants", 0.05),
+            ("B", "Tomato plants love the sun in the garden", 0.05),
+            ("A", "Did you see the football match", 0.05),
+            ("B", "My car needs expensive repairs", 0.05),
+        ]);
+        let off_topic = DcParser::new().calculate(&unrelated).signals.thematic_drift;
+        
+        assert!(with_context < off_topic);
+    }
+    
+    #[test]
+    fn test_jaccard_model_keeps_original_heuristic() {
+        let window = make_window(vec![
+            ("A", "blue sky today", 0.05),
+            ("B", "blue sea today", 0.05),
+        ]);
+        let drift = DcParser::with_thematic_model(ThematicModel::Jaccard)
+            .calculate(&window).signals.thematic_drift;
+        
+        // {blue, sky, today} vs {blue, sea, today}: 2 shared of 4
+        assert!((drift - 0.5).abs() < 1e-12);
+    }
+    
+    #[test]
+    fn test_background_corpus() {
+        let corpus = Arc::new(IdfCorpus::from_documents([
+            "the weather is nice", "weather report for today", "rainy weather again",
+        ]));
+        let parser = DcParser::with_corpus(corpus);
+        let window = make_window(vec![
+            ("A", "Sailing in good weather", 0.05),
+            ("B", "Sailing is the best", 0.05),
+        ]);
+        
+        // "weather" is common in the corpus, so the shared rare "sailing" dominates
+        let result = parser.calculate(&window);
+        let window_idf = DcParser::new().calculate(&window);
+        assert!(result.is_known());
+        assert!(result.signals.thematic_drift < window_idf.signals.thematic_drift);
+    }
+    
+    #[test]
+    fn test_volatility_from_mood_swing() {
+        let steady = make_window(vec![
+            ("A", "I am happy with the plan", 0.05),
+            ("B", "I am glad you like it", 0.05),
+        ]);
+        let swing = make_window(vec![
+            ("A", "I am happy with the plan", 0.05),
+            ("B", "I hate it, this is terrible", 0.05),
+        ]);
+        
+        let steady = DcParser::new().calculate(&steady).signals.emotional_volatility;
+        let swing = DcParser::new().calculate(&swing).signals.emotional_volatility;
+        assert!(steady < 0.15, "got {}", steady);
+        assert!(swing > 0.5, "got {}", swing);
+    }
+    
+    #[test]
+    fn test_exclamations_alone_are_not_volatility() {
+        // Same mood, one speaker just louder
+        let window = make_window(vec![
+            ("A", "The garden looks nice", 0.05),
+            ("B", "The garden looks nice!", 0.05),
+        ]);
+        let volatility = DcParser::new().calculate(&window).signals.emotional_volatility;
+        assert!(volatility < 0.1, "got {}", volatility);
+    }
+    
+    #[test]
+    fn test_turn_sentiment_shows_who_shifted_mood() {
+        let window = make_window(vec![
+            ("A", "Wat een mooie dag", 0.05),
+            ("B", "Ja, heerlijk weer", 0.05),
+            ("A", "Maar ik ben niet blij met mijn werk", 0.05),
+        ]);
+        let result = DcParser::new().calculate(&window);
+        
+        assert_eq!(result.turn_sentiment.len(), 3);
+        assert_eq!(&*result.turn_sentiment[2].speaker, "A");
+        assert!(result.turn_sentiment[0].valence > 0.0);
+        assert!(result.turn_sentiment[1].valence > 0.0);
+        assert!(result.turn_sentiment[2].valence < 0.0);
+    }
+    
+    #[test]
+    fn test_question_without_question_mark() {
+        let window = make_window(vec![
+            ("A", "Waarom ben je zo laat", 0.1),
+            ("B", "Het regent buiten", 0.1),
+        ]);
+        let result = DcParser::new().calculate(&window);
+        assert!(result.signals.qa_mismatch > 0.0);
+    }
+    
+    #[test]
+    fn test_relevance_not_length() {
+        // Long but irrelevant reply used to count as an answer
+        let irrelevant = make_window(vec![
+            ("A", "Where did you park the car?", 0.1),
+            ("B", "The football match yesterday evening was really exciting to watch", 0.1),
+        ]);
+        let relevant = make_window(vec![
+            ("A", "Where did you park the car?", 0.1),
+            ("B", "I parked the car behind the station", 0.1),
+        ]);
+        
+        let irrelevant = DcParser::new().calculate(&irrelevant).signals.qa_mismatch;
+        let relevant = DcParser::new().calculate(&relevant).signals.qa_mismatch;
+        assert!(irrelevant > 0.4, "got {}", irrelevant);
+        assert!(relevant < 0.1, "got {}", relevant);
+    }
+    
+    #[test]
+    fn test_question_answered_a_turn_later() {
+        let window = make_window(vec![
+            ("A", "Can you bring the tent tomorrow?", 0.1),
+            ("B", "Hmm, let me check", 0.1),
+            ("A", "Sure", 0.1),
+            ("B", "Yes, I will bring the tent", 0.1),
+        ]);
+        let late = DcParser::new().calculate(&window).signals.qa_mismatch;
+        
+        let never = make_window(vec![
+            ("A", "Can you bring the tent tomorrow?", 0.1),
+            ("B", "Hmm, let me check", 0.1),
+            ("A", "Sure", 0.1),
+            ("B", "The weather looks grey", 0.1),
+        ]);
+        let never = DcParser::new().calculate(&never).signals.qa_mismatch;
+        
+        assert!(late < never, "late {} vs never {}", late, never);
+    }
+    
+    #[test]
+    fn test_dropped_threads_in_result() {
+        let window = make_window(vec![
+            ("A", "I am worried about my job interview", 0.1),
+            ("B", "Did you see the football match", 0.1),
+            ("A", "No", 0.1),
+            ("B", "The football was great", 0.1),
+        ]);
+        let result = DcParser::new().calculate(&window);
+        
+        let dropped: Vec<&str> = result.dropped_threads.iter()
+            .map(|t| t.referent.as_str())
+            .collect();
+        assert!(dropped.contains(&"interview"), "{:?}", dropped);
+        assert!(!dropped.contains(&"football"));
+        assert!(result.signals.reference_decay > 0.5);
+        
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"dropped_threads\""));
+    }
+    
+    #[test]
+    fn test_picked_up_threads_do_not_decay() {
+        let window = make_window(vec![
+            ("A", "Our garden needs tomato plants", 0.1),
+            ("B", "Tomato plants like a sunny garden", 0.1),
+            ("A", "The garden gets sun all day", 0.1),
+            ("B", "Then the tomato plants will grow well", 0.1),
+        ]);
+        let result = DcParser::new().calculate(&window);
+        assert!(result.signals.reference_decay < 0.5, "got {}", result.signals.reference_decay);
+        assert!(result.dropped_threads.iter().all(|t| t.referent != "garden"));
+    }
+    
+    #[test]
+    fn test_pair_contributions_sum_to_dc() {
+        let window = make_window(vec![
+            ("A", "How is the garden doing", 0.1),
+            ("B", "The garden is full of roses", 0.1),
+            ("A", "Lovely, the roses smell great", 0.1),
+            ("B", "My car broke down on the highway", 0.1),
+        ]);
+        let result = DcParser::new().calculate(&window);
+        
+        assert_eq!(result.pairs.len(), 3);
+        let sum: f64 = result.pairs.iter().map(|p| p.contribution).sum();
+        assert!((sum - result.value.unwrap()).abs() < 1e-9, "sum {} vs {:?}", sum, result.value);
+        
+        // The off-topic reply carries the largest share
+        let worst = result.pairs.iter()
+            .max_by(|a, b| a.contribution.total_cmp(&b.contribution))
+            .unwrap();
+        assert_eq!(worst.second, 3);
+        assert_eq!(&*worst.second_speaker, "B");
+    }
+    
+    #[test]
+    fn test_pair_contributions_sum_with_saturated_breaks() {
+        // Five transition words would score 1.5 unclamped
+        let window = make_window(vec![
+            ("A", "How is the garden doing", 0.1),
+            ("B", "But however, anyway, by the way, speaking of cars, off topic", 0.1),
+            ("A", "The roses are blooming", 0.1),
+        ]);
+        let result = DcParser::new().calculate(&window);
+        
+        assert!(result.pairs.iter().all(|p| p.signals.logical_breaks <= 1.0));
+        assert_eq!(result.pairs[0].signals.logical_breaks, 1.0);
+        let sum: f64 = result.pairs.iter().map(|p| p.contribution).sum();
+        assert!((sum - result.value.unwrap()).abs() < 1e-9, "sum {} vs {:?}", sum, result.value);
+    }
+    
+    #[test]
+    fn test_group_matrix_and_speaker_coherence() {
+        let window = make_window(vec![
+            ("A", "How is the garden doing", 0.1),
+            ("B", "The garden is full of roses", 0.1),
+            ("C", "My car broke down on the highway", 0.1),
+            ("A", "The roses in the garden need sun", 0.1),
+            ("B", "The garden roses get sun all day", 0.1),
+        ]);
+        let result = DcParser::new().calculate(&window);
+        
+        assert_eq!(result.matrix.len(), 3);
+        let ab = result.matrix.iter().find(|p| p.is_pair("B", "A")).unwrap();
+        assert_eq!(ab.speakers, ["A".to_string(), "B".to_string()]);
+        assert_eq!(ab.exchanges, 2);
+        assert!(result.pair_dc("A", "B").unwrap() < result.pair_dc("B", "C").unwrap());
+        assert!(result.pair_dc("A", "D").is_none());
+        
+        // C only ever answered off-topic
+        let coherence: Vec<&str> = result.speaker_coherence.iter().map(|s| s.speaker.as_str()).collect();
+        assert_eq!(coherence, vec!["A", "B", "C"]);
+        let c = &result.speaker_coherence[2];
+        assert!(result.speaker_coherence.iter().all(|s| s.dc <= c.dc));
+    }
+    
+    #[test]
+    fn test_group_aggregation() {
+        let window = make_window(vec![
+            ("A", "How is the garden doing", 0.1),
+            ("B", "The garden is full of roses", 0.1),
+            ("C", "My car broke down on the highway", 0.1),
+            ("A", "The roses in the garden need sun", 0.1),
+            ("B", "The garden roses get sun all day", 0.1),
+        ]);
+        let mean = DcParser::new().calculate(&window);
+        let worst = DcParser::with_group_aggregation(GroupAggregation::WorstPair).calculate(&window);
+        let median = DcParser::with_group_aggregation(GroupAggregation::Median).calculate(&window);
+        
+        let pair_values: Vec<f64> = mean.matrix.iter().map(|p| p.dc).collect();
+        let max = pair_values.iter().cloned().fold(0.0, f64::max);
+        assert!((worst.value.unwrap() - max).abs() < 1e-12);
+        assert_eq!(worst.aggregation, GroupAggregation::WorstPair);
+        assert!(median.value.unwrap() <= worst.value.unwrap());
+        assert!(pair_values.iter().any(|v| (v - median.value.unwrap()).abs() < 1e-12));
+        assert!(mean.value.unwrap() <= worst.value.unwrap());
+    }
+    
+    #[test]
+    fn test_duo_aggregation_is_unchanged() {
+        let window = make_window(vec![
+            ("A", "How is the garden doing", 0.1),
+            ("B", "The garden is full of roses", 0.1),
+            ("A", "My car broke down", 0.1),
+        ]);
+        let mean = DcParser::new().calculate(&window);
+        for aggregation in [GroupAggregation::WorstPair, GroupAggregation::Median] {
+            let other = DcParser::with_group_aggregation(aggregation).calculate(&window);
+            assert!((other.value.unwrap() - mean.value.unwrap()).abs() < 1e-12);
+        }
+        assert_eq!(mean.matrix.len(), 1);
+        assert_eq!(mean.speaker_coherence.len(), 2);
+    }
+    
+    #[test]
+    fn test_turn_imbalance_raises_dc() {
+        let long = "I have been thinking about the project and the deadline and everything before Friday";
+        let window = make_window(vec![
+            ("A", long, 0.1),
+            ("B", "Ok", 0.1),
+            ("A", long, 0.1),
+            ("B", "Ok", 0.1),
+        ]);
+        let result = DcParser::with_balance_weight(0.1).calculate(&window);
+        assert!(result.signals.turn_imbalance > 0.5, "got {}", result.signals.turn_imbalance);
+        assert_eq!(result.balance.as_ref().unwrap().dominant.as_deref(), Some("A"));
+        
+        // Reported, but off by default
+        let default = DcParser::new().calculate(&window);
+        assert_eq!(default.signals.turn_imbalance, result.signals.turn_imbalance);
+        let without = DcParser::with_balance_weight(0.0).calculate(&window);
+        assert_eq!(default.value, without.value);
+        assert!(result.value.unwrap() > without.value.unwrap());
+    }
+    
+    #[test]
+    fn test_zero_extra_weights_is_five_signal_dc() {
+        let window = make_window(vec![
+            ("A", "How is the garden doing", 0.1),
+            ("B", "The garden is full of roses", 0.1),
+            ("A", "My car broke down", 0.1),
+        ]);
+        let mut parser = DcParser::with_balance_weight(0.0);
+        parser.set_style_share(0.0);
+        assert_eq!(parser.weights(), DcWeights::lld());
+        let result = parser.calculate(&window);
+        let s = &result.signals;
+        let five = s.thematic_drift * 0.31 + s.emotional_volatility * 0.28
+            + s.logical_breaks * 0.22 + s.qa_mismatch * 0.12 + s.reference_decay * 0.07;
+        assert_eq!(result.value.unwrap(), five.clamp(0.0, 1.0));
+    }
+    
+    #[test]
+    fn test_style_matching_softens_topic_change() {
+        // Same function-word style, different topics
+        let window = make_window(vec![
+            ("A", "I think the weather is nice and we should go to the beach", 0.1),
+            ("B", "I think the budget is fine and we should talk to the bank", 0.1),
+        ]);
+        let topic_only = DcParser::with_style_share(0.0).calculate(&window);
+        let with_style = DcParser::with_style_share(0.3).calculate(&window);
+        
+        assert!(with_style.signals.style_mismatch < 0.2, "got {}", with_style.signals.style_mismatch);
+        assert!(with_style.value.unwrap() < topic_only.value.unwrap());
+        
+        // Reported, but off by default
+        let default = DcParser::new().calculate(&window);
+        assert_eq!(default.value, topic_only.value);
+        assert_eq!(default.signals.style_mismatch, with_style.signals.style_mismatch);
+        
+        let style = with_style.style.unwrap();
+        assert!(style.lsm > 0.8);
+        assert!(style.categories.iter().any(|c| c.category == "articles"));
+    }
+    
+    #[test]
+    fn test_style_mismatch_between_registers() {
+        let window = make_window(vec![
+            ("A", "I was wondering if we could maybe talk about it tonight", 0.1),
+            ("B", "Budget. Friday. Urgent!", 0.1),
+        ]);
+        let result = DcParser::new().calculate(&window);
+        assert!(result.signals.style_mismatch > 0.5, "got {}", result.signals.style_mismatch);
+        assert!(result.pairs[0].signals.style_mismatch > 0.5);
+    }
+    
+    #[test]
+    fn test_timing_reported_and_optionally_weighted() {
+        use std::time::{Duration, Instant};
+        
+        let now = Instant::now();
+        let mut window = ConversationWindow::with_duration(120);
+        for (speaker, text, secs_ago) in [
+            ("A", "How is the garden doing", 60),
+            ("B", "The garden is full of roses", 58),
+            ("A", "Do the roses need water", 57),
+            ("B", "Yes, the roses need water daily", 20),
+        ] {
+            let turn = Turn::new(speaker, text, 0.1).with_timestamp(now - Duration::from_secs(secs_ago));
+            window.add_turn(turn);
+        }
+        
+        let reported = DcParser::new().calculate(&window);
+        let timing = reported.timing.as_ref().unwrap();
+        assert_eq!(timing.long_silences.len(), 1);
+        assert_eq!(timing.long_silences[0].broken_by, "B");
+        assert_eq!(reported.pairs[2].latency_ms, Some(37_000));
+        assert!(reported.signals.timing_irregularity > 0.0);
+        
+        // Default weight 0: timing does not move ΔC
+        let mut lld = DcParser::new();
+        lld.set_timing_weight(0.0);
+        let unweighted = lld.calculate(&window).value.unwrap();
+        assert!((unweighted - reported.value.unwrap()).abs() < 1e-12);
+        
+        let weighted = DcParser::with_timing_weight(0.5).calculate(&window);
+        assert_ne!(weighted.value, reported.value);
+    }
+    
     #[test]
     fn test_reason_codes() {
         let parser = DcParser::new();
@@ -353,4 +1729,103 @@ mod tests {
         // Should successfully compute (either LOW_COHERENT or COMPUTED depending on threshold)
         assert!(result.is_known(), "Should compute ΔC for valid conversation");
     }
+    
+    fn assert_same_dc(warm: &DcResult, cold: &DcResult) {
+        match (warm.value, cold.value) {
+            (Some(w), Some(c)) => assert!((w - c).abs() < 1e-12, "{} vs {}", w, c),
+            (w, c) => assert_eq!(w, c),
+        }
+        let (w, c) = (&warm.signals, &cold.signals);
+        assert!((w.thematic_drift - c.thematic_drift).abs() < 1e-12, "{} vs {}", w.thematic_drift, c.thematic_drift);
+        assert!((w.qa_mismatch - c.qa_mismatch).abs() < 1e-12, "{} vs {}", w.qa_mismatch, c.qa_mismatch);
+        assert_eq!(warm.reason, cold.reason);
+        assert_eq!(warm.pair_count, cold.pair_count);
+        assert_eq!(warm.dropped_threads, cold.dropped_threads);
+        assert_eq!(warm.matrix.len(), cold.matrix.len());
+        assert_eq!(warm.pairs.len(), cold.pairs.len());
+    }
+    
+    #[test]
+    fn test_state_gives_same_dc() {
+        let parser = DcParser::new();
+        let mut state = DcState::default();
+        let mut window = ConversationWindow::new();
+        let texts = [
+            ("A", "How is the garden doing?"),
+            ("B", "The roses are blooming, I water them daily"),
+            ("A", "Do they need more sun?"),
+            ("B", "Anyway, my car broke down yesterday"),
+        ];
+        for (speaker, text) in texts {
+            window.add_turn(Turn::new(speaker, text, 0.1));
+            assert_same_dc(&parser.calculate_with(&window, &mut state), &parser.calculate(&window));
+        }
+        
+        // One entry per turn, each pair scored once
+        assert_eq!(state.turns.len(), 4);
+        assert_eq!(state.pairs, 3);
+    }
+    
+    #[test]
+    fn test_state_follows_pruning_and_merges() {
+        use std::time::{Duration, Instant};
+        use crate::types::WindowPolicy;
+        
+        // Window IDF re-weights every turn as turns come and go; a corpus does not
+        let corpus = Arc::new(IdfCorpus::from_documents(["the hotel in Rome", "train tickets to Rome"]));
+        let parsers = [
+            DcParser::with_thematic_model(ThematicModel::TfIdf),
+            DcParser::with_corpus(corpus),
+            DcParser::with_thematic_model(ThematicModel::Jaccard),
+        ];
+        for parser in &parsers {
+            let mut state = DcState::default();
+            let mut window = ConversationWindow::with_policy(WindowPolicy::turns(4));
+            window.set_merge_gap(Some(Duration::from_secs(2)));
+            let base = Instant::now();
+            let turns = [
+                ("A", "Did you book the hotel in Rome?", 0),
+                ("A", "The one near the station?", 1),
+                ("B", "Yes, the hotel near the station", 5),
+                ("A", "Great, and the train tickets?", 9),
+                ("B", "Not yet", 13),
+                ("B", "I will book the train tonight", 14),
+                ("C", "Can I come along to Rome?", 20),
+                ("A", "Sure, the hotel has room", 24),
+                ("B", "Then I book three train tickets", 28),
+                ("C", "Which hotel was it again?", 32),
+                ("A", "The one near the station in Rome", 36),
+            ];
+            for (speaker, text, at) in turns {
+                let at = base + Duration::from_secs(at);
+                window.add_turn(Turn::new(speaker, text, 0.1).with_timestamp(at));
+                assert_same_dc(&parser.calculate_with(&window, &mut state), &parser.calculate(&window));
+            }
+            
+            // The same state also follows a window that was cleared
+            window.clear();
+            window.add_turn(Turn::new("A", "Fresh start", 0.1));
+            window.add_turn(Turn::new("B", "Fresh start indeed", 0.1));
+            assert_same_dc(&parser.calculate_with(&window, &mut state), &parser.calculate(&window));
+            assert_eq!(state.turns.len(), 2);
+        }
+    }
+    
+    #[test]
+    fn test_timeout_reason() {
+        use std::time::{Duration, Instant};
+        use crate::types::WindowPolicy;
+        
+        let long_ago = Instant::now() - Duration::from_secs(120);
+        let mut window = ConversationWindow::new();
+        window.add_turn(Turn::new("A", "How is the garden doing", 0.1).with_timestamp(long_ago));
+        window.add_turn(Turn::new("B", "The roses are blooming", 0.1));
+        assert_eq!(DcParser::new().calculate(&window).reason, DcReason::R013_DC_UNKNOWN_TIMEOUT);
+        
+        // Same turns under a turn-count policy: still paired
+        let mut window = ConversationWindow::with_policy(WindowPolicy::turns(20));
+        window.add_turn(Turn::new("A", "How is the garden doing", 0.1).with_timestamp(long_ago));
+        window.add_turn(Turn::new("B", "The roses are blooming", 0.1));
+        assert!(DcParser::new().calculate(&window).is_known());
+    }
 }
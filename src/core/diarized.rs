@@ -0,0 +1,274 @@
+//! Diarized speech transcripts: RTTM speaker turns plus segment JSON
+//!
+//! Offline speech tools give two files: speech-to-text segments (start,
+//! end, text, sometimes a speaker and word timings) and an RTTM file that
+//! says who spoke when. They are combined as follows:
+//! - A segment takes the RTTM speaker it overlaps most. With word timings
+//!   each word does, so a segment spanning a speaker change is split
+//! - Without RTTM, or with no overlap, the segment's own speaker is used,
+//!   then the nearest RTTM turn
+//! - Pieces are ordered by start time; consecutive pieces by one speaker
+//!   merge into one turn, so overlapping and back-to-back segments of the
+//!   same speaker become a single turn
+//!
+//! Turn offsets are the recording time at which each turn starts.
+//!
+//! Segment JSON is either a list of segments or an object with a
+//! `segments` list (Whisper-style output):
+//! `{"segments": [{"start": 0.0, "end": 2.4, "text": "Hi", "speaker": "SPEAKER_00"}]}`
+
+use serde::Deserialize;
+use crate::types::{
+    Transcript, TranscriptTurn, TranscriptFormat, ImportReason, RttmTurn, SpeechSegment,
+};
+
+/// Speaker name when neither RTTM nor the segment names one
+pub const UNKNOWN_SPEAKER: &str = "unknown";
+
+/// A speaker-attributed stretch of text before turns are merged
+#[derive(Debug)]
+struct Piece {
+    speaker: String,
+    text: String,
+    start_ms: u64,
+}
+
+/// Read a segment JSON file and an optional RTTM file and combine them
+pub fn load_diarized(segments_path: &str, rttm_path: Option<&str>) -> Result<Transcript, ImportReason> {
+    let json = std::fs::read_to_string(segments_path).map_err(|_| ImportReason::R401_IMPORT_READ_ERROR)?;
+    let rttm = match rttm_path {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).map_err(|_| ImportReason::R401_IMPORT_READ_ERROR)?;
+            parse_rttm(&text)
+        }
+        None => Vec::new(),
+    };
+    combine_diarized(&parse_segments(&json)?, &rttm)
+}
+
+/// Parse RTTM `SPEAKER` lines, sorted by onset (other lines are skipped)
+///
+/// `SPEAKER <file> <channel> <onset> <duration> <NA> <NA> <speaker> <NA> <NA>`
+pub fn parse_rttm(text: &str) -> Vec<RttmTurn> {
+    let mut turns: Vec<RttmTurn> = text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 8 || fields[0] != "SPEAKER" {
+                return None;
+            }
+            let onset: f64 = fields[3].parse().ok()?;
+            let duration: f64 = fields[4].parse().ok()?;
+            if !(onset >= 0.0 && duration >= 0.0) {
+                return None;
+            }
+            Some(RttmTurn {
+                speaker: fields[7].to_string(),
+                start_ms: secs_to_ms(onset),
+                end_ms: secs_to_ms(onset + duration),
+            })
+        })
+        .collect();
+    turns.sort_by_key(|t| t.start_ms);
+    turns
+}
+
+/// Parse segment JSON: a list of segments or `{"segments": [...]}`
+pub fn parse_segments(json: &str) -> Result<Vec<SpeechSegment>, ImportReason> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SegmentFile {
+        List(Vec<SpeechSegment>),
+        Object { segments: Vec<SpeechSegment> },
+    }
+    
+    match serde_json::from_str(json) {
+        Ok(SegmentFile::List(segments)) | Ok(SegmentFile::Object { segments }) => Ok(segments),
+        Err(_) => Err(ImportReason::R403_IMPORT_INVALID_SEGMENTS),
+    }
+}
+
+/// Attribute segments to RTTM speakers and merge them into turns
+pub fn combine_diarized(segments: &[SpeechSegment], rttm: &[RttmTurn]) -> Result<Transcript, ImportReason> {
+    let mut pieces: Vec<Piece> = Vec::new();
+    for segment in segments {
+        let start = secs_to_ms(segment.start);
+        let end = secs_to_ms(segment.end).max(start);
+        let own = segment.speaker.as_deref();
+        
+        let aligned = !rttm.is_empty()
+            && !segment.words.is_empty()
+            && segment.words.iter().all(|w| w.start.is_some());
+        if aligned {
+            for word in &segment.words {
+                let word_start = secs_to_ms(word.start.unwrap_or(segment.start));
+                let word_end = word.end.map_or(word_start, secs_to_ms).max(word_start);
+                let text = word.word.trim();
+                if !text.is_empty() {
+                    pieces.push(Piece {
+                        speaker: speaker_for(rttm, word_start, word_end, own),
+                        text: text.to_string(),
+                        start_ms: word_start,
+                    });
+                }
+            }
+        } else if !segment.text.trim().is_empty() {
+            pieces.push(Piece {
+                speaker: speaker_for(rttm, start, end, own),
+                text: segment.text.trim().to_string(),
+                start_ms: start,
+            });
+        }
+    }
+    
+    // Stable sort keeps word order within a segment
+    pieces.sort_by_key(|p| p.start_ms);
+    
+    let mut turns: Vec<TranscriptTurn> = Vec::new();
+    for piece in pieces {
+        match turns.last_mut() {
+            Some(last) if last.speaker == piece.speaker => {
+                last.text.push(' ');
+                last.text.push_str(&piece.text);
+            }
+            _ => turns.push(TranscriptTurn::new(piece.speaker, piece.text).at_ms(piece.start_ms)),
+        }
+    }
+    
+    if turns.is_empty() {
+        return Err(ImportReason::R402_IMPORT_NO_TURNS);
+    }
+    Ok(Transcript { format: TranscriptFormat::Diarized, turns })
+}
+
+/// Speaker for `start..end`: most-overlapping RTTM turn, then the segment's own, then the nearest
+fn speaker_for(rttm: &[RttmTurn], start_ms: u64, end_ms: u64, own: Option<&str>) -> String {
+    // Zero-length words still fall inside a turn
+    let end_ms = end_ms.max(start_ms.saturating_add(1));
+    let best = rttm.iter()
+        .map(|t| (t, t.overlap_ms(start_ms, end_ms)))
+        .filter(|(_, overlap)| *overlap > 0)
+        .max_by_key(|(_, overlap)| *overlap);
+    if let Some((turn, _)) = best {
+        return turn.speaker.clone();
+    }
+    
+    own.map(str::to_string)
+        .or_else(|| {
+            rttm.iter()
+                .min_by_key(|t| t.start_ms.saturating_sub(end_ms).max(start_ms.saturating_sub(t.end_ms)))
+                .map(|t| t.speaker.clone())
+        })
+        .unwrap_or_else(|| UNKNOWN_SPEAKER.to_string())
+}
+
+fn secs_to_ms(secs: f64) -> u64 {
+    (secs.max(0.0) * 1000.0).round() as u64
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{analyze_transcript, AnalyzeOptions, Scorers};
+    use crate::types::SpeechWord;
+    
+    const RTTM: &str = "\
+SPEAKER call 1 0.00 4.00 <NA> <NA> spk_a <NA> <NA>
+SPEAKER call 1 4.20 3.00 <NA> <NA> spk_b <NA> <NA>
+;; comment lines and other record types are ignored
+SPEAKER call 1 7.50 2.50 <NA> <NA> spk_a <NA> <NA>
+";
+
+    fn segment(start: f64, end: f64, text: &str) -> SpeechSegment {
+        SpeechSegment { start, end, text: text.to_string(), speaker: None, words: Vec::new() }
+    }
+    
+    fn word(word: &str, start: f64, end: f64) -> SpeechWord {
+        SpeechWord { word: word.to_string(), start: Some(start), end: Some(end) }
+    }
+    
+    #[test]
+    fn test_parse_rttm() {
+        let turns = parse_rttm(RTTM);
+        assert_eq!(turns.len(), 3);
+        assert_eq!(turns[1], RttmTurn { speaker: "spk_b".to_string(), start_ms: 4200, end_ms: 7200 });
+    }
+    
+    #[test]
+    fn test_parse_segment_json_shapes() {
+        let object = r#"{"language": "en", "segments": [{"start": 0.0, "end": 1.5, "text": " Hello", "speaker": "SPEAKER_00"}]}"#;
+        let list = r#"[{"start": 0.0, "end": 1.5, "text": "Hello", "words": [{"text": "Hello", "start": 0.1, "end": 0.6}]}]"#;
+        assert_eq!(parse_segments(object).unwrap()[0].speaker.as_deref(), Some("SPEAKER_00"));
+        assert_eq!(parse_segments(list).unwrap()[0].words[0].word, "Hello");
+        assert_eq!(parse_segments("{\"segments\": 3}").unwrap_err(), ImportReason::R403_IMPORT_INVALID_SEGMENTS);
+    }
+    
+    #[test]
+    fn test_segments_take_overlapping_speaker_and_merge() {
+        let segments = vec![
+            segment(0.0, 2.0, "So how was the trip?"),
+            segment(2.0, 3.9, "Tell me everything."),
+            segment(4.3, 7.0, "It was long but worth it."),
+            segment(7.6, 9.5, "Worth it how?"),
+        ];
+        let transcript = combine_diarized(&segments, &parse_rttm(RTTM)).unwrap();
+        let speakers: Vec<&str> = transcript.turns.iter().map(|t| t.speaker.as_str()).collect();
+        
+        assert_eq!(speakers, vec!["spk_a", "spk_b", "spk_a"]);
+        assert_eq!(transcript.turns[0].text, "So how was the trip? Tell me everything.");
+        assert_eq!(transcript.turns[1].offset_ms, Some(4300));
+        assert_eq!(transcript.format, TranscriptFormat::Diarized);
+    }
+    
+    #[test]
+    fn test_word_timings_split_segment_at_speaker_change() {
+        // One ASR segment runs across the change from spk_a to spk_b
+        let mut crossing = segment(3.0, 5.0, "fine thanks yes");
+        crossing.words = vec![word("fine", 3.0, 3.4), word("thanks", 3.5, 3.9), word("yes", 4.3, 4.6)];
+        let transcript = combine_diarized(&[crossing], &parse_rttm(RTTM)).unwrap();
+        
+        assert_eq!(transcript.turns.len(), 2);
+        assert_eq!(transcript.turns[0].text, "fine thanks");
+        assert_eq!(transcript.turns[1].speaker, "spk_b");
+        assert_eq!(transcript.turns[1].offset_ms, Some(4300));
+    }
+    
+    #[test]
+    fn test_fallback_speakers() {
+        let mut own = segment(20.0, 21.0, "After the RTTM ends");
+        own.speaker = Some("SPEAKER_07".to_string());
+        let orphan = segment(30.0, 31.0, "Nobody claims this");
+        
+        let transcript = combine_diarized(&[own.clone(), orphan.clone()], &parse_rttm(RTTM)).unwrap();
+        assert_eq!(transcript.turns[0].speaker, "SPEAKER_07");
+        assert_eq!(transcript.turns[1].speaker, "spk_a"); // nearest RTTM turn
+        
+        let transcript = combine_diarized(&[orphan], &[]).unwrap();
+        assert_eq!(transcript.turns[0].speaker, UNKNOWN_SPEAKER);
+    }
+    
+    #[test]
+    fn test_turns_feed_analysis() {
+        let segments = vec![
+            segment(0.0, 3.9, "The garden needs water today"),
+            segment(4.3, 7.0, "The garden does need water"),
+            segment(7.6, 9.5, "I will water the garden"),
+        ];
+        let transcript = combine_diarized(&segments, &parse_rttm(RTTM)).unwrap();
+        let analysis = analyze_transcript(&transcript, &Scorers::default(), &AnalyzeOptions::default(), |_| [0; 64]);
+        let turns = &analysis.report.turns;
+        
+        assert_eq!(analysis.report.speakers, vec!["spk_a", "spk_b"]);
+        assert_eq!(turns.iter().map(|t| t.offset_ms).collect::<Vec<_>>(), vec![0, 4300, 7600]);
+        assert!(turns[1].dc.is_some() && turns[2].dc.is_some());
+    }
+    
+    #[test]
+    fn test_speaker_at_end_of_time() {
+        let rttm = [RttmTurn { speaker: "spk_a".to_string(), start_ms: u64::MAX - 10, end_ms: u64::MAX }];
+        assert_eq!(speaker_for(&rttm, u64::MAX, u64::MAX, None), "spk_a");
+    }
+}
@@ -5,14 +5,26 @@
 //! - APPROACHING → LOCKED: r < 0.15 AND stable ≥ 8 sec
 //! - LOCKED → DRIFT: r ≥ 0.15 (immediate)
 //! - DRIFT → APPROACHING: r < 0.25
+//!
+//! Turn confidence:
+//! - Turns below full-weight confidence move r only partially
+//! - Turns below the minimum confidence cannot start or sustain LOCKED
 
 use std::time::Instant;
 use crate::{
     R_THRESHOLD_LOCKED, R_THRESHOLD_APPROACHING, R_THRESHOLD_DRIFT,
-    STABILITY_DURATION_MS,
+    STABILITY_DURATION_MS, CONFIDENCE_MIN_LOCK, CONFIDENCE_FULL_WEIGHT,
 };
 use crate::types::{FacelockState, ReasonCode, StateOutput};
 
+/// Minimum lock confidence from user input: finite, 0.0 to 1.0
+pub fn min_confidence_from(value: f64) -> Result<f64, String> {
+    if !(0.0..=1.0).contains(&value) {
+        return Err("minimum confidence must be between 0.0 and 1.0".to_string());
+    }
+    Ok(value)
+}
+
 /// Facelock state machine engine
 #[derive(Debug)]
 pub struct FacelockEngine {
@@ -30,6 +42,10 @@ pub struct FacelockEngine {
     last_input: Instant,
     /// Number of updates
     update_count: u64,
+    /// Minimum turn confidence to start or sustain a lock
+    min_confidence: f64,
+    /// Ring the terminal bell on LOCKED
+    bell: bool,
 }
 
 impl Default for FacelockEngine {
@@ -50,12 +66,42 @@ impl FacelockEngine {
             first_input: None,
             last_input: now,
             update_count: 0,
+            min_confidence: CONFIDENCE_MIN_LOCK,
+            bell: true,
         }
     }
     
-    /// Update with new r value, return output with state and reason
+    /// Create engine with custom minimum lock confidence
+    pub fn with_min_confidence(min_confidence: f64) -> Self {
+        Self {
+            min_confidence: min_confidence.clamp(0.0, 1.0),
+            ..Self::new()
+        }
+    }
+    
+    /// Update with new r value at full confidence
     pub fn update(&mut self, r: f64) -> StateOutput {
-        let now = Instant::now();
+        self.update_with_confidence(r, 1.0)
+    }
+    
+    /// Update with new r value and the turn's confidence, return output with state and reason
+    pub fn update_with_confidence(&mut self, raw_r: f64, confidence: f64) -> StateOutput {
+        self.update_at(raw_r, confidence, Instant::now())
+    }
+    
+    /// Update at an explicit time (replayed transcripts); `now` should not go backwards
+    pub fn update_at(&mut self, raw_r: f64, confidence: f64, now: Instant) -> StateOutput {
+        let now = now.max(self.last_input);
+        
+        // Low-confidence turns only move r part of the way (first input counts fully)
+        let weight = confidence_weight(confidence);
+        let r = if self.first_input.is_some() {
+            self.last_r + (raw_r - self.last_r) * weight
+        } else {
+            raw_r
+        };
+        let confident = confidence >= self.min_confidence;
+        
         self.last_input = now;
         self.last_r = r;
         self.update_count += 1;
@@ -65,8 +111,8 @@ impl FacelockEngine {
             self.first_input = Some(now);
         }
         
-        // Calculate stability
-        let is_lock_candidate = r < R_THRESHOLD_LOCKED;
+        // Calculate stability (only confident turns can start or sustain it)
+        let is_lock_candidate = r < R_THRESHOLD_LOCKED && confident;
         
         if is_lock_candidate {
             if self.lock_candidate_since.is_none() {
@@ -81,7 +127,26 @@ impl FacelockEngine {
             .unwrap_or(0);
         
         // Determine transition and reason
-        let (new_state, reason) = self.compute_transition(r, stable_ms);
+        let (mut new_state, mut reason) = self.compute_transition(r, stable_ms);
+        
+        if !confident && r < R_THRESHOLD_LOCKED {
+            // Too little text to start or sustain a lock
+            match new_state {
+                FacelockState::Locked => {
+                    new_state = FacelockState::Approaching;
+                    reason = ReasonCode::R006_CONFIDENCE_BELOW_MIN;
+                }
+                FacelockState::Approaching if self.state == FacelockState::Approaching => {
+                    reason = ReasonCode::R006_CONFIDENCE_BELOW_MIN;
+                }
+                _ => {}
+            }
+        } else if weight < 1.0 && new_state == self.state
+            && self.compute_transition(raw_r, stable_ms).0 != new_state
+        {
+            // Damping held back a transition the raw r would have caused
+            reason = ReasonCode::R006_CONFIDENCE_DAMPED;
+        }
         
         // Apply transition if changed
         if new_state != self.state {
@@ -89,7 +154,7 @@ impl FacelockEngine {
             self.state_since = now;
             
             // Ring bell on LOCKED transition
-            if new_state == FacelockState::Locked {
+            if new_state == FacelockState::Locked && self.bell {
                 print!("\x07"); // Terminal bell
             }
         }
@@ -159,7 +224,7 @@ impl FacelockEngine {
     /// Get stability duration in milliseconds
     pub fn stable_ms(&self) -> u64 {
         self.lock_candidate_since
-            .map(|s| Instant::now().duration_since(s).as_millis() as u64)
+            .map(|s| Instant::now().max(self.last_input).duration_since(s).as_millis() as u64)
             .unwrap_or(0)
     }
     
@@ -188,12 +253,29 @@ impl FacelockEngine {
         )
     }
     
-    /// Reset engine to initial state
+    /// Get minimum lock confidence
+    pub fn min_confidence(&self) -> f64 {
+        self.min_confidence
+    }
+    
+    /// Enable or disable the terminal bell on LOCKED (off for batch output)
+    pub fn set_bell(&mut self, bell: bool) {
+        self.bell = bell;
+    }
+    
+    /// Reset engine to initial state (keeps configuration)
     pub fn reset(&mut self) {
-        *self = Self::new();
+        let bell = self.bell;
+        *self = Self::with_min_confidence(self.min_confidence);
+        self.bell = bell;
     }
 }
 
+/// Weight of a turn's r based on its confidence (0.0-1.0)
+fn confidence_weight(confidence: f64) -> f64 {
+    (confidence / CONFIDENCE_FULL_WEIGHT).clamp(0.0, 1.0)
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -245,6 +327,20 @@ mod tests {
         assert_eq!(output.state, FacelockState::Locked);
     }
     
+    #[test]
+    fn test_update_at_uses_given_clock() {
+        let mut engine = FacelockEngine::new();
+        let start = Instant::now();
+        engine.update_at(0.20, 1.0, start); // → APPROACHING
+        engine.update_at(0.10, 1.0, start + Duration::from_secs(1));
+        
+        // Transcript time, no sleeping
+        let output = engine.update_at(0.10, 1.0, start + Duration::from_secs(10));
+        assert_eq!(output.state, FacelockState::Locked);
+        assert_eq!(output.stable_ms, 9000);
+        assert!(engine.stable_ms() >= 9000);
+    }
+    
     #[test]
     fn test_locked_to_drift_immediate() {
         let mut engine = FacelockEngine::new();
@@ -286,6 +382,67 @@ mod tests {
         assert_eq!(engine.stable_ms(), 0);
     }
     
+    #[test]
+    fn test_low_confidence_cannot_start_lock() {
+        let mut engine = FacelockEngine::new();
+        engine.update(0.20); // → APPROACHING
+        
+        // Lock-level r, but a four-word turn
+        let output = engine.update_with_confidence(0.0, 0.08);
+        assert!(output.r < 0.15);
+        assert_eq!(output.state, FacelockState::Approaching);
+        assert_eq!(output.reason, ReasonCode::R006_CONFIDENCE_BELOW_MIN);
+        assert_eq!(engine.stable_ms(), 0);
+    }
+    
+    #[test]
+    fn test_low_confidence_cannot_sustain_lock() {
+        let mut engine = FacelockEngine::new();
+        engine.update(0.20); // → APPROACHING
+        engine.update(0.10);
+        sleep(Duration::from_millis(STABILITY_DURATION_MS + 100));
+        engine.update(0.10); // → LOCKED
+        assert_eq!(engine.state(), FacelockState::Locked);
+        
+        let output = engine.update_with_confidence(0.05, 0.04);
+        assert_eq!(output.state, FacelockState::Approaching);
+        assert_eq!(output.reason, ReasonCode::R006_CONFIDENCE_BELOW_MIN);
+    }
+    
+    #[test]
+    fn test_low_confidence_damps_transition() {
+        let mut engine = FacelockEngine::with_min_confidence(0.0);
+        engine.update(0.20); // → APPROACHING
+        
+        // Short shout: raw r would cause DRIFT, damped r does not
+        let output = engine.update_with_confidence(0.50, 0.04);
+        assert_eq!(output.state, FacelockState::Approaching);
+        assert_eq!(output.reason, ReasonCode::R006_CONFIDENCE_DAMPED);
+        assert!(output.r < 0.30);
+        
+        // Same r from a long turn drifts
+        let output = engine.update_with_confidence(0.50, 1.0);
+        assert_eq!(output.state, FacelockState::Drift);
+    }
+    
+    #[test]
+    fn test_min_confidence_input() {
+        assert_eq!(min_confidence_from(0.3), Ok(0.3));
+        assert_eq!(min_confidence_from(1.0), Ok(1.0));
+        for bad in [f64::NAN, f64::INFINITY, -0.1, 1.5] {
+            assert!(min_confidence_from(bad).is_err(), "{} accepted", bad);
+        }
+    }
+    
+    #[test]
+    fn test_reset_keeps_min_confidence() {
+        let mut engine = FacelockEngine::with_min_confidence(0.3);
+        engine.update(0.20);
+        engine.reset();
+        assert_eq!(engine.state(), FacelockState::Waiting);
+        assert!((engine.min_confidence() - 0.3).abs() < 1e-9);
+    }
+    
     #[test]
     fn test_proof_only_in_locked() {
         let mut engine = FacelockEngine::new();
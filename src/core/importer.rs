@@ -0,0 +1,654 @@
+//! Transcript importer: chat exports, subtitles and plain logs
+//!
+//! Supported formats:
+//! - WhatsApp: Android ("12/03/2024, 14:05 - Name: text") and iOS
+//!   ("[12/03/2024, 14:05:33] Name: text"), 12 h clocks included. Day and
+//!   month order is inferred from the whole file (DD/MM unless a value
+//!   over 12 shows otherwise)
+//! - Signal: "[2024-03-12 14:05] Name: text"
+//! - SRT / WebVTT: cue start times; speaker from a `<v Name>` voice tag or a
+//!   "Name:" / "- Name:" prefix. Untagged lines continue the last speaker
+//! - Log: "Name: text", optionally prefixed by "[HH:MM:SS]", "HH:MM" or a
+//!   date and time
+//! - Diarized: speech-to-text segment JSON (see `diarized` for RTTM)
+//!
+//! Lines without a message header continue the previous message. System
+//! lines ("Messages are end-to-end encrypted") and media placeholders are
+//! dropped. Logs and subtitles have no header to tell "Note: buy milk" from
+//! a speaker, so there a "Name:" prefix needs two letters (or a single
+//! capital, "A:") and must be reused on `MIN_SPEAKER_LINES` lines. One-off
+//! names only count while fewer than two names are reused (a short
+//! exchange). Other prefixed lines count as lines without a prefix. Offsets are relative to the first timestamp and never go
+//! backwards; log clock times that jump back more than 12 h wrap past
+//! midnight.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use regex::Regex;
+use lazy_static::lazy_static;
+use chrono::NaiveDate;
+use crate::core::diarized::{parse_segments, combine_diarized};
+use crate::types::{Transcript, TranscriptTurn, TranscriptFormat, ImportReason};
+
+/// Longest accepted speaker name (characters)
+const MAX_SPEAKER_LEN: usize = 40;
+
+/// Most words in a speaker name ("Anna van der Berg")
+const MAX_SPEAKER_WORDS: usize = 4;
+
+/// Lines a "Name:" prefix must open before logs and subtitles take it as a speaker
+const MIN_SPEAKER_LINES: usize = 2;
+
+/// Non-empty lines inspected for format detection
+const DETECT_LINES: usize = 20;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Message bodies that stand in for media or deleted messages
+const PLACEHOLDERS: &[&str] = &[
+    "<media omitted>", "<attached:", "image omitted", "video omitted", "audio omitted",
+    "sticker omitted", "gif omitted", "document omitted", "contact card omitted",
+    "this message was deleted", "you deleted this message", "null",
+];
+
+lazy_static! {
+    /// WhatsApp header: date, time, optional AM/PM, then the message (iOS brackets or Android dash)
+    static ref WHATSAPP_LINE: Regex = Regex::new(
+        r"^\[?(\d{1,2})[/.\-](\d{1,2})[/.\-](\d{2,4}),?\s+(\d{1,2})[:.](\d{2})(?:[:.](\d{2}))?\s*([AaPp])?\.?\s*[Mm]?\.?\]?\s*(?:-\s+)?(.*)$"
+    ).unwrap();
+    
+    /// Signal header: ISO date and time in brackets
+    static ref SIGNAL_LINE: Regex = Regex::new(
+        r"^\[(\d{4})-(\d{2})-(\d{2})[ T](\d{2}):(\d{2})(?::(\d{2}))?\]\s*(.*)$"
+    ).unwrap();
+    
+    /// Subtitle cue time ("00:01:02,500" or "01:02.500")
+    static ref CUE_TIME: Regex = Regex::new(r"(?:(\d+):)?(\d{1,2}):(\d{2})[,.](\d{1,3})").unwrap();
+    
+    /// WebVTT voice tag at the start of a cue line
+    static ref VOICE_TAG: Regex = Regex::new(r"^<v(?:\.[^\s>]+)?\s+([^>]+)>(.*)$").unwrap();
+    
+    /// Formatting tags and sound descriptions in subtitle text
+    static ref CUE_MARKUP: Regex = Regex::new(r"<[^>]*>|\{\\[^}]*\}|\[[^\]]*\]").unwrap();
+    
+    /// Log timestamp prefix: bracketed, or a bare (date and) time
+    static ref LOG_STAMP: Regex = Regex::new(
+        r"^(?:\[([^\]]+)\]|(\d{4}-\d{2}-\d{2}[ T]\d{1,2}:\d{2}(?::\d{2}(?:[.,]\d{1,3})?)?|\d{1,2}:\d{2}(?::\d{2}(?:[.,]\d{1,3})?)?))\s*(?:-\s+)?(.*)$"
+    ).unwrap();
+    
+    /// Clock time with optional date ("14:05", "00:01:23.5", "2024-03-12 14:05:33")
+    static ref CLOCK: Regex = Regex::new(
+        r"^(?:(\d{4})-(\d{2})-(\d{2})[ T])?(\d{1,2}):(\d{2})(?::(\d{2})(?:[.,](\d{1,3}))?)?$"
+    ).unwrap();
+}
+
+/// A turn before offsets are resolved
+#[derive(Debug)]
+struct RawTurn {
+    speaker: String,
+    text: String,
+    /// Absolute time in ms (epoch for dated stamps, time of day otherwise)
+    at_ms: Option<i64>,
+}
+
+impl RawTurn {
+    fn new(speaker: &str, text: &str, at_ms: Option<i64>) -> Self {
+        Self {
+            speaker: speaker.to_string(),
+            text: text.to_string(),
+            at_ms,
+        }
+    }
+    
+    /// Append a continuation line
+    fn push_line(&mut self, line: &str) {
+        if !self.text.is_empty() {
+            self.text.push('\n');
+        }
+        self.text.push_str(line);
+    }
+}
+
+/// Read and parse a transcript file; the format is detected when not given
+pub fn load_transcript(path: &str, format: Option<TranscriptFormat>) -> Result<Transcript, ImportReason> {
+    let text = std::fs::read_to_string(path).map_err(|_| ImportReason::R401_IMPORT_READ_ERROR)?;
+    let format = format
+        .or_else(|| format_from_extension(path))
+        .unwrap_or_else(|| detect_format(&text));
+    parse_transcript(&text, format)
+}
+
+/// Parse a transcript in a known format
+pub fn parse_transcript(text: &str, format: TranscriptFormat) -> Result<Transcript, ImportReason> {
+    let text = text.trim_start_matches('\u{feff}');
+    let raw = match format {
+        TranscriptFormat::WhatsApp => parse_whatsapp(text),
+        TranscriptFormat::Signal => parse_signal(text),
+        TranscriptFormat::Srt | TranscriptFormat::WebVtt => parse_subtitles(text),
+        TranscriptFormat::Log => parse_log(text),
+        TranscriptFormat::Diarized => return combine_diarized(&parse_segments(text)?, &[]),
+    };
+    let turns = resolve_offsets(raw, format == TranscriptFormat::Log);
+    if turns.is_empty() {
+        return Err(ImportReason::R402_IMPORT_NO_TURNS);
+    }
+    Ok(Transcript { format, turns })
+}
+
+/// Parse a transcript, detecting its format from the content
+pub fn import_transcript(text: &str) -> Result<Transcript, ImportReason> {
+    parse_transcript(text, detect_format(text))
+}
+
+/// Guess the format from the first lines of a transcript
+pub fn detect_format(text: &str) -> TranscriptFormat {
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    if text.starts_with("WEBVTT") {
+        return TranscriptFormat::WebVtt;
+    }
+    if text.starts_with('{') || (text.starts_with('[') && text[1..].trim_start().starts_with('{')) {
+        return TranscriptFormat::Diarized;
+    }
+    
+    let lines: Vec<&str> = text.lines()
+        .map(clean_line)
+        .filter(|l| !l.is_empty())
+        .take(DETECT_LINES)
+        .collect();
+    if lines.iter().any(|l| l.contains("-->") && CUE_TIME.is_match(l)) {
+        return TranscriptFormat::Srt;
+    }
+    
+    let whatsapp = lines.iter().filter(|l| WHATSAPP_LINE.is_match(l)).count();
+    let signal = lines.iter().filter(|l| SIGNAL_LINE.is_match(l)).count();
+    if whatsapp > 0 && whatsapp >= signal {
+        TranscriptFormat::WhatsApp
+    } else if signal > 0 {
+        TranscriptFormat::Signal
+    } else {
+        TranscriptFormat::Log
+    }
+}
+
+/// Format implied by a file extension (subtitles and JSON; chats are .txt)
+fn format_from_extension(path: &str) -> Option<TranscriptFormat> {
+    match Path::new(path).extension()?.to_str()?.to_lowercase().as_str() {
+        "srt" => Some(TranscriptFormat::Srt),
+        "vtt" => Some(TranscriptFormat::WebVtt),
+        "json" => Some(TranscriptFormat::Diarized),
+        _ => None,
+    }
+}
+
+/// Trim a line and drop the direction marks chat apps insert
+fn clean_line(line: &str) -> &str {
+    line.trim_matches(|c: char| c.is_whitespace() || c == '\u{200e}' || c == '\u{200f}')
+}
+
+/// Split "Name: text"; names are short and carry no sentence punctuation
+fn split_speaker(line: &str) -> Option<(&str, &str)> {
+    let (name, text) = line.split_once(':')?;
+    let name = name.trim();
+    if name.is_empty()
+        || name.chars().count() > MAX_SPEAKER_LEN
+        || name.split_whitespace().count() > MAX_SPEAKER_WORDS
+        || name.contains(['?', '!', '"', '<', '>'])
+        || text.starts_with("//")
+    {
+        return None;
+    }
+    Some((name, clean_line(text)))
+}
+
+/// Names of the candidate "Name:" lines that are speakers: two letters or a
+/// single capital ("A", "Q"), and reused unless too few names are
+fn speaker_names<'a>(candidates: impl Iterator<Item = &'a str>) -> HashSet<&'a str> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for (name, _) in candidates.filter_map(split_speaker) {
+        let letters = name.chars().filter(|c| c.is_alphabetic()).count();
+        let capital = name.chars().count() == 1 && name.chars().all(|c| c.is_uppercase());
+        if letters >= 2 || capital {
+            *counts.entry(name).or_insert(0) += 1;
+        }
+    }
+    let reused = counts.values().filter(|&&count| count >= MIN_SPEAKER_LINES).count();
+    counts.into_iter()
+        .filter(|&(_, count)| count >= MIN_SPEAKER_LINES || reused < 2)
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// `split_speaker`, limited to the given names
+fn known_speaker<'a>(line: &'a str, names: &HashSet<&str>) -> Option<(&'a str, &'a str)> {
+    split_speaker(line).filter(|(name, _)| names.contains(name))
+}
+
+/// Does the message stand in for media or a deleted message?
+fn is_placeholder(text: &str) -> bool {
+    let text = text.to_lowercase();
+    PLACEHOLDERS.iter().any(|p| text.starts_with(p))
+}
+
+/// Epoch milliseconds of a calendar date and time
+fn epoch_ms(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<i64> {
+    NaiveDate::from_ymd_opt(year, month, day)?
+        .and_hms_opt(hour, minute, second)
+        .map(|t| t.and_utc().timestamp_millis())
+}
+
+/// Drop empty and placeholder turns and turn absolute times into offsets
+fn resolve_offsets(raw: Vec<RawTurn>, wrap_midnight: bool) -> Vec<TranscriptTurn> {
+    let mut turns = Vec::with_capacity(raw.len());
+    let mut start: Option<i64> = None;
+    let mut last: Option<i64> = None;
+    let mut shift = 0;
+    
+    for turn in raw {
+        let text = turn.text.trim();
+        if text.is_empty() || is_placeholder(text) {
+            continue;
+        }
+        let offset_ms = turn.at_ms.map(|at| {
+            let mut at = at + shift;
+            if let Some(prev) = last {
+                if wrap_midnight && prev - at > DAY_MS / 2 {
+                    shift += DAY_MS;
+                    at += DAY_MS;
+                }
+                at = at.max(prev);
+            }
+            last = Some(at);
+            (at - *start.get_or_insert(at)) as u64
+        });
+        turns.push(TranscriptTurn {
+            speaker: turn.speaker,
+            text: text.to_string(),
+            offset_ms,
+        });
+    }
+    turns
+}
+
+/// WhatsApp header fields, before day/month order is known
+struct WhatsAppHeader<'a> {
+    first: u32,
+    second: u32,
+    year: i32,
+    hour: u32,
+    minute: u32,
+    second_of_minute: u32,
+    rest: &'a str,
+}
+
+fn parse_whatsapp(text: &str) -> Vec<RawTurn> {
+    // First pass: headers and continuation lines
+    let lines: Vec<(Option<WhatsAppHeader>, &str)> = text.lines()
+        .map(clean_line)
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let header = WHATSAPP_LINE.captures(line).map(|c| {
+                let num = |i: usize| c.get(i).map_or(0, |m| m.as_str().parse::<u32>().unwrap_or(0));
+                let mut hour = num(4);
+                match c.get(7).map(|m| m.as_str().to_ascii_lowercase()) {
+                    Some(ref p) if p == "p" && hour < 12 => hour += 12,
+                    Some(ref p) if p == "a" && hour == 12 => hour = 0,
+                    _ => {}
+                }
+                let year = num(3) as i32;
+                WhatsAppHeader {
+                    first: num(1),
+                    second: num(2),
+                    year: if year < 100 { 2000 + year } else { year },
+                    hour,
+                    minute: num(5),
+                    second_of_minute: num(6),
+                    rest: c.get(8).map_or("", |m| m.as_str()),
+                }
+            });
+            (header, line)
+        })
+        .collect();
+    
+    // Month first only when no first field exceeds 12 but some second field does
+    let headers = || lines.iter().filter_map(|(h, _)| h.as_ref());
+    let month_first = !headers().any(|h| h.first > 12) && headers().any(|h| h.second > 12);
+    
+    let mut turns: Vec<RawTurn> = Vec::new();
+    let mut open = false;
+    for (header, line) in &lines {
+        match header {
+            Some(h) => {
+                let (month, day) = if month_first { (h.first, h.second) } else { (h.second, h.first) };
+                let at = epoch_ms(h.year, month, day, h.hour, h.minute, h.second_of_minute);
+                // Lines without "Name:" are system messages
+                open = match split_speaker(h.rest) {
+                    Some((speaker, body)) => {
+                        turns.push(RawTurn::new(speaker, body, at));
+                        true
+                    }
+                    None => false,
+                };
+            }
+            None if open => turns.last_mut().unwrap().push_line(line),
+            None => {}
+        }
+    }
+    turns
+}
+
+fn parse_signal(text: &str) -> Vec<RawTurn> {
+    let mut turns: Vec<RawTurn> = Vec::new();
+    let mut open = false;
+    for line in text.lines().map(clean_line).filter(|l| !l.is_empty()) {
+        match SIGNAL_LINE.captures(line) {
+            Some(c) => {
+                let num = |i: usize| c.get(i).map_or(0, |m| m.as_str().parse::<u32>().unwrap_or(0));
+                let at = epoch_ms(num(1) as i32, num(2), num(3), num(4), num(5), num(6));
+                open = match split_speaker(c.get(7).map_or("", |m| m.as_str())) {
+                    Some((speaker, body)) => {
+                        turns.push(RawTurn::new(speaker, body, at));
+                        true
+                    }
+                    None => false,
+                };
+            }
+            None if open => turns.last_mut().unwrap().push_line(line),
+            None => {}
+        }
+    }
+    turns
+}
+
+/// Milliseconds of a subtitle cue time
+fn cue_ms(text: &str) -> Option<i64> {
+    let c = CUE_TIME.captures(text)?;
+    let num = |i: usize| c.get(i).map_or(0, |m| m.as_str().parse::<i64>().unwrap_or(0));
+    let frac = c.get(4).map_or("0", |m| m.as_str());
+    let millis = frac.parse::<i64>().unwrap_or(0) * 10_i64.pow(3 - frac.len() as u32);
+    Some(((num(1) * 60 + num(2)) * 60 + num(3)) * 1000 + millis)
+}
+
+/// Cue line without a voice tag, from where a "Name:" prefix would start
+fn untagged_cue_text(line: &str) -> Option<&str> {
+    if VOICE_TAG.is_match(line) {
+        return None;
+    }
+    Some(line.trim_start_matches(['-', '>']).trim_start())
+}
+
+/// Speaker tag and text of one cue line (prefixes only for the given names)
+fn cue_line<'a>(line: &'a str, names: &HashSet<&str>) -> (Option<&'a str>, String) {
+    let (speaker, rest) = match VOICE_TAG.captures(line) {
+        Some(c) => (c.get(1).map(|m| m.as_str().trim()), c.get(2).map_or("", |m| m.as_str())),
+        None => (None, line),
+    };
+    let rest = rest.trim_start_matches(['-', '>']).trim_start();
+    let (speaker, rest) = match speaker {
+        Some(speaker) => (Some(speaker), rest),
+        None => match known_speaker(rest, names) {
+            Some((speaker, text)) => (Some(speaker), text),
+            None => (None, rest),
+        },
+    };
+    let text = CUE_MARKUP.replace_all(rest, "");
+    (speaker, text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn parse_subtitles(text: &str) -> Vec<RawTurn> {
+    let names = speaker_names(text.lines().map(clean_line).filter_map(untagged_cue_text));
+    let mut turns: Vec<RawTurn> = Vec::new();
+    let mut speaker: Option<String> = None;
+    let mut cue_start: Option<i64> = None;
+    // Is the last turn still open for lines of the current cue?
+    let mut in_cue = false;
+    
+    for line in text.lines().map(clean_line) {
+        if line.is_empty() {
+            in_cue = false;
+            cue_start = None;
+            continue;
+        }
+        if line.contains("-->") {
+            cue_start = cue_ms(line.split("-->").next().unwrap_or(""));
+            in_cue = false;
+            continue;
+        }
+        // Cue ids, the WEBVTT header and NOTE / STYLE blocks sit outside a cue
+        let Some(at) = cue_start else {
+            continue;
+        };
+        
+        let (tag, body) = cue_line(line, &names);
+        if body.is_empty() {
+            continue;
+        }
+        match tag {
+            Some(tag) => {
+                let same = speaker.as_deref() == Some(tag);
+                if same && in_cue {
+                    turns.last_mut().unwrap().push_line(&body);
+                } else {
+                    speaker = Some(tag.to_string());
+                    turns.push(RawTurn::new(tag, &body, Some(at)));
+                }
+            }
+            None => match &speaker {
+                Some(_) if in_cue => turns.last_mut().unwrap().push_line(&body),
+                Some(current) => turns.push(RawTurn::new(current, &body, Some(at))),
+                None => continue,
+            },
+        }
+        in_cue = true;
+    }
+    turns
+}
+
+/// Milliseconds of a log clock time (epoch when dated, time of day otherwise)
+fn clock_ms(text: &str) -> Option<i64> {
+    let c = CLOCK.captures(text.trim())?;
+    let num = |i: usize| c.get(i).map_or(0, |m| m.as_str().parse::<u32>().unwrap_or(0));
+    let millis = c.get(7).map_or(0, |m| {
+        let frac = m.as_str();
+        frac.parse::<i64>().unwrap_or(0) * 10_i64.pow(3 - frac.len() as u32)
+    });
+    let base = if c.get(1).is_some() {
+        epoch_ms(num(1) as i32, num(2), num(3), num(4), num(5), num(6))?
+    } else {
+        ((num(4) as i64 * 60 + num(5) as i64) * 60 + num(6) as i64) * 1000
+    };
+    Some(base + millis)
+}
+
+/// Time stamp and the rest of a log line
+fn log_line(line: &str) -> (Option<i64>, &str) {
+    match LOG_STAMP.captures(line) {
+        Some(c) => {
+            let stamp = c.get(1).or_else(|| c.get(2)).map_or("", |m| m.as_str());
+            match clock_ms(stamp) {
+                Some(at) => (Some(at), c.get(3).map_or("", |m| m.as_str())),
+                None => (None, line),
+            }
+        }
+        None => (None, line),
+    }
+}
+
+fn parse_log(text: &str) -> Vec<RawTurn> {
+    let lines: Vec<(&str, Option<i64>, &str)> = text.lines()
+        .map(clean_line)
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let (at, rest) = log_line(line);
+            (line, at, rest)
+        })
+        .collect();
+    let names = speaker_names(lines.iter().map(|&(_, _, rest)| rest));
+    
+    let mut turns: Vec<RawTurn> = Vec::new();
+    for &(line, at, rest) in &lines {
+        match known_speaker(rest, &names) {
+            Some((speaker, body)) => turns.push(RawTurn::new(speaker, body, at)),
+            None if at.is_none() => {
+                if let Some(last) = turns.last_mut() {
+                    last.push_line(line);
+                }
+            }
+            None => {}
+        }
+    }
+    turns
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn offsets(transcript: &Transcript) -> Vec<Option<u64>> {
+        transcript.turns.iter().map(|t| t.offset_ms).collect()
+    }
+    
+    #[test]
+    fn test_whatsapp_android() {
+        let text = "12/03/2024, 14:05 - Messages and calls are end-to-end encrypted.\n\
+                    12/03/2024, 14:05 - Anna: Are you free tomorrow?\n\
+                    12/03/2024, 14:07 - Ben: Yes, after lunch.\n\
+                    It depends on the train though\n\
+                    12/03/2024, 14:08 - Ben: <Media omitted>\n\
+                    13/03/2024, 09:00 - Anna: Great, see you then";
+        assert_eq!(detect_format(text), TranscriptFormat::WhatsApp);
+        
+        let transcript = import_transcript(text).unwrap();
+        assert_eq!(transcript.turns.len(), 3);
+        assert_eq!(transcript.turns[1].speaker, "Ben");
+        assert_eq!(transcript.turns[1].text, "Yes, after lunch.\nIt depends on the train though");
+        assert_eq!(offsets(&transcript), vec![Some(0), Some(120_000), Some(68_100_000)]);
+        assert_eq!(transcript.speakers(), vec!["Anna", "Ben"]);
+    }
+    
+    #[test]
+    fn test_whatsapp_ios_month_first_12h() {
+        let text = "[3/14/24, 11:59:30 PM] Anna: Still up?\n\
+                    [3/15/24, 12:00:10 AM] Ben: \u{200e}image omitted\n\
+                    [3/15/24, 12:00:40 AM] Ben: Barely";
+        let transcript = parse_transcript(text, TranscriptFormat::WhatsApp).unwrap();
+        
+        // 3/14 only parses month first; 12 AM is midnight
+        assert_eq!(transcript.turns.len(), 2);
+        assert_eq!(offsets(&transcript), vec![Some(0), Some(70_000)]);
+    }
+    
+    #[test]
+    fn test_signal_export() {
+        let text = "[2024-03-12 14:05] Anna: Did you read it?\n\
+                    [2024-03-12 14:05:40] Ben: Not yet";
+        assert_eq!(detect_format(text), TranscriptFormat::Signal);
+        
+        let transcript = import_transcript(text).unwrap();
+        assert_eq!(transcript.turns[1].speaker, "Ben");
+        assert_eq!(offsets(&transcript), vec![Some(0), Some(40_000)]);
+    }
+    
+    #[test]
+    fn test_srt_with_speaker_prefixes() {
+        let text = "1\n00:00:01,000 --> 00:00:03,000\nANNA: Where were you?\n\n\
+                    2\n00:00:03,500 --> 00:00:05,000\n- BEN: Out.\n- ANNA: Out where?\n\n\
+                    3\n00:00:06,250 --> 00:00:08,000\n[sighs]\nJust out.\n";
+        assert_eq!(detect_format(text), TranscriptFormat::Srt);
+        
+        let transcript = import_transcript(text).unwrap();
+        let speakers: Vec<&str> = transcript.turns.iter().map(|t| t.speaker.as_str()).collect();
+        assert_eq!(speakers, vec!["ANNA", "BEN", "ANNA", "ANNA"]);
+        assert_eq!(transcript.turns[3].text, "Just out.");
+        assert_eq!(offsets(&transcript), vec![Some(0), Some(2500), Some(2500), Some(5250)]);
+    }
+    
+    #[test]
+    fn test_webvtt_voice_tags() {
+        let text = "WEBVTT\n\nNOTE recorded call\n\n\
+                    intro\n00:01.000 --> 00:04.000\n<v Anna>Shall we start?</v>\n\n\
+                    00:04.500 --> 00:07.000\n<v.loud Ben>Yes, <i>finally</i>.\nLet's go.\n";
+        assert_eq!(detect_format(text), TranscriptFormat::WebVtt);
+        
+        let transcript = import_transcript(text).unwrap();
+        assert_eq!(transcript.turns.len(), 2);
+        assert_eq!(transcript.turns[1].speaker, "Ben");
+        assert_eq!(transcript.turns[1].text, "Yes, finally.\nLet's go.");
+        assert_eq!(offsets(&transcript), vec![Some(0), Some(3500)]);
+    }
+    
+    #[test]
+    fn test_log_with_and_without_timestamps() {
+        let untimed = "Anna: Hi\nBen: Hello there\nand welcome\nAnna: Thanks";
+        assert_eq!(detect_format(untimed), TranscriptFormat::Log);
+        let transcript = import_transcript(untimed).unwrap();
+        assert_eq!(transcript.turns.len(), 3);
+        assert_eq!(transcript.turns[1].text, "Hello there\nand welcome");
+        assert!(!transcript.is_timed());
+        
+        let timed = "[23:59:50] Anna: Late call\n23:59:58 - Ben: Indeed\n[00:00:05] Anna: Past midnight";
+        let transcript = parse_transcript(timed, TranscriptFormat::Log).unwrap();
+        assert_eq!(offsets(&transcript), vec![Some(0), Some(8000), Some(15_000)]);
+    }
+    
+    #[test]
+    fn test_one_off_labels_are_not_speakers() {
+        let text = "Anna: Shall we shop tomorrow?\n\
+                    Ben: Yes, after lunch\n\
+                    Note: buy milk\n\
+                    Anna: Fine, milk and bread\n\
+                    Ben: See you then\n\
+                    x: y";
+        let transcript = import_transcript(text).unwrap();
+        assert_eq!(transcript.speakers(), vec!["Anna", "Ben"]);
+        assert_eq!(transcript.turns[1].text, "Yes, after lunch\nNote: buy milk");
+        assert_eq!(transcript.turns[3].text, "See you then\nx: y");
+        
+        // Single capitals count once reused; lower case or one-off ones do not
+        let text = "A: one\nB: two\nA: three\nB: four\nb: five\nQ: six";
+        let transcript = import_transcript(text).unwrap();
+        assert_eq!(transcript.speakers(), vec!["A", "B"]);
+        assert_eq!(transcript.turns[3].text, "four\nb: five\nQ: six");
+        
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\nANNA: Ready?\n\n\
+                   2\n00:00:02,500 --> 00:00:04,000\nBEN: Yes. Warning: loud\n\n\
+                   3\n00:00:04,500 --> 00:00:05,000\nANNA: Go\n\n\
+                   4\n00:00:05,500 --> 00:00:06,000\nBEN: Now\nWarning: very loud\n";
+        let transcript = import_transcript(srt).unwrap();
+        assert_eq!(transcript.speakers(), vec!["ANNA", "BEN"]);
+        assert_eq!(transcript.turns[3].text, "Now\nWarning: very loud");
+    }
+    
+    #[test]
+    fn test_urls_are_not_speakers() {
+        let transcript = import_transcript("Anna: look\nhttps://example.com/page").unwrap();
+        assert_eq!(transcript.turns.len(), 1);
+        assert_eq!(transcript.turns[0].text, "look\nhttps://example.com/page");
+    }
+    
+    #[test]
+    fn test_segment_json_detected() {
+        let json = r#"[{"start": 0.0, "end": 1.0, "text": "Hi", "speaker": "A"}, {"start": 1.2, "end": 2.0, "text": "Hey", "speaker": "B"}]"#;
+        assert_eq!(detect_format(json), TranscriptFormat::Diarized);
+        assert_eq!(detect_format("[2024-03-12 14:05] Anna: Hi"), TranscriptFormat::Signal);
+        
+        let transcript = import_transcript(json).unwrap();
+        assert_eq!(transcript.speakers(), vec!["A", "B"]);
+        assert_eq!(transcript.turns[1].offset_ms, Some(1200));
+    }
+    
+    #[test]
+    fn test_no_turns() {
+        assert_eq!(
+            import_transcript("just some prose without speakers").unwrap_err(),
+            ImportReason::R402_IMPORT_NO_TURNS
+        );
+        assert_eq!(
+            load_transcript("/nonexistent/chat.txt", None).unwrap_err(),
+            ImportReason::R401_IMPORT_READ_ERROR
+        );
+    }
+}
@@ -1,15 +1,47 @@
 //! Core modules for Soul-0
 
 pub mod r_parser;
+pub mod scorer;
 pub mod facelock;
 pub mod dc_parser;
+pub mod thematic;
+pub mod sentiment;
+pub mod question;
+pub mod referents;
+pub mod balance;
+pub mod conflict;
+pub mod style;
+pub mod timing;
 pub mod proof;
 pub mod snapshot;
+pub mod compaction;
+pub mod store;
+pub mod importer;
+pub mod diarized;
+pub mod analyze;
+pub mod audit;
+pub mod replay;
 pub mod api;
 
-pub use r_parser::RParser;
-pub use facelock::FacelockEngine;
-pub use dc_parser::DcParser;
+pub use r_parser::{RParser, split_sentences};
+pub use facelock::{FacelockEngine, min_confidence_from};
+pub use dc_parser::{DcParser, DcState};
+pub use thematic::{ThematicModel, IdfCorpus, Language};
+pub use sentiment::{Sentiment, score_sentiment};
+pub use question::{QuestionKind, question_kind, is_question};
+pub use referents::track_referents;
+pub use balance::turn_balance;
+pub use conflict::conflict_avoidance;
+pub use style::{StyleProfile, style_match};
+pub use timing::{timing_stats, turn_gaps};
+pub use scorer::{RScorer, DcScorer, DcScorerState, Scorers};
 pub use proof::{ProofGenerator, verify_proof, hash_paired_turns};
-pub use snapshot::{SnapshotGenerator, save_snapshot, load_snapshot, load_and_validate_snapshot, validate_snapshot_proof};
-pub use api::{create_router, run_server};
+pub use snapshot::{SnapshotGenerator, save_snapshot, load_snapshot, load_and_validate_snapshot, validate_snapshot_proof, validate_dir, plural};
+pub use store::{SnapshotStore, DirStore, SqliteStore, migrate_dir};
+pub use compaction::{compact_snapshots, compact_store, spawn_compaction_job, COMPACTED_DIR};
+pub use importer::{load_transcript, parse_transcript, import_transcript, detect_format};
+pub use diarized::{load_diarized, parse_rttm, parse_segments, combine_diarized};
+pub use analyze::{analyze_transcript, AnalyzeOptions, TranscriptAnalysis, TranscriptRunner, TurnStep};
+pub use audit::{AuditLog, read_audit_log, parse_audit_log};
+pub use replay::{replay, recorded_scorers};
+pub use api::{create_router, create_router_with_scorers, create_router_with_audit, create_router_with_store, run_server, run_server_with_scorers, run_server_with_audit, run_server_with_store, DEFAULT_SNAPSHOT_LIMIT, MAX_SNAPSHOT_LIMIT};
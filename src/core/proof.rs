@@ -71,6 +71,7 @@ impl ProofGenerator {
     }
     
     /// Generate a proof
+    #[allow(clippy::too_many_arguments)]
     pub fn generate(
         &self,
         session_id: [u8; 16],
@@ -89,7 +90,7 @@ impl ProofGenerator {
         let pairs = window.paired_turns();
         let dc_final = dc_result.value.unwrap_or(0.0);
         
-        // Calculate conversation hash (only paired turns in window)
+        // Calculate conversation hash (only paired logical turns in window)
         let conversation_hash = hash_paired_turns(&pairs);
         
         // Get window start time
@@ -157,6 +158,22 @@ pub fn verify_proof(
 }
 
 /// Hash paired turns (deterministic)
+///
+/// Commits to, for each pair in order: first speaker, 0x00, first text, 0x00,
+/// second speaker, 0x00, second text, 0x00 (UTF-8 bytes, SHA-256). A turn
+/// that sits in two pairs is hashed twice. Timestamps and r are not committed.
+///
+/// What the texts are depends on the window's pairing mode
+/// (`ConversationWindow::paired_turns`):
+/// - raw turns (no merge gap): a turn followed by another turn from the same
+///   speaker is in no pair, so it is not committed ("A1, A2, B" commits A2, B)
+/// - merged (merge gap set): each burst is one text, its messages joined by
+///   `MERGED_TURN_SEPARATOR` ("A1\nA2", B), so every message in the burst
+///   is committed; bursts split by a pause longer than the gap are separate
+///   logical turns and only the last one pairs
+///
+/// The same conversation therefore hashes differently in the two modes;
+/// a verifier must pair turns with the same merge gap.
 pub fn hash_paired_turns(pairs: &[TurnPair]) -> [u8; 32] {
     let mut hasher = Sha256::new();
     
@@ -467,4 +484,29 @@ mod tests {
         
         assert_ne!(hash1, hash2, "Different content should have different hash");
     }
+    
+    #[test]
+    fn test_hash_commits_to_merged_bursts() {
+        let mut window = ConversationWindow::new();
+        window.add_turn(Turn::new("A", "Hello", 0.1));
+        window.add_turn(Turn::new("A", "Are you there?", 0.1));
+        window.add_turn(Turn::new("B", "Yes", 0.1));
+        let raw = hash_paired_turns(&window.paired_turns());
+        
+        // Raw mode commits only the last message of the burst
+        let mut last_only = ConversationWindow::new();
+        last_only.add_turn(Turn::new("A", "Are you there?", 0.1));
+        last_only.add_turn(Turn::new("B", "Yes", 0.1));
+        assert_eq!(raw, hash_paired_turns(&last_only.paired_turns()));
+        
+        // Merged mode commits the whole burst
+        window.set_merge_gap(Some(std::time::Duration::from_secs(5)));
+        let merged = hash_paired_turns(&window.paired_turns());
+        assert_ne!(merged, raw);
+        
+        let mut joined = ConversationWindow::new();
+        joined.add_turn(Turn::new("A", "Hello\nAre you there?", 0.1));
+        joined.add_turn(Turn::new("B", "Yes", 0.1));
+        assert_eq!(merged, hash_paired_turns(&joined.paired_turns()));
+    }
 }
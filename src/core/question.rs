@@ -0,0 +1,187 @@
+//! Question detection for EN and NL
+//!
+//! A sentence is a question when it ends in '?' or opens with an inverted
+//! auxiliary + subject ("do you", "is it", "heb je", "kun jij"). A question
+//! word (what/why/how…, wie/wat/waar/waarom/hoe…) makes it an open question
+//! when a '?' or an auxiliary follows it ("why did you", "hoe gaat het"), so
+//! questions typed without a question mark are still recognised while "When
+//! I got home, I slept", "What a day" and "Hoe dan ook" are not.
+
+/// What kind of answer a question asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionKind {
+    /// Open question (what/why/how, wie/wat/hoe)
+    Wh,
+    /// Closed question (inversion, or a bare '?')
+    YesNo,
+}
+
+const WH_WORDS: &[&str] = &[
+    "what", "why", "how", "when", "where", "who", "whom", "whose", "which",
+    "wie", "wat", "waar", "waarom", "hoe", "wanneer", "welke", "welk", "hoeveel", "waarheen",
+];
+
+/// Question words that take a noun before the verb ("which book do you", "hoeveel tijd heb je")
+const WH_DETERMINERS: &[&str] = &["which", "whose", "what", "welke", "welk", "hoeveel", "wat"];
+
+/// Exclamative and concessive openers that look like questions ("what a day", "hoe dan ook")
+const NOT_QUESTIONS: &[&[&str]] = &[
+    &["what", "a"], &["what", "an"], &["wat", "een"], &["hoe", "dan", "ook"], &["wat", "dan", "ook"], &["wie", "dan", "ook"],
+    &["waar", "dan", "ook"], &["whatever"], &["however"], &["whoever"], &["wherever"],
+];
+
+/// Auxiliaries that start an English yes/no question when followed by a subject
+const EN_AUXILIARIES: &[&str] = &[
+    "do", "does", "did", "is", "are", "was", "were", "am", "can", "could", "will", "would",
+    "should", "shall", "have", "has", "had", "may", "might", "must",
+    "don't", "doesn't", "didn't", "isn't", "aren't", "wasn't", "weren't", "can't",
+    "couldn't", "won't", "wouldn't", "shouldn't", "haven't", "hasn't",
+];
+
+const EN_SUBJECTS: &[&str] = &[
+    "i", "you", "he", "she", "it", "we", "they", "this", "that", "there", "these", "those",
+    "the", "a", "an", "your", "my", "his", "her", "our", "their", "anyone", "anybody",
+    "someone", "somebody", "everyone", "everybody",
+];
+
+/// Dutch finite verbs that start a question when followed by a subject pronoun
+const NL_VERBS: &[&str] = &[
+    "heb", "hebt", "heeft", "hebben", "ben", "bent", "is", "zijn", "was", "waren", "kan",
+    "kun", "kunt", "kon", "konden", "wil", "wilt", "wilde", "zal", "zult", "zou", "zouden",
+    "moet", "moeten", "mag", "mogen", "ga", "gaat", "gaan", "weet", "weten", "vind", "vindt",
+    "zie", "ziet", "doe", "doet", "denk", "denkt", "snap", "begrijp", "ken", "kent", "hoef",
+    "hoor", "kom", "komt", "had", "hadden",
+];
+
+const NL_SUBJECTS: &[&str] = &[
+    "je", "jij", "u", "jullie", "hij", "zij", "ze", "het", "we", "wij", "er", "ik", "dat",
+];
+
+/// Classify text as a question, if any sentence in it is one
+pub fn question_kind(text: &str) -> Option<QuestionKind> {
+    let mut kind = None;
+    
+    for sentence in text.split_inclusive(['.', '!', '?', '\n']) {
+        let trimmed = sentence.trim();
+        if trimmed.is_empty() || trimmed.ends_with('!') {
+            continue;
+        }
+        
+        let words: Vec<String> = trimmed
+            .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+            .filter(|w| !w.is_empty())
+            .take(4)
+            .map(|w| w.replace('’', "'").to_lowercase())
+            .collect();
+        let word = |i: usize| words.get(i).map(String::as_str).unwrap_or("");
+        let (first, second) = (word(0), word(1));
+        
+        let sentence_kind = if NOT_QUESTIONS.iter().any(|opener| starts_with_words(&words, opener)) {
+            None
+        } else if WH_WORDS.contains(&first)
+            && (trimmed.ends_with('?')
+                || is_verb(second)
+                || (WH_DETERMINERS.contains(&first) && is_verb(word(2))))
+        {
+            Some(QuestionKind::Wh)
+        } else if is_inversion(first, second) || trimmed.ends_with('?') {
+            Some(QuestionKind::YesNo)
+        } else {
+            None
+        };
+        
+        // An open question anywhere makes the turn an open question
+        match (kind, sentence_kind) {
+            (_, Some(QuestionKind::Wh)) => return Some(QuestionKind::Wh),
+            (None, Some(k)) => kind = Some(k),
+            _ => {}
+        }
+    }
+    
+    kind
+}
+
+/// Is this text a question?
+pub fn is_question(text: &str) -> bool {
+    question_kind(text).is_some()
+}
+
+/// Auxiliary or finite verb that follows a question word ("why did", "hoe gaat")
+fn is_verb(word: &str) -> bool {
+    EN_AUXILIARIES.contains(&word) || NL_VERBS.contains(&word)
+        || matches!(word, "staat" | "ligt" | "zit" | "heet" | "kost" | "duurt")
+}
+
+/// Do the lowercased leading words start with `opener`?
+fn starts_with_words(words: &[String], opener: &[&str]) -> bool {
+    words.len() >= opener.len() && words.iter().zip(opener).all(|(w, o)| w == o)
+}
+
+/// Auxiliary/verb followed by a subject ("do you", "heb je")
+fn is_inversion(first: &str, second: &str) -> bool {
+    // "Do it", "Do that": imperative, not a question
+    if first == "do" && !matches!(second, "i" | "you" | "we" | "they") {
+        return false;
+    }
+    (EN_AUXILIARIES.contains(&first) && EN_SUBJECTS.contains(&second))
+        || (NL_VERBS.contains(&first) && NL_SUBJECTS.contains(&second))
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_wh_questions_without_question_mark() {
+        assert_eq!(question_kind("what do you think"), Some(QuestionKind::Wh));
+        assert_eq!(question_kind("Why did you leave."), Some(QuestionKind::Wh));
+        assert_eq!(question_kind("Waarom ben je weggegaan"), Some(QuestionKind::Wh));
+        assert_eq!(question_kind("wie komt er morgen"), Some(QuestionKind::Wh));
+        assert_eq!(question_kind("Hoe gaat het"), Some(QuestionKind::Wh));
+    }
+    
+    #[test]
+    fn test_inversions() {
+        assert_eq!(question_kind("Do you like it"), Some(QuestionKind::YesNo));
+        assert_eq!(question_kind("Is the shop open"), Some(QuestionKind::YesNo));
+        assert_eq!(question_kind("Heb je dat gezien"), Some(QuestionKind::YesNo));
+        assert_eq!(question_kind("Kun jij morgen komen"), Some(QuestionKind::YesNo));
+        assert_eq!(question_kind("Don't you agree"), Some(QuestionKind::YesNo));
+    }
+    
+    #[test]
+    fn test_statements_are_not_questions() {
+        assert!(!is_question("I think it is fine"));
+        assert!(!is_question("Do it now"));
+        assert!(!is_question("What a day!"));
+        assert!(!is_question("Ik heb het gezien"));
+        assert!(!is_question("Als je wilt, kom dan langs"));
+        assert!(!is_question(""));
+    }
+    
+    #[test]
+    fn test_leading_question_word_alone_is_not_a_question() {
+        assert!(!is_question("When I got home, I slept."));
+        assert!(!is_question("How I wish it were summer."));
+        assert!(!is_question("What a day."));
+        assert!(!is_question("Wat een mooie dag."));
+        assert!(!is_question("Hoe dan ook, we gaan."));
+        assert!(!is_question("Where we live it rains a lot"));
+        
+        assert_eq!(question_kind("Which book do you mean"), Some(QuestionKind::Wh));
+        assert_eq!(question_kind("Hoeveel tijd heb je"), Some(QuestionKind::Wh));
+        assert_eq!(question_kind("When did you get home?"), Some(QuestionKind::Wh));
+        assert_eq!(question_kind("Why?"), Some(QuestionKind::Wh));
+    }
+    
+    #[test]
+    fn test_question_mark_and_mixed_turns() {
+        assert_eq!(question_kind("You're coming?"), Some(QuestionKind::YesNo));
+        assert_eq!(question_kind("Nice garden. Where did you buy the roses"), Some(QuestionKind::Wh));
+        assert_eq!(question_kind("Is it new? How much was it?"), Some(QuestionKind::Wh));
+    }
+}
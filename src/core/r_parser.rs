@@ -1,105 +1,280 @@
-//! r-Parser: Measures ego noise via 7 linguistic signals
+//! r-Parser: Measures ego noise via 7 linguistic signals + intensity
 //! 
-//! Based on LLD v1.0 with Grok's empirically tuned weights (sum = 18.5)
+//! Based on LLD v1.0 with Grok's empirically tuned weights (sum = 18.5).
+//! The 8th signal, intensity, reads the surface of the text (CAPS, "!!!",
+//! "sooo", 😡) that lowercased word matching cannot see. It is always
+//! reported in the signals but only scored when opted in: its weight is added
+//! to the normalization sum, and the default weight 0.0 gives the original
+//! seven-signal r.
+//!
+//! Scoring is single-pass: one Aho-Corasick automaton holds the terms of all
+//! signals, tagged by signal. The per-signal regexes are kept as `parse_regex`
+//! for verification and benchmarks.
 
+use std::borrow::Cow;
+use aho_corasick::AhoCorasick;
 use lazy_static::lazy_static;
+use rayon::prelude::*;
 use regex::Regex;
 use crate::{
     R_WEIGHT_FIRST_PERSON, R_WEIGHT_ABSOLUTES, R_WEIGHT_FUTURE,
     R_WEIGHT_PAST, R_WEIGHT_COMPARISON, R_WEIGHT_JUDGMENT, 
-    R_WEIGHT_URGENCY, R_WEIGHT_SUM,
+    R_WEIGHT_URGENCY, R_WEIGHT_SUM, R_WEIGHT_INTENSITY,
 };
-use crate::types::{RSignals, RValue};
+use crate::types::{RSignals, RValue, SentenceR, RAggregation};
+
+// =============================================================================
+// Signal 1: First person (weight: 2.8)
+// Dutch: ik, mij, mijn, me, m'n, mezelf, mijzelf, zelf, wij, ons, onze
+// =============================================================================
+const FIRST_PERSON_TERMS: &[&str] = &[
+    "i", "me", "my", "mine", "myself", "i'm", "i've", "i'll", "i'd", "i am",
+    "ik", "mij", "mijn", "me", "m'n", "mezelf", "mijzelf", "zelf", "wij", "ons", "onze",
+];
+
+// =============================================================================
+// Signal 2: Absolutes (weight: 3.1)
+// Dutch: altijd, nooit, iedereen, niemand, alles, niets, helemaal, volledig, totaal
+// =============================================================================
+const ABSOLUTES_TERMS: &[&str] = &[
+    "always", "never", "everything", "nothing", "everyone", "no one", "nobody", "everybody",
+    "all", "none", "every", "any", "completely", "totally", "absolutely",
+    "altijd", "nooit", "iedereen", "niemand", "alles", "niets", "helemaal", "volledig",
+    "totaal", "elk", "elke", "geen enkele", "overal", "nergens",
+];
+
+// =============================================================================
+// Signal 3: Future projection (weight: 2.4)
+// Dutch: zal, ga, gaat, moet, moeten, morgen, straks, binnenkort, later
+// =============================================================================
+const FUTURE_TERMS: &[&str] = &[
+    "will", "going to", "gonna", "must", "have to", "need to", "should", "ought to", "shall",
+    "tomorrow", "next", "soon", "later", "eventually",
+    "zal", "zullen", "ga", "gaat", "gaan", "moet", "moeten", "morgen", "straks", "binnenkort",
+    "later", "ooit", "dadelijk", "zo meteen",
+];
+
+// =============================================================================
+// Signal 4: Past attachment (weight: 1.9)
+// Dutch: vroeger, toen, gisteren, voorheen, ooit, was, waren, had, hadden
+// =============================================================================
+const PAST_TERMS: &[&str] = &[
+    "was", "were", "had", "used to", "before", "previously", "back then", "in the past",
+    "yesterday", "last", "earlier", "once",
+    "vroeger", "toen", "gisteren", "voorheen", "ooit", "destijds", "in het verleden",
+    "was", "waren", "had", "hadden", "geweest",
+];
+
+// =============================================================================
+// Signal 5: Comparison (weight: 2.2)
+// Dutch: beter, slechter, meer, minder, dan, liever, eerder, vergelijken
+// =============================================================================
+const COMPARISON_TERMS: &[&str] = &[
+    "better", "worse", "more", "less", "than", "compared to", "versus", "vs", "superior",
+    "inferior", "ahead", "behind", "rather", "instead",
+    "beter", "slechter", "meer", "minder", "dan", "liever", "eerder", "vergeleken met",
+    "in vergelijking", "anders dan",
+];
+
+// =============================================================================
+// Signal 6: Judgment (weight: 3.5 - HIGHEST)
+// Dutch: zou moeten, fout, schuld, slecht, goed, dom, stom, verkeerd, terecht
+// =============================================================================
+const JUDGMENT_TERMS: &[&str] = &[
+    "should", "shouldn't", "wrong", "right", "fault", "blame", "guilty", "mistake", "bad",
+    "good", "terrible", "awful", "stupid", "idiot", "horrible", "perfect", "correct",
+    "incorrect",
+    "zou moeten", "had moeten", "fout", "schuld", "slecht", "goed", "dom", "stom", "verkeerd",
+    "terecht", "onterecht", "verschrikkelijk", "vreselijk", "idioot", "perfect", "juist",
+    "onjuist",
+];
+
+// =============================================================================
+// Signal 7: Urgency (weight: 2.6)
+// Dutch: nu, meteen, snel, dringend, belangrijk, direct, onmiddellijk
+// =============================================================================
+const URGENCY_TERMS: &[&str] = &[
+    "now", "immediately", "right now", "quickly", "hurry", "urgent", "asap", "fast", "rush",
+    "important", "critical", "essential",
+    "nu", "meteen", "snel", "dringend", "belangrijk", "direct", "onmiddellijk", "gauw",
+    "haast", "spoed", "acuut", "cruciaal",
+];
+
+/// Term lists in signal order (index = signal tag)
+const SIGNAL_TERMS: [&[&str]; SIGNAL_COUNT] = [
+    FIRST_PERSON_TERMS,
+    ABSOLUTES_TERMS,
+    FUTURE_TERMS,
+    PAST_TERMS,
+    COMPARISON_TERMS,
+    JUDGMENT_TERMS,
+    URGENCY_TERMS,
+];
+
+/// Number of r signals
+const SIGNAL_COUNT: usize = 7;
 
 lazy_static! {
-    // =========================================================================
-    // Signal 1: First person (weight: 2.8)
-    // Dutch: ik, mij, mijn, me, m'n, mezelf, mijzelf, zelf, wij, ons, onze
-    // =========================================================================
-    static ref RE_FIRST_PERSON: Regex = Regex::new(
-        r"(?i)\b(i|me|my|mine|myself|i'm|i've|i'll|i'd|i am|ik|mij|mijn|me|m'n|mezelf|mijzelf|zelf|wij|ons|onze)\b"
-    ).unwrap();
-    
-    // =========================================================================
-    // Signal 2: Absolutes (weight: 3.1)
-    // Dutch: altijd, nooit, iedereen, niemand, alles, niets, helemaal, volledig, totaal
-    // =========================================================================
-    static ref RE_ABSOLUTES: Regex = Regex::new(
-        r"(?i)\b(always|never|everything|nothing|everyone|no one|nobody|everybody|all|none|every|any|completely|totally|absolutely|altijd|nooit|iedereen|niemand|alles|niets|helemaal|volledig|totaal|elk|elke|geen enkele|overal|nergens)\b"
-    ).unwrap();
-    
-    // =========================================================================
-    // Signal 3: Future projection (weight: 2.4)
-    // Dutch: zal, ga, gaat, moet, moeten, morgen, straks, binnenkort, later
-    // =========================================================================
-    static ref RE_FUTURE: Regex = Regex::new(
-        r"(?i)\b(will|going to|gonna|must|have to|need to|should|ought to|shall|tomorrow|next|soon|later|eventually|zal|zullen|ga|gaat|gaan|moet|moeten|morgen|straks|binnenkort|later|ooit|dadelijk|zo meteen)\b"
-    ).unwrap();
-    
-    // =========================================================================
-    // Signal 4: Past attachment (weight: 1.9)
-    // Dutch: vroeger, toen, gisteren, voorheen, ooit, was, waren, had, hadden
-    // =========================================================================
-    static ref RE_PAST: Regex = Regex::new(
-        r"(?i)\b(was|were|had|used to|before|previously|back then|in the past|yesterday|last|earlier|once|vroeger|toen|gisteren|voorheen|ooit|destijds|in het verleden|was|waren|had|hadden|geweest)\b"
-    ).unwrap();
-    
-    // =========================================================================
-    // Signal 5: Comparison (weight: 2.2)
-    // Dutch: beter, slechter, meer, minder, dan, liever, eerder, vergelijken
-    // =========================================================================
-    static ref RE_COMPARISON: Regex = Regex::new(
-        r"(?i)\b(better|worse|more|less|than|compared to|versus|vs|superior|inferior|ahead|behind|rather|instead|beter|slechter|meer|minder|dan|liever|eerder|vergeleken met|in vergelijking|anders dan)\b"
-    ).unwrap();
-    
-    // =========================================================================
-    // Signal 6: Judgment (weight: 3.5 - HIGHEST)
-    // Dutch: zou moeten, fout, schuld, slecht, goed, dom, stom, verkeerd, terecht
-    // =========================================================================
-    static ref RE_JUDGMENT: Regex = Regex::new(
-        r"(?i)\b(should|shouldn't|wrong|right|fault|blame|guilty|mistake|bad|good|terrible|awful|stupid|idiot|horrible|perfect|correct|incorrect|zou moeten|had moeten|fout|schuld|slecht|goed|dom|stom|verkeerd|terecht|onterecht|verschrikkelijk|vreselijk|idioot|perfect|juist|onjuist)\b"
-    ).unwrap();
-    
-    // =========================================================================
-    // Signal 7: Urgency (weight: 2.6)
-    // Dutch: nu, meteen, snel, dringend, belangrijk, direct, onmiddellijk
-    // =========================================================================
-    static ref RE_URGENCY: Regex = Regex::new(
-        r"(?i)\b(now|immediately|right now|quickly|hurry|urgent|asap|fast|rush|important|critical|essential|nu|meteen|snel|dringend|belangrijk|direct|onmiddellijk|gauw|haast|spoed|acuut|cruciaal)\b"
-    ).unwrap();
+    /// All term lists in one automaton; pattern ids follow `TERM_SIGNALS`
+    static ref TERMS: AhoCorasick = AhoCorasick::builder()
+        .ascii_case_insensitive(true)
+        .build(SIGNAL_TERMS.iter().flat_map(|terms| terms.iter()))
+        .unwrap();
+    
+    /// Signal tag per pattern id (index into `SIGNAL_TERMS`)
+    static ref TERM_SIGNALS: Vec<usize> = SIGNAL_TERMS.iter()
+        .enumerate()
+        .flat_map(|(signal, terms)| terms.iter().map(move |_| signal))
+        .collect();
+    
+    /// Per-signal regexes (reference implementation)
+    static ref SIGNAL_REGEXES: Vec<Regex> = SIGNAL_TERMS.iter()
+        .map(|terms| Regex::new(&format!(r"(?i)\b({})\b", terms.join("|"))).unwrap())
+        .collect();
 }
 
+/// Emoji that mark shouting or strong emphasis
+const EMPHATIC_EMOJI: &[char] = &[
+    '😡', '🤬', '😠', '😤', '💢', '🔥', '💥', '😱', '🙄', '‼', '⁉', '❗', '❕', '❓',
+];
+
 /// r-Parser for measuring ego noise
-#[derive(Debug, Default)]
-pub struct RParser;
+#[derive(Debug)]
+pub struct RParser {
+    /// Weight of the intensity signal (0.0 = seven-signal r)
+    intensity_weight: f64,
+    /// How sentence r values combine (Whole = no sentence split)
+    aggregation: RAggregation,
+}
+
+impl Default for RParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl RParser {
     /// Create new parser
     pub fn new() -> Self {
-        Self
+        Self::with_intensity_weight(R_WEIGHT_INTENSITY)
+    }
+    
+    /// Create parser with a custom intensity weight
+    pub fn with_intensity_weight(weight: f64) -> Self {
+        Self {
+            intensity_weight: weight.max(0.0),
+            aggregation: RAggregation::Whole,
+        }
+    }
+    
+    /// Create parser that scores sentences and combines them
+    pub fn with_aggregation(aggregation: RAggregation) -> Self {
+        Self {
+            aggregation,
+            ..Self::new()
+        }
+    }
+    
+    /// Get intensity weight
+    pub fn intensity_weight(&self) -> f64 {
+        self.intensity_weight
+    }
+    
+    /// Get sentence aggregation
+    pub fn aggregation(&self) -> RAggregation {
+        self.aggregation
+    }
+    
+    /// Set sentence aggregation
+    pub fn set_aggregation(&mut self, aggregation: RAggregation) {
+        self.aggregation = aggregation;
     }
     
     /// Parse text and return r value with full signal breakdown
+    ///
+    /// Single pass: the combined term automaton runs over the text once.
+    /// With a sentence-level aggregation, a text of two or more sentences gets r from
+    /// its sentences; signals, confidence and word count still describe the whole text.
     pub fn parse(&self, text: &str) -> RValue {
         let text = text.trim();
+        if text.is_empty() {
+            return RValue::new(0.0, RSignals::zero(), 0.0, 0);
+        }
+        let whole = self.score(text, count_signals(text));
+        
+        if !self.aggregation.is_sentence_level() {
+            return whole;
+        }
+        self.aggregate_sentences(text, whole)
+    }
+    
+    /// Replace r of a whole-text score with the aggregate of its sentences
+    fn aggregate_sentences(&self, text: &str, mut whole: RValue) -> RValue {
+        let sentences = split_sentences(text);
+        if sentences.len() < 2 {
+            return whole;
+        }
+        
+        let scored: Vec<SentenceR> = sentences
+            .into_iter()
+            .map(|sentence| SentenceR {
+                text: sentence.to_string(),
+                r: self.score(sentence, count_signals(sentence)),
+            })
+            .collect();
         
-        // Handle empty input
+        let values: Vec<(f64, usize)> = scored
+            .iter()
+            .map(|s| (s.r.value, s.r.word_count))
+            .collect();
+        
+        whole.value = self.aggregation.combine(&values).clamp(0.0, 1.0);
+        whole.sentences = scored;
+        whole
+    }
+    
+    /// Parse text with one regex per signal
+    ///
+    /// Reference implementation, kept for verification and benchmarks. Same result as `parse`
+    /// with `RAggregation::Whole`.
+    pub fn parse_regex(&self, text: &str) -> RValue {
+        let text = text.trim();
         if text.is_empty() {
             return RValue::new(0.0, RSignals::zero(), 0.0, 0);
         }
         
+        let mut counts = [0usize; SIGNAL_COUNT];
+        for (count, regex) in counts.iter_mut().zip(SIGNAL_REGEXES.iter()) {
+            *count = regex.find_iter(text).count();
+        }
+        self.score(text, counts)
+    }
+    
+    /// Parse many texts in parallel (uses all cores)
+    pub fn parse_many(&self, texts: &[&str]) -> Vec<RValue> {
+        texts.par_iter().map(|text| self.parse(text)).collect()
+    }
+    
+    /// Quick parse - just return the r value
+    pub fn quick_parse(&self, text: &str) -> f64 {
+        self.parse(text).value
+    }
+    
+    /// Build the r value from per-signal match counts
+    fn score(&self, text: &str, counts: [usize; SIGNAL_COUNT]) -> RValue {
         let word_count = text.split_whitespace().count().max(1);
         let wc = word_count as f64;
         
         // Count matches for each signal (normalized by word count)
         let signals = RSignals {
-            first_person: count_matches(&RE_FIRST_PERSON, text) / wc,
-            absolutes: count_matches(&RE_ABSOLUTES, text) / wc,
-            future_projection: count_matches(&RE_FUTURE, text) / wc,
-            past_attachment: count_matches(&RE_PAST, text) / wc,
-            comparison: count_matches(&RE_COMPARISON, text) / wc,
-            judgment: count_matches(&RE_JUDGMENT, text) / wc,
-            urgency: count_matches(&RE_URGENCY, text) / wc,
+            first_person: counts[0] as f64 / wc,
+            absolutes: counts[1] as f64 / wc,
+            future_projection: counts[2] as f64 / wc,
+            past_attachment: counts[3] as f64 / wc,
+            comparison: counts[4] as f64 / wc,
+            judgment: counts[5] as f64 / wc,
+            urgency: counts[6] as f64 / wc,
+            intensity: intensity(text, wc),
             language_hits: None, // Debug field, not used in normal parsing
         };
         
@@ -113,24 +288,178 @@ impl RParser {
             signals.judgment * R_WEIGHT_JUDGMENT +
             signals.urgency * R_WEIGHT_URGENCY;
         
-        // Normalize: r = (sum / 18.5).clamp(0.0, 1.0)
-        let value = (raw_score / R_WEIGHT_SUM).clamp(0.0, 1.0);
+        // Normalize: r = (sum / 18.5).clamp(0.0, 1.0), intensity only when weighted
+        let value = if self.intensity_weight > 0.0 {
+            (raw_score + signals.intensity * self.intensity_weight)
+                / (R_WEIGHT_SUM + self.intensity_weight)
+        } else {
+            raw_score / R_WEIGHT_SUM
+        };
+        let value = value.clamp(0.0, 1.0);
         
         // Confidence based on text length (more words = more reliable)
         let confidence = (word_count as f64 / 50.0).min(1.0);
         
         RValue::new(value, signals, confidence, word_count)
     }
+}
+
+/// Split text into sentences
+///
+/// A sentence ends at a line break, or at a run of `.`, `!`, `?` or `…` (plus closing
+/// quotes/brackets) followed by whitespace or the end of the text, so "3.5" and
+/// "e.g." mid-word stay intact. Pieces without letters or digits are dropped.
+pub fn split_sentences(text: &str) -> Vec<&str> {
+    let is_terminator = |c: char| matches!(c, '.' | '!' | '?' | '…' | '‼' | '⁉');
+    let is_closer = |c: char| matches!(c, '"' | '\'' | ')' | ']' | '”' | '’' | '»');
     
-    /// Quick parse - just return the r value
-    pub fn quick_parse(&self, text: &str) -> f64 {
-        self.parse(text).value
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    
+    while let Some((i, c)) = chars.next() {
+        let end = if c == '\n' {
+            Some(i)
+        } else if is_terminator(c) {
+            let mut end = i + c.len_utf8();
+            while let Some(&(j, next)) = chars.peek() {
+                if !is_terminator(next) && !is_closer(next) {
+                    break;
+                }
+                end = j + next.len_utf8();
+                chars.next();
+            }
+            match chars.peek() {
+                None => Some(end),
+                Some(&(_, next)) if next.is_whitespace() => Some(end),
+                _ => None,
+            }
+        } else {
+            None
+        };
+        
+        if let Some(end) = end {
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    sentences.push(&text[start..]);
+    
+    sentences
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| s.chars().any(char::is_alphanumeric))
+        .collect()
+}
+
+/// Count term matches per signal in one pass of the term automaton
+///
+/// Mirrors `(?i)\b(term|...)\b` with `find_iter`: terms start and end at word
+/// boundaries, the first listed term wins at a position, and matches of the
+/// same signal never overlap.
+fn count_signals(text: &str) -> [usize; SIGNAL_COUNT] {
+    // The automaton folds ASCII case only; fold the rest the way `(?i)` does
+    let haystack = if text.is_ascii() { Cow::Borrowed(text) } else { Cow::Owned(fold(text)) };
+    let haystack = haystack.as_ref();
+    
+    // Whole-word matches as (start, pattern id, end); pattern ids follow list order
+    let mut found: Vec<(usize, usize, usize)> = TERMS.find_overlapping_iter(haystack)
+        .filter(|m| {
+            let before = haystack[..m.start()].chars().next_back();
+            let after = haystack[m.end()..].chars().next();
+            !before.is_some_and(is_word) && !after.is_some_and(is_word)
+        })
+        .map(|m| (m.start(), m.pattern().as_usize(), m.end()))
+        .collect();
+    found.sort_unstable();
+    
+    let mut counts = [0usize; SIGNAL_COUNT];
+    // First byte at which each signal may match again
+    let mut next_free = [0usize; SIGNAL_COUNT];
+    for (start, pattern, end) in found {
+        let signal = TERM_SIGNALS[pattern];
+        if start >= next_free[signal] {
+            counts[signal] += 1;
+            next_free[signal] = end;
+        }
+    }
+    
+    counts
+}
+
+/// Is `c` a word character (same definition as the regex `\b`)?
+fn is_word(c: char) -> bool {
+    if c.is_ascii() {
+        c.is_ascii_alphanumeric() || c == '_'
+    } else {
+        regex_syntax::is_word_character(c)
+    }
+}
+
+/// Intensity: surface emphasis per word (0.0-1.0)
+///
+/// Counts ALL-CAPS words (2+ letters), every '!' at half weight, runs of
+/// two or more '!'/'?', elongated words ("sooo", 3+ repeated letters) and
+/// emphatic emoji, normalized by word count.
+fn intensity(text: &str, wc: f64) -> f64 {
+    let mut hits = 0.0;
+    
+    for word in text.split_whitespace() {
+        let letters: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+        
+        // ALL-CAPS word
+        if letters.len() >= 2 && letters.iter().all(|c| c.is_uppercase()) {
+            hits += 1.0;
+        }
+        
+        // Elongated word: same letter 3+ times in a row
+        let elongated = letters.windows(3).any(|w| {
+            w[0].to_lowercase().eq(w[1].to_lowercase()) && w[1].to_lowercase().eq(w[2].to_lowercase())
+        });
+        if elongated {
+            hits += 1.0;
+        }
     }
+    
+    // Exclamation marks and !!!/?!/?? runs
+    let mut run = 0;
+    for c in text.chars().chain(std::iter::once(' ')) {
+        if c == '!' {
+            hits += 0.5;
+        }
+        if c == '!' || c == '?' {
+            run += 1;
+        } else {
+            if run >= 2 {
+                hits += 1.0;
+            }
+            run = 0;
+        }
+    }
+    
+    // Emphatic emoji
+    hits += text.chars().filter(|c| EMPHATIC_EMOJI.contains(c)).count() as f64;
+    
+    (hits / wc).min(1.0)
 }
 
-/// Count regex matches in text
-fn count_matches(regex: &Regex, text: &str) -> f64 {
-    regex.find_iter(text).count() as f64
+/// Case-fold text the way `(?i)` compares it against the ASCII term lists
+///
+/// Characters whose lowercase form is not a single char are kept as-is, and
+/// long s folds to 's' (it is in the regex's case class for 's').
+fn fold(word: &str) -> String {
+    word.chars()
+        .map(|c| {
+            if c == 'ſ' {
+                return 's';
+            }
+            let mut lower = c.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(l), None) => l,
+                _ => c,
+            }
+        })
+        .collect()
 }
 
 // =============================================================================
@@ -228,6 +557,149 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_single_pass_matches_regex() {
+        let parser = RParser::new();
+        let texts = [
+            "I always think I should do better than everyone else immediately! It's their fault, not mine!",
+            "I'm sure I am right, I've said it, I'll say it again, I'd bet on it.",
+            "Ik vind dat iedereen altijd te veel praat, vroeger was het beter.",
+            "Dat is m'n fout, in het verleden had ik het anders dan nu gedaan.",
+            "No one is going to  do it, we're going to do it, GOING TO do it, going-to.",
+            "right now right now RIGHT NOW, in the past, back then, zo meteen",
+            "i_am not matched, but i-am is; all-time, anyway, everything's fine",
+            "ſhould \u{212A}now: unicode folding, İk, ÏK, naïve café",
+            "vs. versus... compared  to compared to, geen enkele, zou moeten had moeten",
+            "Ooit was het perfect, ooit zal ik gaan; should I? Me, me, later LATER.",
+            "   ",
+            "",
+        ];
+        
+        for text in &texts {
+            let fast = parser.parse(text);
+            let slow = parser.parse_regex(text);
+            assert_eq!(fast.value.to_bits(), slow.value.to_bits(), "r differs for {:?}", text);
+            assert_eq!(fast.word_count, slow.word_count);
+            assert_eq!(fast.signals.first_person, slow.signals.first_person, "{:?}", text);
+            assert_eq!(fast.signals.absolutes, slow.signals.absolutes, "{:?}", text);
+            assert_eq!(fast.signals.future_projection, slow.signals.future_projection, "{:?}", text);
+            assert_eq!(fast.signals.past_attachment, slow.signals.past_attachment, "{:?}", text);
+            assert_eq!(fast.signals.comparison, slow.signals.comparison, "{:?}", text);
+            assert_eq!(fast.signals.judgment, slow.signals.judgment, "{:?}", text);
+            assert_eq!(fast.signals.urgency, slow.signals.urgency, "{:?}", text);
+        }
+    }
+    
+    #[test]
+    fn test_parse_many_matches_parse() {
+        let parser = RParser::new();
+        let texts = ["Stillness.", "I always blame everyone", "Ik moet nu gaan"];
+        let batch = parser.parse_many(&texts);
+        
+        assert_eq!(batch.len(), texts.len());
+        for (text, r) in texts.iter().zip(&batch) {
+            assert_eq!(r.value, parser.quick_parse(text));
+        }
+    }
+    
+    #[test]
+    fn test_intensity_shouting_raises_r() {
+        let parser = RParser::with_intensity_weight(2.0);
+        let calm = parser.parse("This is wrong.");
+        let shout = parser.parse("THIS IS WRONG!!!");
+        
+        assert_eq!(calm.signals.intensity, 0.0);
+        assert!(shout.signals.intensity > 0.5, "got {}", shout.signals.intensity);
+        assert!(shout.value > calm.value);
+    }
+    
+    #[test]
+    fn test_intensity_elongation_and_emoji() {
+        let parser = RParser::new();
+        assert!(parser.parse("nooooo way").signals.intensity > 0.0);
+        assert!(parser.parse("really 😡").signals.intensity > 0.0);
+        assert!(parser.parse("seriously?!").signals.intensity > 0.0);
+        assert_eq!(parser.parse("I see a tree").signals.intensity, 0.0);
+    }
+    
+    #[test]
+    fn test_intensity_weight_zero_is_backward_compatible() {
+        let parser = RParser::new();
+        assert_eq!(parser.intensity_weight(), 0.0);
+        let shout = parser.parse("THIS IS WRONG!!!");
+        let calm = parser.parse("this is wrong");
+        
+        // Same words, same r: only the lexical signals count
+        assert_eq!(shout.value.to_bits(), calm.value.to_bits());
+        // judgment = 1/3, weight 3.5, normalized by the original 18.5
+        assert!((calm.value - (1.0 / 3.0) * 3.5 / 18.5).abs() < 1e-12);
+    }
+    
+    #[test]
+    fn test_split_sentences() {
+        assert_eq!(
+            split_sentences("It costs 3.5 euro. Really?! Yes.\nNew line"),
+            vec!["It costs 3.5 euro.", "Really?!", "Yes.", "New line"]
+        );
+        assert_eq!(split_sentences("He said \"stop.\" Then left"), vec!["He said \"stop.\"", "Then left"]);
+        assert_eq!(split_sentences("One sentence"), vec!["One sentence"]);
+        assert!(split_sentences("... !!!").is_empty());
+    }
+    
+    #[test]
+    fn test_whole_aggregation_has_no_sentences() {
+        let value = RParser::new().parse("The sky is blue. I always blame everyone!");
+        assert!(value.sentences.is_empty());
+    }
+    
+    #[test]
+    fn test_sentence_aggregation_undilutes_outburst() {
+        let text = "You always ruin everything and it is your fault! \
+                    The weather is mild today. The park is green. \
+                    Birds sit in the old oak trees. The river runs past the bridge.";
+        
+        let whole = RParser::new().parse(text).value;
+        let max = RParser::with_aggregation(RAggregation::Max).parse(text);
+        let mean = RParser::with_aggregation(RAggregation::Mean).parse(text);
+        let top1 = RParser::with_aggregation(RAggregation::TopK(1)).parse(text);
+        let top2 = RParser::with_aggregation(RAggregation::TopK(2)).parse(text);
+        
+        assert_eq!(max.sentences.len(), 5);
+        assert!(max.value > whole, "max {} should exceed whole {}", max.value, whole);
+        assert!(mean.value < max.value);
+        assert_eq!(top1.value, max.value);
+        assert!(top2.value < top1.value && top2.value > mean.value);
+        // Signals still describe the whole text
+        assert_eq!(max.word_count, text.split_whitespace().count());
+    }
+    
+    #[test]
+    fn test_single_sentence_aggregation_matches_whole() {
+        let text = "I should never have done that";
+        let whole = RParser::new().parse(text);
+        for aggregation in [RAggregation::Mean, RAggregation::Max, RAggregation::LengthWeighted] {
+            let value = RParser::with_aggregation(aggregation).parse(text);
+            assert_eq!(value.value, whole.value);
+            assert!(value.sentences.is_empty());
+        }
+    }
+    
+    #[test]
+    fn test_aggregation_combine_and_parse() {
+        let values = [(0.6, 2), (0.0, 6), (0.3, 2)];
+        assert!((RAggregation::Mean.combine(&values) - 0.3).abs() < 1e-12);
+        assert_eq!(RAggregation::Max.combine(&values), 0.6);
+        assert!((RAggregation::LengthWeighted.combine(&values) - 0.18).abs() < 1e-12);
+        assert!((RAggregation::TopK(2).combine(&values) - 0.45).abs() < 1e-12);
+        assert_eq!(RAggregation::TopK(10).combine(&values), RAggregation::Mean.combine(&values));
+        
+        assert_eq!("top-3".parse::<RAggregation>(), Ok(RAggregation::TopK(3)));
+        assert_eq!("length-weighted".parse::<RAggregation>(), Ok(RAggregation::LengthWeighted));
+        assert_eq!(RAggregation::TopK(2).to_string().parse::<RAggregation>(), Ok(RAggregation::TopK(2)));
+        assert!("top-0".parse::<RAggregation>().is_err());
+        assert!("median".parse::<RAggregation>().is_err());
+    }
+    
     #[test]
     fn test_confidence_increases_with_length() {
         let parser = RParser::new();
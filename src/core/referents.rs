@@ -0,0 +1,604 @@
+//! Referent tracking: which topics were raised, picked up or left hanging
+//!
+//! A referent is a noun-like content word of four or more letters, or a
+//! capitalised word (a name) that does not open a sentence, after stopword
+//! removal and stemming, so "garden" and "gardens" are one referent. Verbs
+//! (after a subject pronoun, "to" or a modal), "-ed"/"-ing"/"-ly"-style words
+//! not preceded by a determiner, time adverbs and interjections are skipped.
+//! Each referent is followed through the whole window: who raised it, when it
+//! was last mentioned and by whom. `ReferentTracker` does the same for a
+//! sliding window, one turn at a time.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use crate::core::thematic::{is_stopword, stem, Language};
+use crate::types::ReferentThread;
+
+/// Replies by other speakers after which an unanswered referent counts as dropped
+pub const DROP_AFTER_REPLIES: usize = 2;
+
+/// Words after which a noun follows ("the garden", "mijn moeder")
+const DETERMINERS: &[&str] = &[
+    "the", "a", "an", "my", "your", "his", "her", "our", "their", "its", "this", "that",
+    "these", "those", "some", "any", "no", "every", "each", "de", "het", "een", "mijn",
+    "jouw", "haar", "ons", "onze", "hun", "deze", "die", "dit", "dat", "geen", "elk", "elke",
+];
+
+/// Words after which a verb follows ("I worry", "to plant", "ik denk")
+const VERB_CUES: &[&str] = &[
+    "i", "you", "we", "they", "he", "she", "it", "to", "will", "would", "can", "could",
+    "should", "must", "might", "may", "shall", "don't", "didn't", "doesn't", "won't",
+    "can't", "ik", "jij", "je", "wij", "zij", "ze", "hij", "jullie", "zal", "wil", "kan",
+    "moet", "ga",
+];
+
+/// Time adverbs, fillers and interjections that never name a topic
+const NON_REFERENTS: &[&str] = &[
+    "hmm", "hmmm", "yeah", "okay", "wow", "well", "today", "tomorrow", "tonight",
+    "yesterday", "always", "never", "often", "sometimes", "maybe", "really", "actually",
+    "still", "already", "soon", "later", "anyway", "perhaps", "quite", "rather", "though",
+    "thanks", "please", "sorry", "nice", "good", "great", "vandaag", "morgen", "gisteren",
+    "altijd", "nooit", "vaak", "soms", "misschien", "echt", "eigenlijk", "even", "gewoon",
+    "prima", "leuk", "mooi", "straks", "later", "graag", "bedankt",
+];
+
+/// Verb, adjective and adverb endings: only a referent right after a determiner
+const NON_NOUN_SUFFIXES: &[&str] = &[
+    "ed", "ing", "ly", "ful", "ous", "less", "able", "ible", "lijk", "lijke",
+];
+
+/// Referents of one text: (key, surface form), each key once
+pub fn referents(text: &str) -> Vec<(String, String)> {
+    let language = Language::detect(text);
+    let mut seen = Vec::new();
+    let mut out = Vec::new();
+    
+    for sentence in text.split_inclusive(['.', '!', '?', '\n']) {
+        let mut previous = String::new();
+        let words = sentence
+            .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+            .map(|w| w.trim_matches(|c| c == '\'' || c == '’'))
+            .filter(|w| !w.is_empty());
+        
+        for (position, word) in words.enumerate() {
+            let lower = word.to_lowercase();
+            let after = std::mem::replace(&mut previous, lower.clone());
+            if is_stopword(&lower) || NON_REFERENTS.contains(&lower.as_str())
+                || lower.chars().any(|c| c.is_numeric())
+            {
+                continue;
+            }
+            
+            let capitalised = word.chars().next().is_some_and(char::is_uppercase);
+            let is_name = capitalised && position > 0 && word.chars().count() > 1;
+            if !is_name {
+                let after_determiner = DETERMINERS.contains(&after.as_str());
+                if lower.chars().count() < 4
+                    || VERB_CUES.contains(&after.as_str())
+                    || (!after_determiner && NON_NOUN_SUFFIXES.iter().any(|s| lower.ends_with(s)))
+                {
+                    continue;
+                }
+            }
+            
+            let key = stem(&lower, language);
+            if !seen.contains(&key) {
+                seen.push(key.clone());
+                out.push((key, word.to_string()));
+            }
+        }
+    }
+    
+    out
+}
+
+/// Follow every referent through the window (turns as (speaker, text), oldest first)
+pub fn track_referents(turns: &[(&str, &str)]) -> Vec<ReferentThread> {
+    let mut threads: Vec<ReferentThread> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    
+    for (at, (speaker, text)) in turns.iter().enumerate() {
+        for (key, surface) in referents(text) {
+            match index.get(&key) {
+                Some(&i) => {
+                    let thread = &mut threads[i];
+                    thread.mentions += 1;
+                    thread.last_mentioned_at = at;
+                    thread.last_mentioned_by = speaker.to_string();
+                    if thread.introduced_by != *speaker {
+                        thread.picked_up = true;
+                    }
+                }
+                None => {
+                    index.insert(key, threads.len());
+                    threads.push(ReferentThread {
+                        referent: surface,
+                        introduced_by: speaker.to_string(),
+                        introduced_at: at,
+                        last_mentioned_by: speaker.to_string(),
+                        last_mentioned_at: at,
+                        mentions: 1,
+                        picked_up: false,
+                    });
+                }
+            }
+        }
+    }
+    
+    threads
+}
+
+/// Replies by speakers other than `thread.introduced_by` after it was raised
+pub fn replies_since(thread: &ReferentThread, turns: &[(&str, &str)]) -> usize {
+    turns[thread.introduced_at + 1..]
+        .iter()
+        .filter(|(speaker, _)| *speaker != thread.introduced_by)
+        .count()
+}
+
+/// `replies_since` for many threads: turn positions per speaker, counted by binary search
+struct ReplyCounts<'a> {
+    turns: usize,
+    positions: HashMap<&'a str, Vec<usize>>,
+}
+
+impl<'a> ReplyCounts<'a> {
+    fn new(turns: &[(&'a str, &str)]) -> Self {
+        let mut positions: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (at, (speaker, _)) in turns.iter().enumerate() {
+            positions.entry(speaker).or_default().push(at);
+        }
+        Self { turns: turns.len(), positions }
+    }
+    
+    fn since(&self, thread: &ReferentThread) -> usize {
+        let after = self.turns - thread.introduced_at - 1;
+        let own = self.positions.get(thread.introduced_by.as_str())
+            .map_or(0, |p| p.len() - p.partition_point(|&at| at <= thread.introduced_at));
+        after - own
+    }
+}
+
+/// Threads nobody else picked up within `DROP_AFTER_REPLIES` replies
+pub fn dropped_threads(threads: &[ReferentThread], turns: &[(&str, &str)]) -> Vec<ReferentThread> {
+    let replies = ReplyCounts::new(turns);
+    threads.iter()
+        .filter(|t| !t.picked_up && replies.since(t) >= DROP_AFTER_REPLIES)
+        .cloned()
+        .collect()
+}
+
+/// Share of referents that had a chance to be picked up and were dropped
+pub fn reference_decay(threads: &[ReferentThread], turns: &[(&str, &str)]) -> f64 {
+    let replies = ReplyCounts::new(turns);
+    let eligible: Vec<&ReferentThread> = threads.iter()
+        .filter(|t| t.picked_up || replies.since(t) >= DROP_AFTER_REPLIES)
+        .collect();
+    if eligible.is_empty() {
+        return 0.0;
+    }
+    
+    let dropped = eligible.iter().filter(|t| !t.picked_up).count();
+    dropped as f64 / eligible.len() as f64
+}
+
+/// One mention of a referent
+#[derive(Debug, Clone)]
+struct Mention {
+    /// Id of the turn (see `ConversationWindow::turn_ids`)
+    turn: u64,
+    speaker: String,
+    /// The referent as written there
+    surface: String,
+    /// Position among the referents of that turn
+    order: usize,
+}
+
+/// A referent followed through a sliding window
+#[derive(Debug)]
+struct Thread {
+    /// Mentions in the window, oldest first (the first one raised it)
+    mentions: VecDeque<Mention>,
+    /// Mentions by speakers other than the one who raised it
+    others: usize,
+    /// Replies by other speakers since it was raised, counted up to `DROP_AFTER_REPLIES`
+    replies: usize,
+    /// Turn that gave the last of those replies
+    settled_by: Option<u64>,
+}
+
+impl Thread {
+    fn raised(&self) -> &Mention {
+        &self.mentions[0]
+    }
+    
+    fn eligible(&self) -> bool {
+        self.others > 0 || self.replies >= DROP_AFTER_REPLIES
+    }
+    
+    fn dropped(&self) -> bool {
+        self.others == 0 && self.replies >= DROP_AFTER_REPLIES
+    }
+}
+
+/// A turn as the tracker keeps it
+#[derive(Debug)]
+struct TrackedTurn {
+    id: u64,
+    speaker: String,
+    /// Threads this turn gave their last counted reply
+    settled: Vec<String>,
+}
+
+/// Eligible and dropped threads (the two sides of `reference_decay`)
+#[derive(Debug, Default)]
+struct Tally {
+    eligible: usize,
+    dropped: HashSet<String>,
+}
+
+impl Tally {
+    fn add(&mut self, key: &str, thread: &Thread) {
+        if thread.eligible() {
+            self.eligible += 1;
+        }
+        if thread.dropped() {
+            self.dropped.insert(key.to_string());
+        }
+    }
+    
+    fn remove(&mut self, key: &str, thread: &Thread) {
+        if thread.eligible() {
+            self.eligible -= 1;
+        }
+        self.dropped.remove(key);
+    }
+}
+
+/// Referent threads of a sliding window, kept up to date turn by turn
+///
+/// Turns enter and leave at either end; ids grow from front to back. Each
+/// step touches the
+/// referents of that turn and the threads still waiting for replies, so
+/// the decay of a long window costs no more than that of a short one.
+/// `decay` and `dropped_threads` agree with `reference_decay` and
+/// `dropped_threads` over the same turns.
+#[derive(Debug, Default)]
+pub struct ReferentTracker {
+    threads: HashMap<String, Thread>,
+    /// Threads with fewer than `DROP_AFTER_REPLIES` replies
+    waiting: HashSet<String>,
+    turns: VecDeque<TrackedTurn>,
+    tally: Tally,
+}
+
+impl ReferentTracker {
+    /// A new turn `id` by `speaker` with its `referents`
+    pub fn push(&mut self, id: u64, speaker: &str, referents: &[(String, String)]) {
+        // A reply to every waiting thread someone else raised
+        let mut settled = Vec::new();
+        for key in &self.waiting {
+            let thread = self.threads.get_mut(key).expect("waiting thread");
+            if thread.raised().speaker == speaker {
+                continue;
+            }
+            self.tally.remove(key, thread);
+            thread.replies += 1;
+            if thread.replies >= DROP_AFTER_REPLIES {
+                thread.settled_by = Some(id);
+                settled.push(key.clone());
+            }
+            self.tally.add(key, thread);
+        }
+        for key in &settled {
+            self.waiting.remove(key);
+        }
+        
+        for (order, (key, surface)) in referents.iter().enumerate() {
+            let mention = Mention {
+                turn: id,
+                speaker: speaker.to_string(),
+                surface: surface.clone(),
+                order,
+            };
+            match self.threads.get_mut(key) {
+                Some(thread) => {
+                    self.tally.remove(key, thread);
+                    if thread.raised().speaker != speaker {
+                        thread.others += 1;
+                    }
+                    thread.mentions.push_back(mention);
+                    self.tally.add(key, thread);
+                }
+                None => {
+                    self.threads.insert(key.clone(), Thread {
+                        mentions: VecDeque::from([mention]),
+                        others: 0,
+                        replies: 0,
+                        settled_by: None,
+                    });
+                    self.waiting.insert(key.clone());
+                }
+            }
+        }
+        
+        self.turns.push_back(TrackedTurn { id, speaker: speaker.to_string(), settled });
+    }
+    
+    /// A turn older than all others enters at the front (a merged part left it)
+    pub fn push_front(&mut self, id: u64, speaker: &str, referents: &[(String, String)]) {
+        self.turns.push_front(TrackedTurn { id, speaker: speaker.to_string(), settled: Vec::new() });
+        
+        for (order, (key, surface)) in referents.iter().enumerate() {
+            let mention = Mention {
+                turn: id,
+                speaker: speaker.to_string(),
+                surface: surface.clone(),
+                order,
+            };
+            let thread = self.threads.entry(key.clone()).or_insert_with(|| Thread {
+                mentions: VecDeque::new(),
+                others: 0,
+                replies: 0,
+                settled_by: None,
+            });
+            self.tally.remove(key, thread);
+            thread.mentions.push_front(mention);
+            self.recount(key);
+        }
+    }
+    
+    /// The oldest turn leaves, with the referents it was pushed with
+    pub fn pop_front(&mut self, referents: &[(String, String)]) {
+        if self.turns.pop_front().is_none() {
+            return;
+        }
+        
+        for (key, _) in referents {
+            let Some(thread) = self.threads.get_mut(key) else {
+                continue;
+            };
+            self.tally.remove(key, thread);
+            thread.mentions.pop_front();
+            if thread.mentions.is_empty() {
+                self.threads.remove(key);
+                self.waiting.remove(key);
+            } else {
+                self.recount(key);
+            }
+        }
+    }
+    
+    /// Count a thread again from the mention that now raised it (taken out of the tally)
+    fn recount(&mut self, key: &str) {
+        let Some(thread) = self.threads.get_mut(key) else {
+            return;
+        };
+        let raised = thread.raised().clone();
+        thread.others = thread.mentions.iter().filter(|m| m.speaker != raised.speaker).count();
+        thread.replies = 0;
+        thread.settled_by = None;
+        
+        let after = self.turns.partition_point(|t| t.id <= raised.turn);
+        for turn in self.turns.range_mut(after..) {
+            if turn.speaker != raised.speaker {
+                thread.replies += 1;
+                if thread.replies >= DROP_AFTER_REPLIES {
+                    thread.settled_by = Some(turn.id);
+                    turn.settled.push(key.to_string());
+                    break;
+                }
+            }
+        }
+        
+        if thread.replies < DROP_AFTER_REPLIES {
+            self.waiting.insert(key.to_string());
+        } else {
+            self.waiting.remove(key);
+        }
+        self.tally.add(key, thread);
+    }
+    
+    /// The newest turn leaves, with the referents it was pushed with
+    pub fn pop_back(&mut self, referents: &[(String, String)]) {
+        let Some(turn) = self.turns.pop_back() else {
+            return;
+        };
+        
+        for (key, _) in referents {
+            let Some(thread) = self.threads.get_mut(key) else {
+                continue;
+            };
+            self.tally.remove(key, thread);
+            thread.mentions.pop_back();
+            if thread.mentions.is_empty() {
+                self.threads.remove(key);
+                self.waiting.remove(key);
+                continue;
+            }
+            if thread.raised().speaker != turn.speaker {
+                thread.others -= 1;
+            }
+            self.tally.add(key, thread);
+        }
+        
+        // Take back its reply to the threads it counted for
+        for key in &self.waiting {
+            let thread = self.threads.get_mut(key).expect("waiting thread");
+            if thread.raised().speaker != turn.speaker {
+                self.tally.remove(key, thread);
+                thread.replies = thread.replies.saturating_sub(1);
+                self.tally.add(key, thread);
+            }
+        }
+        for key in turn.settled {
+            let Some(thread) = self.threads.get_mut(&key) else {
+                continue;
+            };
+            if thread.settled_by == Some(turn.id) {
+                self.tally.remove(&key, thread);
+                thread.replies -= 1;
+                thread.settled_by = None;
+                self.tally.add(&key, thread);
+                self.waiting.insert(key);
+            }
+        }
+    }
+    
+    /// `reference_decay` of the turns in the window
+    pub fn decay(&self) -> f64 {
+        if self.tally.eligible == 0 {
+            return 0.0;
+        }
+        self.tally.dropped.len() as f64 / self.tally.eligible as f64
+    }
+    
+    /// `dropped_threads` of the turns in the window, in the order they were raised
+    pub fn dropped_threads(&self) -> Vec<ReferentThread> {
+        let index_of = |id: u64| self.turns.partition_point(|t| t.id < id);
+        let mut dropped: Vec<&Thread> = self.tally.dropped.iter()
+            .filter_map(|key| self.threads.get(key))
+            .collect();
+        dropped.sort_by_key(|t| (t.raised().turn, t.raised().order));
+        
+        dropped.into_iter()
+            .map(|thread| {
+                let raised = thread.raised();
+                let last = thread.mentions.back().unwrap_or(raised);
+                ReferentThread {
+                    referent: raised.surface.clone(),
+                    introduced_by: raised.speaker.clone(),
+                    introduced_at: index_of(raised.turn),
+                    last_mentioned_by: last.speaker.clone(),
+                    last_mentioned_at: index_of(last.turn),
+                    mentions: thread.mentions.len(),
+                    picked_up: false,
+                }
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_referents_nouns_and_names() {
+        let keys: Vec<String> = referents("Yesterday Bob planted new gardens with the roses")
+            .into_iter()
+            .map(|(_, surface)| surface)
+            .collect();
+        assert_eq!(keys, vec!["Bob", "gardens", "roses"]);
+        assert!(referents("Is it ok for you?").is_empty());
+    }
+    
+    #[test]
+    fn test_referents_skip_verbs_adverbs_and_interjections() {
+        let found = referents("Hmm. Lovely, I planted tomatoes and I worry about tomorrow.");
+        assert_eq!(found, vec![("tomato".to_string(), "tomatoes".to_string())]);
+        
+        // A determiner makes an "-ing" word a noun; a sentence-initial capital is no name
+        let keys: Vec<String> = referents("Walking, then. I enjoyed the walking")
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(keys, vec!["walk"]);
+        assert_eq!(referents("Ik denk aan mijn moeder")[0].1, "moeder");
+        assert_eq!(referents("Ik denk aan mijn moeder").len(), 1);
+    }
+    
+    #[test]
+    fn test_tracking_across_window() {
+        let turns = [
+            ("A", "The garden needs roses"),
+            ("B", "Roses need sun"),
+            ("A", "My sister visits tomorrow"),
+            ("B", "The garden will look nice"),
+            ("A", "Shall we plant them"),
+        ];
+        let threads = track_referents(&turns);
+        
+        let garden = threads.iter().find(|t| t.referent == "garden").unwrap();
+        assert_eq!(garden.introduced_by, "A");
+        assert_eq!(garden.introduced_at, 0);
+        assert_eq!(garden.last_mentioned_at, 3);
+        assert_eq!(garden.last_mentioned_by, "B");
+        assert!(garden.picked_up);
+        
+        let sister = threads.iter().find(|t| t.referent == "sister").unwrap();
+        assert!(!sister.picked_up);
+        
+        // "sister" had only one reply since; "visits"/"tomorrow" likewise
+        assert!(dropped_threads(&threads, &turns).iter().all(|t| t.introduced_at != 2));
+    }
+    
+    #[test]
+    fn test_dropped_after_replies() {
+        let turns = [
+            ("A", "I worry about my mother and her garden"),
+            ("B", "The weather is lovely"),
+            ("A", "Yes"),
+            ("B", "Lovely weather for walking"),
+        ];
+        let threads = track_referents(&turns);
+        let dropped: Vec<String> = dropped_threads(&threads, &turns)
+            .into_iter()
+            .map(|t| t.referent)
+            .collect();
+        
+        assert!(!dropped.contains(&"worry".to_string()));
+        assert!(dropped.contains(&"mother".to_string()));
+        assert!(dropped.contains(&"garden".to_string()));
+        assert!(!dropped.contains(&"weather".to_string()));
+        assert!(reference_decay(&threads, &turns) > 0.5);
+    }
+    
+    #[test]
+    fn test_tracker_follows_sliding_window() {
+        let turns = [
+            ("A", "I worry about my mother and her garden"),
+            ("B", "The weather is lovely"),
+            ("A", "My sister visits tomorrow"),
+            ("B", "Lovely weather for walking"),
+            ("A", "The garden needs roses"),
+            ("B", "Roses need sun"),
+            ("A", "Shall we plant them"),
+        ];
+        let found: Vec<Vec<(String, String)>> = turns.iter().map(|(_, text)| referents(text)).collect();
+        let check = |tracker: &ReferentTracker, window: &[(&str, &str)]| {
+            let threads = track_referents(window);
+            assert_eq!(tracker.decay(), reference_decay(&threads, window), "{:?}", window);
+            assert_eq!(tracker.dropped_threads(), dropped_threads(&threads, window), "{:?}", window);
+        };
+        
+        let mut tracker = ReferentTracker::default();
+        for (i, (speaker, _)) in turns.iter().enumerate() {
+            tracker.push(i as u64, speaker, &found[i]);
+            check(&tracker, &turns[..=i]);
+        }
+        
+        // Oldest turns leave: "garden" is raised again by A at turn 4
+        tracker.pop_front(&found[0]);
+        check(&tracker, &turns[1..]);
+        tracker.pop_front(&found[1]);
+        check(&tracker, &turns[2..]);
+        
+        // The newest turn is replaced (a merged part joined it)
+        tracker.pop_back(&found[6]);
+        check(&tracker, &turns[2..6]);
+        tracker.pop_back(&found[5]);
+        check(&tracker, &turns[2..5]);
+        tracker.push(5, "B", &found[5]);
+        check(&tracker, &turns[2..6]);
+        
+        // An older turn comes back at the front
+        tracker.push_front(1, "B", &found[1]);
+        check(&tracker, &turns[1..6]);
+        tracker.push_front(0, "A", &found[0]);
+        check(&tracker, &turns[..6]);
+    }
+}
@@ -0,0 +1,251 @@
+//! Deterministic replay of audit logs
+//!
+//! Re-feeds the recorded turns through a `TranscriptRunner` at their
+//! recorded offsets and compares every regenerated output with the logged
+//! one. The runner keeps its own clock, so the replay speed only changes
+//! how long it takes, never the results. `recorded_scorers` rebuilds the
+//! parsers a session was recorded with; replaying with other scorers tests
+//! parser changes against recorded sessions.
+
+use std::io;
+use std::time::Duration;
+use sha2::{Sha256, Digest};
+use crate::core::{Scorers, TranscriptRunner, TurnStep, AnalyzeOptions};
+use crate::types::{AuditRecording, RecordedTurn, ReplayDiff, ReplayReport, ReplaySpeed, FacelockState};
+
+/// Tolerance for r and ΔC values
+const VALUE_TOLERANCE: f64 = 1e-6;
+
+/// Tolerance for stability (offsets are logged in µs, stability in ms)
+const STABLE_TOLERANCE_MS: u64 = 1;
+
+/// The scorers a recording was made with, or `fallback` when it did not record
+/// them (custom scorers, older logs)
+pub fn recorded_scorers(recording: &AuditRecording, fallback: &Scorers) -> io::Result<Scorers> {
+    match recording.config.scorers {
+        Some(ref config) => Scorers::from_config(config),
+        None => Ok(fallback.clone()),
+    }
+}
+
+/// Replay a recording and diff the outputs; `on_turn` sees every turn as it is re-fed
+pub fn replay(
+    recording: &AuditRecording,
+    scorers: &Scorers,
+    speed: ReplaySpeed,
+    sign_fn: impl Fn(&[u8]) -> [u8; 64],
+    mut on_turn: impl FnMut(&RecordedTurn, &TurnStep),
+) -> ReplayReport {
+    let options = AnalyzeOptions {
+        window: recording.config.window,
+        merge_gap: recording.config.merge_gap_ms.map(Duration::from_millis),
+        min_confidence: recording.config.min_confidence,
+    };
+    let mut runner = TranscriptRunner::new(scorers, &options, session_bytes(&recording.session_id));
+    let mut diffs = Vec::new();
+    let mut final_state = FacelockState::Waiting;
+    let mut prev_offset = 0;
+    
+    for turn in &recording.turns {
+        let wait = Duration::from_micros(turn.offset_us.saturating_sub(prev_offset));
+        match speed {
+            ReplaySpeed::Original => std::thread::sleep(wait),
+            ReplaySpeed::Factor(factor) => std::thread::sleep(wait.div_f64(factor)),
+            ReplaySpeed::Instant => {}
+        }
+        prev_offset = prev_offset.max(turn.offset_us);
+        
+        let step = runner.step(&turn.speaker, &turn.text, Duration::from_micros(prev_offset), &sign_fn);
+        diffs.extend(diff_turn(turn, &step));
+        final_state = step.output.state;
+        on_turn(turn, &step);
+    }
+    
+    ReplayReport {
+        session_id: recording.session_id.clone(),
+        turns: recording.turns.len(),
+        final_state,
+        diffs,
+    }
+}
+
+/// Compare one regenerated turn with what was recorded
+fn diff_turn(recorded: &RecordedTurn, step: &TurnStep) -> Vec<ReplayDiff> {
+    let mut diffs = Vec::new();
+    let seq = recorded.seq;
+    let mut push = |field: &str, before: String, after: String| {
+        diffs.push(ReplayDiff { seq, field: field.to_string(), recorded: before, replayed: after });
+    };
+    
+    if let Some(ref output) = recorded.state {
+        if output.state != step.output.state {
+            push("state", output.state.to_string(), step.output.state.to_string());
+        }
+        if output.reason != step.output.reason {
+            push("reason", output.reason.code().to_string(), step.output.reason.code().to_string());
+        }
+        if (output.r - step.output.r).abs() > VALUE_TOLERANCE {
+            push("r", format!("{:.6}", output.r), format!("{:.6}", step.output.r));
+        }
+        if output.stable_ms.abs_diff(step.output.stable_ms) > STABLE_TOLERANCE_MS {
+            push("stable_ms", output.stable_ms.to_string(), step.output.stable_ms.to_string());
+        }
+    }
+    if let Some(ref dc) = recorded.dc {
+        let same = match (dc.value, step.dc.value) {
+            (Some(a), Some(b)) => (a - b).abs() <= VALUE_TOLERANCE,
+            (a, b) => a == b,
+        };
+        if !same {
+            push("dc", format_dc(dc.value), format_dc(step.dc.value));
+        }
+        if dc.reason != step.dc.reason {
+            push("dc_reason", dc.reason.code().to_string(), step.dc.reason.code().to_string());
+        }
+    }
+    let replayed_proof = step.report.proof.is_some();
+    if recorded.proof != replayed_proof {
+        push("proof", recorded.proof.to_string(), replayed_proof.to_string());
+    }
+    diffs
+}
+
+fn format_dc(dc: Option<f64>) -> String {
+    dc.map_or_else(|| "UNKNOWN".to_string(), |v| format!("{:.6}", v))
+}
+
+/// Proof session bytes derived from the recorded session ID
+fn session_bytes(session_id: &str) -> [u8; 16] {
+    let hash: [u8; 32] = Sha256::digest(session_id.as_bytes()).into();
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&hash[0..16]);
+    id
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::core::{RParser, DcParser, ThematicModel};
+    use crate::types::{AuditConfig, ScorerConfig, WindowPolicy};
+    
+    fn sign(data: &[u8]) -> [u8; 64] {
+        let hash: [u8; 32] = Sha256::digest(data).into();
+        let mut sig = [0u8; 64];
+        sig[0..32].copy_from_slice(&hash);
+        sig
+    }
+    
+    /// A calm exchange every 4 s, recorded as a live session would log it
+    fn recording() -> AuditRecording {
+        let lines = [
+            ("Anna", "The sky is blue today"),
+            ("Ben", "The sky is blue and clear today"),
+            ("Anna", "The sky is blue and clear today"),
+            ("Ben", "The sky is blue and clear today"),
+            ("Anna", "The sky is blue and clear today"),
+            ("Ben", "The sky is blue and clear today"),
+        ];
+        let config = AuditConfig {
+            window: WindowPolicy::standard(),
+            merge_gap_ms: None,
+            min_confidence: crate::CONFIDENCE_MIN_LOCK,
+            scorers: Some(ScorerConfig::default()),
+        };
+        let options = AnalyzeOptions { window: config.window, merge_gap: None, min_confidence: config.min_confidence };
+        let mut runner = TranscriptRunner::new(&Scorers::default(), &options, [0; 16]);
+        let turns = lines.iter().enumerate().map(|(i, (speaker, text))| {
+            let offset_us = 2_000_000 + i as u64 * 4_000_000;
+            let step = runner.step(speaker, text, Duration::from_micros(offset_us), sign);
+            RecordedTurn {
+                seq: i as u64 + 1,
+                offset_us,
+                speaker: speaker.to_string(),
+                text: text.to_string(),
+                proof: step.report.proof.is_some(),
+                state: Some(step.output),
+                dc: Some(step.dc),
+            }
+        }).collect();
+        AuditRecording {
+            session_id: "test".to_string(),
+            mode: "duo".to_string(),
+            version: crate::VERSION.to_string(),
+            started_unix_ms: 0,
+            config,
+            turns,
+        }
+    }
+    
+    #[test]
+    fn test_replay_reproduces_recording() {
+        let recording = recording();
+        assert!(recording.turns.iter().any(|t| t.proof));
+        
+        let mut seen = 0;
+        let report = replay(&recording, &Scorers::default(), ReplaySpeed::Instant, sign, |_, _| seen += 1);
+        assert!(report.is_clean(), "{:?}", report.diffs);
+        assert_eq!(seen, 6);
+        assert_eq!(report.final_state, FacelockState::Locked);
+    }
+    
+    #[test]
+    fn test_speed_does_not_change_results() {
+        // 22 s of session in 22 ms of wall time
+        let report = replay(&recording(), &Scorers::default(), ReplaySpeed::Factor(1000.0), sign, |_, _| {});
+        assert!(report.is_clean(), "{:?}", report.diffs);
+    }
+    
+    #[test]
+    fn test_parser_change_shows_up_as_diffs() {
+        let scorers = Scorers::new(
+            Arc::new(RParser::new()),
+            Arc::new(DcParser::with_thematic_model(ThematicModel::Jaccard)),
+        );
+        let report = replay(&recording(), &scorers, ReplaySpeed::Instant, sign, |_, _| {});
+        assert!(report.diffs.iter().any(|d| d.field == "dc"));
+    }
+    
+    #[test]
+    fn test_recorded_settings_are_replayed() {
+        // Recorded with Jaccard: the replaying side's TF-IDF scorers are not used
+        let jaccard = ScorerConfig { thematic_model: ThematicModel::Jaccard, ..ScorerConfig::default() };
+        let mut recording = recording();
+        let scorers = Scorers::from_config(&jaccard).unwrap();
+        let mut runner = TranscriptRunner::new(&scorers, &AnalyzeOptions::default(), [0; 16]);
+        for turn in &mut recording.turns {
+            let step = runner.step(&turn.speaker, &turn.text, Duration::from_micros(turn.offset_us), sign);
+            turn.state = Some(step.output);
+            turn.proof = step.report.proof.is_some();
+            turn.dc = Some(step.dc);
+        }
+        recording.config.scorers = Some(jaccard);
+        
+        let scorers = recorded_scorers(&recording, &Scorers::default()).unwrap();
+        let report = replay(&recording, &scorers, ReplaySpeed::Instant, sign, |_, _| {});
+        assert!(report.is_clean(), "{:?}", report.diffs);
+        
+        // Without recorded settings the fallback scores
+        recording.config.scorers = None;
+        let scorers = recorded_scorers(&recording, &Scorers::default()).unwrap();
+        let report = replay(&recording, &scorers, ReplaySpeed::Instant, sign, |_, _| {});
+        assert!(report.diffs.iter().any(|d| d.field == "dc"));
+    }
+    
+    #[test]
+    fn test_tampered_output_is_reported() {
+        let mut recording = recording();
+        recording.turns[2].state.as_mut().unwrap().state = FacelockState::Drift;
+        recording.turns[4].dc = Some(crate::types::DcResult::unknown(crate::types::DcReason::R013_DC_UNKNOWN_TIMEOUT));
+        
+        let report = replay(&recording, &Scorers::default(), ReplaySpeed::Instant, sign, |_, _| {});
+        let fields: Vec<(u64, &str)> = report.diffs.iter().map(|d| (d.seq, d.field.as_str())).collect();
+        assert!(fields.contains(&(3, "state")));
+        assert!(fields.contains(&(5, "dc")));
+        assert!(fields.contains(&(5, "dc_reason")));
+    }
+}
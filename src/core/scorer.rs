@@ -0,0 +1,269 @@
+//! Scorer traits: pluggable r and ΔC models
+//!
+//! `RParser` and `DcParser` are the default implementations. Sessions, the
+//! API and the CLI only depend on these traits, so an alternative lexicon
+//! model or an ensemble can be swapped in without touching the pipeline.
+
+use std::any::Any;
+use std::fmt::{self, Debug};
+use std::io;
+use std::sync::Arc;
+use rayon::prelude::*;
+use sha2::{Sha256, Digest};
+use crate::core::{RParser, DcParser, DcState, IdfCorpus};
+use crate::types::{ConversationWindow, MultiScaleWindow, RValue, DcResult, ScaleDc, ScorerConfig};
+
+/// Scores a single text for ego noise (r)
+pub trait RScorer: Debug + Send + Sync {
+    /// Parse text and return r value with full signal breakdown
+    fn parse(&self, text: &str) -> RValue;
+    
+    /// Quick parse - just return the r value
+    fn quick_parse(&self, text: &str) -> f64 {
+        self.parse(text).value
+    }
+    
+    /// Parse many texts in parallel (uses all cores)
+    fn parse_many(&self, texts: &[&str]) -> Vec<RValue> {
+        texts.par_iter().map(|text| self.parse(text)).collect()
+    }
+}
+
+/// Scores a conversation window for coherence drift (ΔC)
+pub trait DcScorer: Debug + Send + Sync {
+    /// Calculate ΔC from conversation window
+    /// Returns DcResult with value or UNKNOWN reason
+    fn calculate(&self, window: &ConversationWindow) -> DcResult;
+    
+    /// Calculate ΔC, keeping incremental state for this window in `state`
+    /// (scorers without incremental state score the window from scratch)
+    fn calculate_with(&self, window: &ConversationWindow, _state: &mut DcScorerState) -> DcResult {
+        self.calculate(window)
+    }
+    
+    /// ΔC of every window scale, primary first (one state per scale in `states`)
+    fn calculate_scales(&self, windows: &MultiScaleWindow, states: &mut Vec<DcScorerState>) -> Vec<ScaleDc> {
+        states.resize_with(windows.scales().count(), DcScorerState::default);
+        windows.scales()
+            .zip(states.iter_mut())
+            .map(|((name, window), state)| ScaleDc {
+                scale: name.to_string(),
+                policy: window.policy(),
+                turn_count: window.len(),
+                dc: self.calculate_with(window, state),
+            })
+            .collect()
+    }
+}
+
+/// Incremental state a `DcScorer` keeps for one window
+///
+/// Opaque to sessions: they start from `default()` and hand the state back on
+/// every call. Each scorer keeps its own type in it (`DcParser` a `DcState`).
+#[derive(Default)]
+pub struct DcScorerState(Option<Box<dyn Any + Send + Sync>>);
+
+impl DcScorerState {
+    /// Wrap a scorer's own state
+    pub fn new<S: Any + Send + Sync>(state: S) -> Self {
+        Self(Some(Box::new(state)))
+    }
+    
+    /// The state as `S`, if it holds one
+    pub fn get_mut<S: Any>(&mut self) -> Option<&mut S> {
+        self.0.as_mut()?.downcast_mut()
+    }
+    
+    /// The state as `S`, starting from `S::default()` if it holds none (or another type)
+    pub fn get_or_default<S: Any + Send + Sync + Default>(&mut self) -> &mut S {
+        if !self.0.as_ref().is_some_and(|state| state.is::<S>()) {
+            *self = Self::new(S::default());
+        }
+        self.get_mut().expect("state was just set")
+    }
+}
+
+impl Debug for DcScorerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DcScorerState")
+            .field(&self.0.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+impl RScorer for RParser {
+    fn parse(&self, text: &str) -> RValue {
+        RParser::parse(self, text)
+    }
+    
+    fn quick_parse(&self, text: &str) -> f64 {
+        RParser::quick_parse(self, text)
+    }
+    
+    fn parse_many(&self, texts: &[&str]) -> Vec<RValue> {
+        RParser::parse_many(self, texts)
+    }
+}
+
+impl DcScorer for DcParser {
+    fn calculate(&self, window: &ConversationWindow) -> DcResult {
+        DcParser::calculate(self, window)
+    }
+    
+    fn calculate_with(&self, window: &ConversationWindow, state: &mut DcScorerState) -> DcResult {
+        DcParser::calculate_with(self, window, state.get_or_default::<DcState>())
+    }
+}
+
+/// The pair of scorers a session runs with
+#[derive(Debug, Clone)]
+pub struct Scorers {
+    pub r: Arc<dyn RScorer>,
+    pub dc: Arc<dyn DcScorer>,
+    /// Settings of the built-in parsers (None for custom scorers)
+    pub config: Option<ScorerConfig>,
+}
+
+impl Scorers {
+    /// Create from custom scorers
+    pub fn new(r: Arc<dyn RScorer>, dc: Arc<dyn DcScorer>) -> Self {
+        Self { r, dc, config: None }
+    }
+    
+    /// Built-in parsers with these settings; reads the corpus file, which
+    /// must match `corpus_sha256` when that is set
+    pub fn from_config(config: &ScorerConfig) -> io::Result<Self> {
+        let mut r_parser = RParser::with_intensity_weight(config.intensity_weight);
+        r_parser.set_aggregation(config.r_aggregation);
+        let mut dc_parser = DcParser::with_thematic_model(config.thematic_model);
+        dc_parser.set_group_aggregation(config.group_aggregation);
+        dc_parser.set_balance_weight(config.weights.balance);
+        dc_parser.set_style_share(config.weights.style_share);
+        dc_parser.set_timing_weight(config.weights.timing);
+        
+        let mut config = config.clone();
+        if let Some(ref path) = config.corpus {
+            let text = std::fs::read_to_string(path)?;
+            let hash: String = Sha256::digest(text.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect();
+            if config.corpus_sha256.as_ref().is_some_and(|recorded| *recorded != hash) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "corpus changed since it was recorded"));
+            }
+            config.corpus_sha256 = Some(hash);
+            dc_parser.set_corpus(Some(Arc::new(IdfCorpus::from_documents(text.lines()))));
+        }
+        
+        Ok(Self {
+            r: Arc::new(r_parser),
+            dc: Arc::new(dc_parser),
+            config: Some(config),
+        })
+    }
+}
+
+impl Default for Scorers {
+    /// Built-in lexicon parsers
+    fn default() -> Self {
+        Self {
+            config: Some(ScorerConfig::default()),
+            ..Self::new(Arc::new(RParser::new()), Arc::new(DcParser::new()))
+        }
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{RSignals, Turn};
+    
+    /// Scorer that always reports the same r
+    #[derive(Debug)]
+    struct FixedScorer(f64);
+    
+    impl RScorer for FixedScorer {
+        fn parse(&self, text: &str) -> RValue {
+            let words = text.split_whitespace().count();
+            RValue::new(self.0, RSignals::zero(), 1.0, words)
+        }
+    }
+    
+    #[test]
+    fn test_default_scorers_match_parsers() {
+        let scorers = Scorers::default();
+        let text = "I always think I should do better than everyone else";
+        
+        assert_eq!(scorers.r.quick_parse(text), RParser::new().quick_parse(text));
+        
+        let mut window = ConversationWindow::new();
+        window.add_turn(Turn::new("A", "The sky is blue", 0.05));
+        window.add_turn(Turn::new("B", "Yes, very blue", 0.05));
+        assert_eq!(
+            scorers.dc.calculate(&window).value,
+            DcParser::new().calculate(&window).value
+        );
+    }
+    
+    #[test]
+    fn test_scales_primary_first() {
+        use std::time::{Duration, Instant};
+        
+        let earlier = Instant::now() - Duration::from_secs(90);
+        let mut windows = MultiScaleWindow::new();
+        windows.add_turn(Turn::new("A", "How is the garden doing", 0.05).with_timestamp(earlier));
+        windows.add_turn(Turn::new("B", "The roses are blooming", 0.05).with_timestamp(earlier));
+        windows.add_turn(Turn::new("A", "My car broke down", 0.05));
+        
+        let mut states = Vec::new();
+        let scales = Scorers::default().dc.calculate_scales(&windows, &mut states);
+        assert_eq!(scales.len(), 3);
+        assert_eq!(states.len(), 3);
+        assert_eq!(scales[0].scale, "short");
+        assert!(!scales[0].dc.is_known(), "only one recent turn");
+        assert_eq!(scales[2].turn_count, 3);
+        assert!(scales[2].dc.is_known());
+    }
+    
+    /// ΔC scorer that counts its calls per window in its own state
+    #[derive(Debug)]
+    struct CountingScorer;
+    
+    impl DcScorer for CountingScorer {
+        fn calculate(&self, window: &ConversationWindow) -> DcResult {
+            DcParser::new().calculate(window)
+        }
+        
+        fn calculate_with(&self, window: &ConversationWindow, state: &mut DcScorerState) -> DcResult {
+            *state.get_or_default::<usize>() += 1;
+            self.calculate(window)
+        }
+    }
+    
+    #[test]
+    fn test_custom_dc_scorer_keeps_own_state() {
+        let mut window = ConversationWindow::new();
+        window.add_turn(Turn::new("A", "The sky is blue", 0.05));
+        window.add_turn(Turn::new("B", "Yes, very blue", 0.05));
+        
+        let scorers = Scorers::new(Arc::new(RParser::new()), Arc::new(CountingScorer));
+        let mut state = DcScorerState::default();
+        scorers.dc.calculate_with(&window, &mut state);
+        scorers.dc.calculate_with(&window, &mut state);
+        assert_eq!(state.get_mut::<usize>(), Some(&mut 2));
+        
+        // The built-in parser replaces a state it did not make with its own
+        let result = Scorers::default().dc.calculate_with(&window, &mut state);
+        assert_eq!(result.value, DcParser::new().calculate(&window).value);
+        assert!(state.get_mut::<usize>().is_none());
+        assert!(state.get_mut::<DcState>().is_some());
+    }
+    
+    #[test]
+    fn test_custom_r_scorer() {
+        let scorers = Scorers::new(Arc::new(FixedScorer(0.42)), Arc::new(DcParser::new()));
+        assert_eq!(scorers.r.quick_parse("anything at all"), 0.42);
+        assert_eq!(scorers.r.parse("anything at all").word_count, 3);
+    }
+}
@@ -0,0 +1,226 @@
+//! Sentiment: valence/arousal lexicon for EN and NL
+//!
+//! Each lexicon word carries a valence (-1.0 negative … +1.0 positive) and an
+//! arousal (0.0 calm … 1.0 agitated). Intensifiers scale the next sentiment
+//! word, negators within the three preceding words flip and damp it, and
+//! exclamation marks push the turn further in the direction it already leans.
+
+use std::collections::HashMap;
+use lazy_static::lazy_static;
+
+/// (word, valence, arousal)
+const LEXICON: &[(&str, f64, f64)] = &[
+    // English - positive
+    ("good", 0.5, 0.3), ("great", 0.7, 0.5), ("nice", 0.5, 0.3), ("love", 0.8, 0.6),
+    ("loved", 0.8, 0.6), ("lovely", 0.7, 0.4), ("like", 0.3, 0.2), ("happy", 0.7, 0.5),
+    ("glad", 0.6, 0.4), ("joy", 0.8, 0.6), ("calm", 0.5, 0.1), ("peace", 0.6, 0.1),
+    ("peaceful", 0.6, 0.1), ("beautiful", 0.7, 0.4), ("wonderful", 0.8, 0.5),
+    ("amazing", 0.8, 0.7), ("excellent", 0.8, 0.5), ("thanks", 0.5, 0.3), ("thank", 0.5, 0.3),
+    ("grateful", 0.7, 0.3), ("fine", 0.3, 0.1), ("okay", 0.2, 0.1), ("ok", 0.2, 0.1),
+    ("agree", 0.4, 0.2), ("excited", 0.6, 0.8), ("relieved", 0.5, 0.2), ("hope", 0.4, 0.4),
+    ("fun", 0.6, 0.6), ("kind", 0.5, 0.2), ("safe", 0.4, 0.1), ("warm", 0.4, 0.2),
+    ("proud", 0.6, 0.5), ("enjoy", 0.6, 0.4), ("perfect", 0.8, 0.5), ("better", 0.3, 0.3),
+    // English - negative
+    ("bad", -0.5, 0.4), ("terrible", -0.8, 0.7), ("awful", -0.8, 0.7), ("horrible", -0.8, 0.7),
+    ("hate", -0.8, 0.8), ("hated", -0.8, 0.8), ("angry", -0.7, 0.9), ("mad", -0.6, 0.8),
+    ("furious", -0.9, 1.0), ("annoyed", -0.5, 0.6), ("annoying", -0.5, 0.6),
+    ("frustrated", -0.6, 0.7), ("sad", -0.6, 0.3), ("upset", -0.6, 0.6), ("hurt", -0.6, 0.5),
+    ("afraid", -0.6, 0.7), ("scared", -0.6, 0.8), ("fear", -0.6, 0.7), ("worried", -0.5, 0.6),
+    ("worry", -0.4, 0.5), ("stress", -0.5, 0.7), ("stressed", -0.5, 0.7), ("tired", -0.3, 0.1),
+    ("lonely", -0.6, 0.2), ("disappointed", -0.6, 0.4), ("wrong", -0.4, 0.4),
+    ("stupid", -0.7, 0.7), ("useless", -0.6, 0.5), ("sorry", -0.2, 0.3), ("boring", -0.4, 0.1),
+    ("worse", -0.4, 0.4), ("worst", -0.7, 0.6), ("problem", -0.3, 0.4), ("fault", -0.5, 0.5),
+    ("blame", -0.5, 0.6), ("unfair", -0.6, 0.6), ("ridiculous", -0.6, 0.7),
+    // Dutch - positief
+    ("goed", 0.5, 0.3), ("fijn", 0.5, 0.3), ("leuk", 0.5, 0.4), ("mooi", 0.6, 0.3),
+    ("prachtig", 0.8, 0.5), ("geweldig", 0.8, 0.7), ("blij", 0.7, 0.5), ("gelukkig", 0.8, 0.5),
+    ("rustig", 0.5, 0.1), ("vrede", 0.6, 0.1), ("dank", 0.5, 0.3), ("bedankt", 0.5, 0.3),
+    ("dankbaar", 0.7, 0.3), ("prima", 0.4, 0.1), ("eens", 0.3, 0.2), ("hoop", 0.4, 0.4),
+    ("opgelucht", 0.5, 0.2), ("trots", 0.6, 0.5), ("lief", 0.6, 0.3), ("heerlijk", 0.7, 0.4),
+    ("perfect", 0.8, 0.5), ("beter", 0.3, 0.3), ("veilig", 0.4, 0.1),
+    // Dutch - negatief
+    ("slecht", -0.5, 0.4), ("verschrikkelijk", -0.8, 0.7), ("vreselijk", -0.8, 0.7),
+    ("haat", -0.8, 0.8), ("boos", -0.7, 0.9), ("kwaad", -0.7, 0.9), ("woedend", -0.9, 1.0),
+    ("geïrriteerd", -0.5, 0.6), ("gefrustreerd", -0.6, 0.7), ("verdrietig", -0.6, 0.3),
+    ("bang", -0.6, 0.7), ("angst", -0.6, 0.7), ("zorgen", -0.4, 0.5), ("moe", -0.3, 0.1),
+    ("eenzaam", -0.6, 0.2), ("teleurgesteld", -0.6, 0.4), ("fout", -0.4, 0.4),
+    ("dom", -0.7, 0.6), ("stom", -0.6, 0.6), ("sorry", -0.2, 0.3), ("saai", -0.4, 0.1),
+    ("erger", -0.4, 0.4), ("probleem", -0.3, 0.4), ("schuld", -0.5, 0.5),
+    ("oneerlijk", -0.6, 0.6), ("belachelijk", -0.6, 0.7), ("pijn", -0.6, 0.6),
+    ("boze", -0.7, 0.9), ("bange", -0.6, 0.7),
+];
+
+/// Words that scale the next sentiment word (factor)
+const INTENSIFIERS: &[(&str, f64)] = &[
+    ("very", 1.5), ("really", 1.4), ("so", 1.3), ("extremely", 1.8), ("totally", 1.5),
+    ("absolutely", 1.6), ("incredibly", 1.7), ("super", 1.5), ("too", 1.3),
+    ("slightly", 0.5), ("somewhat", 0.6), ("bit", 0.6), ("little", 0.6), ("kinda", 0.7),
+    ("heel", 1.5), ("erg", 1.5), ("zeer", 1.5), ("echt", 1.4), ("ontzettend", 1.8),
+    ("enorm", 1.7), ("zo", 1.3), ("beetje", 0.6), ("enigszins", 0.6),
+];
+
+/// Words that negate a sentiment word within the next three words
+const NEGATORS: &[&str] = &[
+    "not", "no", "never", "nothing", "nobody", "neither", "nor", "without", "hardly",
+    "niet", "geen", "nooit", "niets", "niemand", "nauwelijks", "zonder",
+];
+
+/// Scale applied to a negated sentiment word ("not bad" is mildly positive)
+const NEGATION_SCALE: f64 = -0.74;
+
+/// Smoothing for the valence sum: v / sqrt(v² + alpha) maps any sum into (-1, 1)
+const VALENCE_ALPHA: f64 = 1.0;
+
+/// Valence push per exclamation mark (up to three)
+const EXCLAMATION_BOOST: f64 = 0.07;
+
+lazy_static! {
+    static ref LEXICON_MAP: HashMap<&'static str, (f64, f64)> =
+        LEXICON.iter().map(|&(w, v, a)| (w, (v, a))).collect();
+    static ref INTENSIFIER_MAP: HashMap<&'static str, f64> =
+        INTENSIFIERS.iter().copied().collect();
+}
+
+/// Sentiment of one text
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Sentiment {
+    /// Signed mood: -1.0 (negative) … +1.0 (positive)
+    pub valence: f64,
+    /// Agitation: 0.0 (calm) … 1.0 (agitated)
+    pub arousal: f64,
+    /// Number of lexicon words that contributed
+    pub hits: usize,
+}
+
+/// Find a word in the lexicon, falling back to the Dutch uninflected adjective
+/// ("mooie" → "mooi", "domme" → "dom")
+fn lookup(word: &str) -> Option<(f64, f64)> {
+    if let Some(&entry) = LEXICON_MAP.get(word) {
+        return Some(entry);
+    }
+    
+    let stem = word.strip_suffix('e').filter(|_| word.chars().count() >= 5)?;
+    if let Some(&entry) = LEXICON_MAP.get(stem) {
+        return Some(entry);
+    }
+    
+    let bytes = stem.as_bytes();
+    let n = bytes.len();
+    if n >= 2 && bytes[n - 1] == bytes[n - 2] {
+        return LEXICON_MAP.get(&stem[..n - 1]).copied();
+    }
+    None
+}
+
+/// Score a text against the valence/arousal lexicon
+pub fn score_sentiment(text: &str) -> Sentiment {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+        .map(|w| w.trim_matches(|c| c == '\'' || c == '’'))
+        .filter(|w| !w.is_empty())
+        .collect();
+    
+    let mut sum = 0.0;
+    let mut arousal_sum = 0.0;
+    let mut hits = 0;
+    
+    for (i, word) in words.iter().enumerate() {
+        let Some((valence, arousal)) = lookup(word) else {
+            continue;
+        };
+        
+        let mut v = valence;
+        let mut a = arousal;
+        if let Some(factor) = i.checked_sub(1).and_then(|j| INTENSIFIER_MAP.get(words[j])) {
+            v *= factor;
+            a = (a * factor).min(1.0);
+        }
+        
+        let negated = words[i.saturating_sub(3)..i]
+            .iter()
+            .any(|w| NEGATORS.contains(w) || w.ends_with("n't"));
+        if negated {
+            v *= NEGATION_SCALE;
+        }
+        
+        sum += v;
+        arousal_sum += a;
+        hits += 1;
+    }
+    
+    if hits == 0 {
+        return Sentiment::default();
+    }
+    
+    let exclamations = text.matches('!').count().min(3) as f64;
+    sum += sum.signum() * exclamations * EXCLAMATION_BOOST;
+    
+    Sentiment {
+        valence: sum / (sum * sum + VALENCE_ALPHA).sqrt(),
+        arousal: (arousal_sum / hits as f64 + exclamations * 0.05).min(1.0),
+        hits,
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_signed_valence() {
+        assert!(score_sentiment("I love this, it is wonderful").valence > 0.5);
+        assert!(score_sentiment("This is terrible and I hate it").valence < -0.5);
+        assert_eq!(score_sentiment("The table is made of wood").valence, 0.0);
+        assert_eq!(score_sentiment("The table is made of wood").hits, 0);
+    }
+    
+    #[test]
+    fn test_dutch_lexicon() {
+        assert!(score_sentiment("Ik ben echt blij, het is prachtig").valence > 0.5);
+        assert!(score_sentiment("Ik ben boos en gefrustreerd").valence < -0.5);
+        assert!(score_sentiment("Wat een mooie dag").valence > 0.0);
+        assert!(score_sentiment("Wat een domme fout").valence < 0.0);
+        assert_eq!(score_sentiment("I made it").hits, 0);
+    }
+    
+    #[test]
+    fn test_negation_flips_and_damps() {
+        let good = score_sentiment("that is good").valence;
+        let not_good = score_sentiment("that is not good").valence;
+        let dont_like = score_sentiment("I don't like it").valence;
+        let niet_leuk = score_sentiment("ik vind het niet leuk").valence;
+        
+        assert!(not_good < 0.0);
+        assert!(not_good.abs() < good);
+        assert!(dont_like < 0.0);
+        assert!(niet_leuk < 0.0);
+        assert!(score_sentiment("not bad at all").valence > 0.0);
+    }
+    
+    #[test]
+    fn test_intensifiers_and_exclamations() {
+        let happy = score_sentiment("I am happy");
+        let very_happy = score_sentiment("I am very happy");
+        let slightly = score_sentiment("I am slightly happy");
+        let shouted = score_sentiment("I am happy!!!");
+        
+        assert!(very_happy.valence > happy.valence);
+        assert!(slightly.valence < happy.valence);
+        assert!(shouted.valence > happy.valence);
+        assert!(shouted.arousal > happy.arousal);
+        assert!(score_sentiment("heel erg boos").valence < score_sentiment("boos").valence);
+    }
+    
+    #[test]
+    fn test_arousal_separates_calm_from_angry() {
+        let calm = score_sentiment("peaceful and calm");
+        let furious = score_sentiment("furious and angry");
+        assert!(calm.arousal < 0.3);
+        assert!(furious.arousal > 0.8);
+    }
+}
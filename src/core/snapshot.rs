@@ -3,21 +3,41 @@
 //! Key invariant: Snapshot only created when Proof is generated
 //! This ensures 1-op-1 coupling between proofs and snapshots
 
+use std::sync::Arc;
 use sha2::{Sha256, Digest};
+use crate::core::balance::{turn_balance, DOMINANCE_THRESHOLD};
+use crate::core::timing::gaps_between;
+use crate::core::conflict::{conflict_avoidance, CONFLICT_THRESHOLD};
+use crate::core::{RParser, RScorer};
+use crate::core::timing::timing_stats;
 use crate::types::{
     Turn, ConversationWindow,
     Proof, Snapshot, SeenContent, BlindSpot, BlindSpotCategory,
-    HorizonItem, SnapshotResult, SnapshotReason,
+    HorizonItem, SnapshotResult, SnapshotReason, ValidationReport, ValidationFailure,
 };
 
 /// Snapshot generator
-#[derive(Debug, Default)]
-pub struct SnapshotGenerator;
+#[derive(Debug)]
+pub struct SnapshotGenerator {
+    /// r model used to find judgment spikes
+    r_scorer: Arc<dyn RScorer>,
+}
+
+impl Default for SnapshotGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl SnapshotGenerator {
-    /// Create new generator
+    /// Create new generator (default r parser)
     pub fn new() -> Self {
-        Self
+        Self::with_scorer(Arc::new(RParser::new()))
+    }
+    
+    /// Create a generator that reads r with the session's scorer
+    pub fn with_scorer(r_scorer: Arc<dyn RScorer>) -> Self {
+        Self { r_scorer }
     }
     
     /// Generate snapshot from a proof and conversation window
@@ -70,6 +90,7 @@ impl SnapshotGenerator {
             horizon,
             observers,
             turn_count: turns.len() as u32,
+            timing: timing_stats(window),
         };
         
         SnapshotResult::success(snapshot)
@@ -217,6 +238,26 @@ impl SnapshotGenerator {
             });
         }
         
+        // Check for disagreement that was raised and then sidestepped
+        let conflict = conflict_avoidance(turns, self.r_scorer.as_ref());
+        if conflict.confidence() >= CONFLICT_THRESHOLD {
+            let mut cues = Vec::new();
+            if conflict.topic_escapes > 0 {
+                cues.push(format!("{} met with a change of topic", plural(conflict.topic_escapes, "disagreement")));
+            }
+            if conflict.hedged_challenges > 0 {
+                cues.push(format!("{} hedged or left unanswered", plural(conflict.hedged_challenges, "challenge")));
+            }
+            if conflict.dropped_judgments > 0 {
+                cues.push(format!("{} dropped", plural(conflict.dropped_judgments, "sharp judgment")));
+            }
+            blind_spots.push(BlindSpot {
+                description: format!("Tension sidestepped: {}", cues.join(", ")),
+                category: BlindSpotCategory::ConflictAvoided,
+                confidence: conflict.confidence(),
+            });
+        }
+        
         // Check for uncertainty hidden
         let uncertainty_words = ["maybe", "perhaps", "not sure", "uncertain", "doubt",
                                 "misschien", "wellicht", "weet niet", "onzeker", "twijfel"];
@@ -304,6 +345,24 @@ impl SnapshotGenerator {
             });
         }
         
+        // Check for one speaker dominating the turn-taking
+        let speaker_turns: Vec<(&str, &str)> = turns.iter()
+            .map(|t| (t.speaker.as_str(), t.text.as_str()))
+            .collect();
+        let timestamps: Vec<_> = turns.iter().map(|t| t.timestamp).collect();
+        let balance = turn_balance(&speaker_turns, &gaps_between(&timestamps));
+        if balance.imbalance() >= DOMINANCE_THRESHOLD {
+            blind_spots.push(BlindSpot {
+                description: format!(
+                    "{} did most of the talking ({:.0}% of words)",
+                    balance.dominant.as_deref().unwrap_or("One speaker"),
+                    balance.dominant_share * 100.0
+                ),
+                category: BlindSpotCategory::OneVoiceDominant,
+                confidence: balance.imbalance(),
+            });
+        }
+        
         blind_spots
     }
     
@@ -329,6 +388,7 @@ impl SnapshotGenerator {
                 BlindSpotCategory::HighAbstraction => Some("Ask: What's a concrete example?"),
                 BlindSpotCategory::NoSensoryDetail => Some("Ask: What do you see, hear, feel?"),
                 BlindSpotCategory::NoMetaAwareness => Some("Notice: What is aware of this conversation?"),
+                BlindSpotCategory::OneVoiceDominant => Some("Ask the quieter speaker: What do you see?"),
             };
             
             horizon.push(HorizonItem {
@@ -351,6 +411,15 @@ impl SnapshotGenerator {
     }
 }
 
+/// "1 challenge", "2 challenges"
+pub fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
 /// Save snapshot to JSON file
 pub fn save_snapshot(snapshot: &Snapshot, dir: &str) -> Result<String, SnapshotReason> {
     let filename = format!("{}/{}.json", dir, snapshot.id);
@@ -398,6 +467,18 @@ pub fn load_and_validate_snapshot(path: &str) -> Result<Snapshot, SnapshotReason
     Ok(snapshot)
 }
 
+/// Validate every snapshot file in a directory with `load_and_validate_snapshot`
+pub fn validate_dir(dir: &str) -> Result<ValidationReport, SnapshotReason> {
+    let mut report = ValidationReport::default();
+    for path in crate::core::store::json_files(dir)? {
+        match load_and_validate_snapshot(&path) {
+            Ok(_) => report.valid += 1,
+            Err(reason) => report.failures.push(ValidationFailure { path, reason }),
+        }
+    }
+    Ok(report)
+}
+
 /// Validate snapshot against a proof (checks hash matches)
 pub fn validate_snapshot_proof(snapshot: &Snapshot, proof: &Proof) -> bool {
     let expected_hash = sha256(&proof.to_bytes());
@@ -414,6 +495,30 @@ fn sha256(data: &[u8]) -> [u8; 32] {
     hash
 }
 
+/// Stored snapshot with the given themes and blind spots, for store and compaction tests
+#[cfg(test)]
+pub(crate) fn test_snapshot(id: &str, timestamp_unix: i64, themes: &[&str], spots: &[BlindSpotCategory]) -> Snapshot {
+    Snapshot {
+        id: id.to_string(),
+        timestamp_unix,
+        session_id: [1; 16],
+        proof_hash: [0xab; 32],
+        r_final: 0.1,
+        dc_final: 0.05,
+        lock_duration_secs: 10,
+        seen: SeenContent { themes: themes.iter().map(|t| t.to_string()).collect(), ..Default::default() },
+        blind_spots: spots.iter().map(|&category| BlindSpot {
+            description: category.description().to_string(),
+            category,
+            confidence: 0.5,
+        }).collect(),
+        horizon: Vec::new(),
+        observers: vec!["A".to_string(), "B".to_string()],
+        turn_count: 6,
+        timing: None,
+    }
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -512,6 +617,85 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_dominant_speaker_blind_spot() {
+        let gen = SnapshotGenerator::new();
+        let proof = make_mock_proof();
+        
+        let mut window = ConversationWindow::new();
+        for _ in 0..3 {
+            window.add_turn(Turn::new("A", "I keep going over the plans for the new house and the garden and the move", 0.05));
+            window.add_turn(Turn::new("B", "Mm", 0.05));
+        }
+        let snapshot = gen.generate(&proof, &window, vec![]).snapshot.unwrap();
+        let spot = snapshot.blind_spots.iter()
+            .find(|bs| bs.category == BlindSpotCategory::OneVoiceDominant)
+            .expect("A dominates");
+        assert!(spot.description.starts_with("A did most of the talking"));
+        
+        // An even exchange has no such blind spot
+        let snapshot = gen.generate(&proof, &make_window(), vec![]).snapshot.unwrap();
+        assert!(snapshot.blind_spots.iter().all(|bs| bs.category != BlindSpotCategory::OneVoiceDominant));
+    }
+    
+    #[test]
+    fn test_conflict_avoided_blind_spot() {
+        let gen = SnapshotGenerator::new();
+        let proof = make_mock_proof();
+        
+        let mut window = ConversationWindow::new();
+        window.add_turn(Turn::new("A", "I don't agree with how you handled the budget meeting.", 0.12));
+        window.add_turn(Turn::new("B", "Anyway, did you see the football match yesterday?", 0.05));
+        window.add_turn(Turn::new("A", "You always skip the review, why?", 0.14));
+        window.add_turn(Turn::new("B", "Whatever.", 0.04));
+        let snapshot = gen.generate(&proof, &window, vec![]).snapshot.unwrap();
+        let spot = snapshot.blind_spots.iter()
+            .find(|bs| bs.category == BlindSpotCategory::ConflictAvoided)
+            .expect("conflict sidestepped");
+        assert_eq!(
+            spot.description,
+            "Tension sidestepped: 1 disagreement met with a change of topic, 1 challenge hedged or left unanswered"
+        );
+        assert!((spot.confidence - 0.89).abs() < 1e-9, "got {}", spot.confidence);
+        assert!(snapshot.horizon.iter().any(|h| h.potential_trigger.as_deref() == Some("Ask: What's the hard part?")));
+        
+        // The calm exchange has no such blind spot
+        let snapshot = gen.generate(&proof, &make_window(), vec![]).snapshot.unwrap();
+        assert!(snapshot.blind_spots.iter().all(|bs| bs.category != BlindSpotCategory::ConflictAvoided));
+    }
+    
+    #[test]
+    fn test_conflict_avoided_blind_spot_dutch() {
+        let gen = SnapshotGenerator::new();
+        let proof = make_mock_proof();
+        
+        let mut window = ConversationWindow::new();
+        window.add_turn(Turn::new("A", "Dat is niet eerlijk, jij hebt de boodschappen weer vergeten.", 0.18));
+        window.add_turn(Turn::new("B", "Trouwens, heb je het weerbericht voor morgen gezien?", 0.05));
+        window.add_turn(Turn::new("A", "Dat klopt niet, je doet altijd alsof het niets is.", 0.16));
+        window.add_turn(Turn::new("B", "Laat maar, dan gaan we fietsen.", 0.03));
+        let snapshot = gen.generate(&proof, &window, vec![]).snapshot.unwrap();
+        
+        let spot = snapshot.blind_spots.iter()
+            .find(|bs| bs.category == BlindSpotCategory::ConflictAvoided)
+            .expect("conflict sidestepped");
+        assert!(spot.description.contains("1 disagreement met with a change of topic"));
+        assert!(spot.confidence >= CONFLICT_THRESHOLD);
+    }
+    
+    #[test]
+    fn test_snapshot_reports_timing() {
+        let gen = SnapshotGenerator::new();
+        let proof = make_mock_proof();
+        let snapshot = gen.generate(&proof, &make_window(), vec![]).snapshot.unwrap();
+        
+        let timing = snapshot.timing.as_ref().expect("turns have timestamps");
+        assert_eq!(timing.gaps, snapshot.turn_count as usize - 1);
+        
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(json.contains("mean_latency_ms"));
+    }
+    
     #[test]
     fn test_horizon_generation() {
         let gen = SnapshotGenerator::new();
@@ -574,4 +758,28 @@ mod tests {
         // Proof hash should not be all zeros
         assert_ne!(snapshot.proof_hash, [0u8; 32], "Proof hash should be computed");
     }
+    
+    #[test]
+    fn test_validate_dir_reports_broken_links() {
+        let dir = std::env::temp_dir().join("soul0_validate_dir");
+        let _ = std::fs::remove_dir_all(&dir);
+        let dir = dir.to_str().unwrap();
+        
+        let snapshot = SnapshotGenerator::new().generate(&make_mock_proof(), &make_window(), vec![]).snapshot.unwrap();
+        save_snapshot(&snapshot, dir).unwrap();
+        let tampered = Snapshot { id: "tampered".to_string(), proof_hash: [0; 32], ..snapshot };
+        save_snapshot(&tampered, dir).unwrap();
+        std::fs::write(format!("{}/truncated.json", dir), "{\"id\":").unwrap();
+        
+        let report = validate_dir(dir).unwrap();
+        assert_eq!(report.valid, 1);
+        let failures: Vec<(&str, SnapshotReason)> = report.failures.iter()
+            .map(|f| (f.path.rsplit('/').next().unwrap(), f.reason))
+            .collect();
+        assert_eq!(failures, [
+            ("tampered.json", SnapshotReason::R305_SNAPSHOT_INVALID_PROOF_LINK),
+            ("truncated.json", SnapshotReason::R303_SNAPSHOT_SERIALIZE_ERROR),
+        ]);
+        assert_eq!(report.proof_link_failures(), 1);
+    }
 }
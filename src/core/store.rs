@@ -0,0 +1,642 @@
+//! Snapshot storage: a directory of JSON files or an embedded SQLite index
+//!
+//! Sessions, the API and the CLI save and look up snapshots through
+//! `SnapshotStore`. `DirStore` is the original layout (`<dir>/<id>.json`)
+//! and answers queries by scanning the directory. `SqliteStore` keeps the
+//! same JSON in one table and indexes session, time, observers, themes and
+//! blind-spot categories, so queries don't read every snapshot; themes and
+//! keywords also go into an FTS5 table for `search`. Both also hold the
+//! compaction summaries that replace old snapshots (`compacted/` or a
+//! `summaries` table). `migrate_dir` copies a directory into any store.
+
+use std::fmt::Debug;
+use std::sync::Mutex;
+use rusqlite::{Connection, OptionalExtension, params, params_from_iter, types::Value};
+use crate::core::compaction::COMPACTED_DIR;
+use crate::core::snapshot::{save_snapshot, load_snapshot};
+use crate::types::{
+    Snapshot, SnapshotQuery, SnapshotReason, MigrationReport, BlindSpotCategory, SearchHit,
+    CompactionSummary, CompactionPeriod,
+};
+
+/// Saves snapshots and finds them again
+pub trait SnapshotStore: Debug + Send + Sync {
+    /// Store a snapshot (replacing one with the same ID); returns where it went
+    fn save(&self, snapshot: &Snapshot) -> Result<String, SnapshotReason>;
+    
+    /// Load a snapshot by ID
+    fn load(&self, id: &str) -> Result<Snapshot, SnapshotReason>;
+    
+    /// Snapshots matching the query, newest first
+    fn query(&self, query: &SnapshotQuery) -> Result<Vec<Snapshot>, SnapshotReason>;
+    
+    /// Full-text search over seen themes and keywords, best match first
+    ///
+    /// Every word of `text` is a prefix term; a snapshot matches if any term
+    /// starts a word of one of its themes or keywords. `query` narrows the
+    /// candidates and its `limit` caps the hits.
+    fn search(&self, text: &str, query: &SnapshotQuery) -> Result<Vec<SearchHit>, SnapshotReason> {
+        let candidates = self.query(&SnapshotQuery { limit: None, ..query.clone() })?;
+        Ok(rank_hits(candidates, &search_terms(text), query.limit))
+    }
+    
+    /// Number of snapshots matching the query (its `limit` aside)
+    fn count(&self, query: &SnapshotQuery) -> Result<usize, SnapshotReason> {
+        Ok(self.query(&SnapshotQuery { limit: None, ..query.clone() })?.len())
+    }
+    
+    /// Remove a snapshot (once a compaction summary has replaced it)
+    fn delete(&self, id: &str) -> Result<(), SnapshotReason>;
+    
+    /// Compaction summaries, oldest period first
+    fn summaries(&self) -> Result<Vec<CompactionSummary>, SnapshotReason>;
+    
+    /// Store a summary (replacing one of the same period and start); returns where it went
+    fn save_summary(&self, summary: &CompactionSummary) -> Result<String, SnapshotReason>;
+    
+    /// Remove the summary of a period (once a coarser one has absorbed it)
+    fn delete_summary(&self, period: CompactionPeriod, period_start: i64) -> Result<(), SnapshotReason>;
+    
+    /// Directory or database path, for messages
+    fn location(&self) -> &str;
+}
+
+/// Snapshots as `<dir>/<id>.json` files
+#[derive(Debug, Clone)]
+pub struct DirStore {
+    dir: String,
+}
+
+impl DirStore {
+    /// Store in `dir` (created on the first save)
+    pub fn new(dir: impl Into<String>) -> Self {
+        Self { dir: dir.into() }
+    }
+    
+    /// Path of the snapshot file with this ID (None for IDs that leave the directory)
+    fn snapshot_path(&self, id: &str) -> Option<String> {
+        if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+            return None;
+        }
+        Some(format!("{}/{}.json", self.dir, id))
+    }
+    
+    /// "<dir>/compacted/weekly_2024-06-10.json"
+    fn summary_path(&self, period: CompactionPeriod, period_start: i64) -> String {
+        let date = chrono::DateTime::from_timestamp(period_start, 0).unwrap_or_default().format("%Y-%m-%d");
+        format!("{}/{}/{}_{}.json", self.dir, COMPACTED_DIR, period, date)
+    }
+}
+
+impl SnapshotStore for DirStore {
+    fn save(&self, snapshot: &Snapshot) -> Result<String, SnapshotReason> {
+        save_snapshot(snapshot, &self.dir)
+    }
+    
+    fn load(&self, id: &str) -> Result<Snapshot, SnapshotReason> {
+        let path = self.snapshot_path(id)
+            .filter(|path| std::path::Path::new(path).is_file())
+            .ok_or(SnapshotReason::R306_SNAPSHOT_NOT_FOUND)?;
+        load_snapshot(&path)
+    }
+    
+    fn query(&self, query: &SnapshotQuery) -> Result<Vec<Snapshot>, SnapshotReason> {
+        let mut found: Vec<Snapshot> = scan_dir(&self.dir)?.files.into_iter()
+            .map(|(snapshot, _)| snapshot)
+            .filter(|s| query.matches(s))
+            .collect();
+        found.sort_by(|a, b| b.timestamp_unix.cmp(&a.timestamp_unix).then_with(|| b.id.cmp(&a.id)));
+        if let Some(limit) = query.limit {
+            found.truncate(limit);
+        }
+        Ok(found)
+    }
+    
+    fn delete(&self, id: &str) -> Result<(), SnapshotReason> {
+        let path = self.snapshot_path(id).ok_or(SnapshotReason::R306_SNAPSHOT_NOT_FOUND)?;
+        remove_file(&path)
+    }
+    
+    /// Summary files that don't parse are left out (and left in place)
+    fn summaries(&self) -> Result<Vec<CompactionSummary>, SnapshotReason> {
+        let mut summaries: Vec<CompactionSummary> = json_files(&format!("{}/{}", self.dir, COMPACTED_DIR))?
+            .into_iter()
+            .filter_map(|path| serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok())
+            .collect();
+        summaries.sort_by_key(|s| (s.period_start, s.period as u8));
+        Ok(summaries)
+    }
+    
+    fn save_summary(&self, summary: &CompactionSummary) -> Result<String, SnapshotReason> {
+        let path = self.summary_path(summary.period, summary.period_start);
+        let json = serde_json::to_string_pretty(summary).map_err(|_| SnapshotReason::R303_SNAPSHOT_SERIALIZE_ERROR)?;
+        std::fs::create_dir_all(format!("{}/{}", self.dir, COMPACTED_DIR))
+            .map_err(|_| SnapshotReason::R304_SNAPSHOT_STORAGE_ERROR)?;
+        std::fs::write(&path, json).map_err(|_| SnapshotReason::R304_SNAPSHOT_STORAGE_ERROR)?;
+        Ok(path)
+    }
+    
+    fn delete_summary(&self, period: CompactionPeriod, period_start: i64) -> Result<(), SnapshotReason> {
+        remove_file(&self.summary_path(period, period_start))
+    }
+    
+    fn location(&self) -> &str {
+        &self.dir
+    }
+}
+
+/// Remove a file; R306 if it is not there
+fn remove_file(path: &str) -> Result<(), SnapshotReason> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(SnapshotReason::R306_SNAPSHOT_NOT_FOUND),
+        Err(_) => Err(SnapshotReason::R304_SNAPSHOT_STORAGE_ERROR),
+    }
+}
+
+/// Lowercase words of a search text, without duplicates
+fn search_terms(text: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for word in words(text) {
+        if !terms.contains(&word) {
+            terms.push(word);
+        }
+    }
+    terms
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Score the candidates against the terms (best first, then newest) and keep the hits
+fn rank_hits(candidates: Vec<Snapshot>, terms: &[String], limit: Option<usize>) -> Vec<SearchHit> {
+    let mut hits: Vec<SearchHit> = candidates.into_iter()
+        .filter_map(|snapshot| {
+            let mut matched = Vec::new();
+            let mut found = vec![false; terms.len()];
+            for field in snapshot.seen.themes.iter().chain(&snapshot.seen.keywords) {
+                let mut hit = false;
+                for word in words(field) {
+                    for (i, term) in terms.iter().enumerate() {
+                        if word.starts_with(term.as_str()) {
+                            found[i] = true;
+                            hit = true;
+                        }
+                    }
+                }
+                if hit && !matched.contains(field) {
+                    matched.push(field.clone());
+                }
+            }
+            let score = found.iter().filter(|&&f| f).count();
+            (score > 0).then_some(SearchHit { score, matched, snapshot })
+        })
+        .collect();
+    hits.sort_by(|a, b| {
+        b.score.cmp(&a.score)
+            .then_with(|| b.snapshot.timestamp_unix.cmp(&a.snapshot.timestamp_unix))
+            .then_with(|| b.snapshot.id.cmp(&a.snapshot.id))
+    });
+    if let Some(limit) = limit {
+        hits.truncate(limit);
+    }
+    hits
+}
+
+/// The snapshot files directly in a directory
+#[derive(Debug, Default)]
+pub(crate) struct DirScan {
+    /// Snapshots with their paths, sorted by path
+    pub files: Vec<(Snapshot, String)>,
+    /// `.json` files that did not parse as snapshots
+    pub skipped: Vec<String>,
+}
+
+/// Read every snapshot file in `dir`
+pub(crate) fn scan_dir(dir: &str) -> Result<DirScan, SnapshotReason> {
+    let mut scan = DirScan::default();
+    for path in json_files(dir)? {
+        match load_snapshot(&path) {
+            Ok(snapshot) => scan.files.push((snapshot, path)),
+            Err(_) => scan.skipped.push(path),
+        }
+    }
+    Ok(scan)
+}
+
+/// Paths of the `.json` files directly in `dir`, sorted
+///
+/// A missing directory is empty. Subdirectories such as `compacted/` are not read.
+pub(crate) fn json_files(dir: &str) -> Result<Vec<String>, SnapshotReason> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(_) => return Err(SnapshotReason::R304_SNAPSHOT_STORAGE_ERROR),
+    };
+    let mut paths: Vec<String> = entries.flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "json"))
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS snapshots (
+        id TEXT PRIMARY KEY,
+        timestamp_unix INTEGER NOT NULL,
+        session_id TEXT NOT NULL,
+        body TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS snapshots_time ON snapshots (timestamp_unix);
+    CREATE INDEX IF NOT EXISTS snapshots_session ON snapshots (session_id);
+    CREATE TABLE IF NOT EXISTS snapshot_observers (snapshot_id TEXT NOT NULL, observer TEXT NOT NULL);
+    CREATE INDEX IF NOT EXISTS snapshot_observers_name ON snapshot_observers (observer, snapshot_id);
+    CREATE TABLE IF NOT EXISTS snapshot_themes (snapshot_id TEXT NOT NULL, theme TEXT NOT NULL);
+    CREATE INDEX IF NOT EXISTS snapshot_themes_name ON snapshot_themes (theme, snapshot_id);
+    CREATE TABLE IF NOT EXISTS snapshot_blind_spots (snapshot_id TEXT NOT NULL, category TEXT NOT NULL);
+    CREATE INDEX IF NOT EXISTS snapshot_blind_spots_name ON snapshot_blind_spots (category, snapshot_id);
+    CREATE TABLE IF NOT EXISTS summaries (
+        period TEXT NOT NULL,
+        period_start INTEGER NOT NULL,
+        body TEXT NOT NULL,
+        PRIMARY KEY (period, period_start)
+    );
+    CREATE VIRTUAL TABLE IF NOT EXISTS snapshot_text
+        USING fts5(snapshot_id UNINDEXED, terms, tokenize = 'unicode61 remove_diacritics 0');
+    INSERT INTO snapshot_text (snapshot_id, terms)
+        SELECT id, concat_ws(' ',
+            (SELECT group_concat(value, ' ') FROM json_each(body, '$.seen.themes')),
+            (SELECT group_concat(value, ' ') FROM json_each(body, '$.seen.keywords')))
+        FROM snapshots WHERE id NOT IN (SELECT snapshot_id FROM snapshot_text);
+";
+
+/// Side tables that index one snapshot
+const INDEX_TABLES: [&str; 4] = ["snapshot_observers", "snapshot_themes", "snapshot_blind_spots", "snapshot_text"];
+
+/// Snapshots in an embedded SQLite database
+#[derive(Debug)]
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+    path: String,
+}
+
+impl SqliteStore {
+    /// Open (or create) the database at `path`; ":memory:" keeps it in memory
+    pub fn open(path: &str) -> Result<Self, SnapshotReason> {
+        if let Some(dir) = std::path::Path::new(path).parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|_| SnapshotReason::R304_SNAPSHOT_STORAGE_ERROR)?;
+        }
+        let conn = Connection::open(path).map_err(storage_error)?;
+        conn.execute_batch(SCHEMA).map_err(storage_error)?;
+        Ok(Self { conn: Mutex::new(conn), path: path.to_string() })
+    }
+    
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl SnapshotStore for SqliteStore {
+    fn save(&self, snapshot: &Snapshot) -> Result<String, SnapshotReason> {
+        let body = serde_json::to_string(snapshot).map_err(|_| SnapshotReason::R303_SNAPSHOT_SERIALIZE_ERROR)?;
+        let mut conn = self.conn();
+        let tx = conn.transaction().map_err(storage_error)?;
+        tx.execute(
+            "INSERT OR REPLACE INTO snapshots (id, timestamp_unix, session_id, body) VALUES (?1, ?2, ?3, ?4)",
+            params![snapshot.id, snapshot.timestamp_unix, snapshot.session_hex(), body],
+        ).map_err(storage_error)?;
+        for table in INDEX_TABLES {
+            tx.execute(&format!("DELETE FROM {} WHERE snapshot_id = ?1", table), [&snapshot.id]).map_err(storage_error)?;
+        }
+        for observer in &snapshot.observers {
+            tx.execute("INSERT INTO snapshot_observers VALUES (?1, ?2)", params![snapshot.id, observer])
+                .map_err(storage_error)?;
+        }
+        for theme in &snapshot.seen.themes {
+            tx.execute("INSERT INTO snapshot_themes VALUES (?1, ?2)", params![snapshot.id, theme.to_lowercase()])
+                .map_err(storage_error)?;
+        }
+        for spot in &snapshot.blind_spots {
+            tx.execute("INSERT INTO snapshot_blind_spots VALUES (?1, ?2)", params![snapshot.id, category_key(spot.category)])
+                .map_err(storage_error)?;
+        }
+        let terms: Vec<&str> = snapshot.seen.themes.iter().chain(&snapshot.seen.keywords).map(String::as_str).collect();
+        tx.execute("INSERT INTO snapshot_text VALUES (?1, ?2)", params![snapshot.id, terms.join(" ")])
+            .map_err(storage_error)?;
+        tx.commit().map_err(storage_error)?;
+        Ok(format!("{}#{}", self.path, snapshot.id))
+    }
+    
+    fn load(&self, id: &str) -> Result<Snapshot, SnapshotReason> {
+        let body: Option<String> = self.conn()
+            .query_row("SELECT body FROM snapshots WHERE id = ?1", [id], |row| row.get(0))
+            .optional()
+            .map_err(storage_error)?;
+        let body = body.ok_or(SnapshotReason::R306_SNAPSHOT_NOT_FOUND)?;
+        serde_json::from_str(&body).map_err(|_| SnapshotReason::R303_SNAPSHOT_SERIALIZE_ERROR)
+    }
+    
+    fn query(&self, query: &SnapshotQuery) -> Result<Vec<Snapshot>, SnapshotReason> {
+        self.select(query, None)
+    }
+    
+    fn count(&self, query: &SnapshotQuery) -> Result<usize, SnapshotReason> {
+        let (filter, values) = filter(query, None);
+        let count: i64 = self.conn()
+            .query_row(&format!("SELECT COUNT(*) FROM snapshots{}", filter), params_from_iter(values), |row| row.get(0))
+            .map_err(storage_error)?;
+        Ok(count as usize)
+    }
+    
+    fn delete(&self, id: &str) -> Result<(), SnapshotReason> {
+        let mut conn = self.conn();
+        let tx = conn.transaction().map_err(storage_error)?;
+        if tx.execute("DELETE FROM snapshots WHERE id = ?1", [id]).map_err(storage_error)? == 0 {
+            return Err(SnapshotReason::R306_SNAPSHOT_NOT_FOUND);
+        }
+        for table in INDEX_TABLES {
+            tx.execute(&format!("DELETE FROM {} WHERE snapshot_id = ?1", table), [id]).map_err(storage_error)?;
+        }
+        tx.commit().map_err(storage_error)
+    }
+    
+    fn summaries(&self) -> Result<Vec<CompactionSummary>, SnapshotReason> {
+        let conn = self.conn();
+        let mut statement = conn
+            .prepare("SELECT body FROM summaries ORDER BY period_start, period")
+            .map_err(storage_error)?;
+        let bodies = statement.query_map([], |row| row.get::<_, String>(0)).map_err(storage_error)?;
+        let mut summaries = bodies
+            .map(|body| {
+                let body = body.map_err(storage_error)?;
+                serde_json::from_str(&body).map_err(|_| SnapshotReason::R303_SNAPSHOT_SERIALIZE_ERROR)
+            })
+            .collect::<Result<Vec<CompactionSummary>, _>>()?;
+        summaries.sort_by_key(|s| (s.period_start, s.period as u8));
+        Ok(summaries)
+    }
+    
+    fn save_summary(&self, summary: &CompactionSummary) -> Result<String, SnapshotReason> {
+        let body = serde_json::to_string(summary).map_err(|_| SnapshotReason::R303_SNAPSHOT_SERIALIZE_ERROR)?;
+        self.conn().execute(
+            "INSERT OR REPLACE INTO summaries (period, period_start, body) VALUES (?1, ?2, ?3)",
+            params![summary.period.to_string(), summary.period_start, body],
+        ).map_err(storage_error)?;
+        Ok(format!("{}#{}_{}", self.path, summary.period, summary.period_start))
+    }
+    
+    fn delete_summary(&self, period: CompactionPeriod, period_start: i64) -> Result<(), SnapshotReason> {
+        let deleted = self.conn()
+            .execute("DELETE FROM summaries WHERE period = ?1 AND period_start = ?2", params![period.to_string(), period_start])
+            .map_err(storage_error)?;
+        if deleted == 0 {
+            return Err(SnapshotReason::R306_SNAPSHOT_NOT_FOUND);
+        }
+        Ok(())
+    }
+    
+    fn search(&self, text: &str, query: &SnapshotQuery) -> Result<Vec<SearchHit>, SnapshotReason> {
+        let terms = search_terms(text);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        // FTS5 finds the candidates; ranking is shared with the other stores
+        let fts: Vec<String> = terms.iter().map(|t| format!("\"{}\"*", t)).collect();
+        let candidates = self.select(&SnapshotQuery { limit: None, ..query.clone() }, Some(fts.join(" OR ")))?;
+        Ok(rank_hits(candidates, &terms, query.limit))
+    }
+    
+    fn location(&self) -> &str {
+        &self.path
+    }
+}
+
+impl SqliteStore {
+    /// Snapshots passing the query filters and, if given, an FTS5 match on their terms
+    fn select(&self, query: &SnapshotQuery, text_match: Option<String>) -> Result<Vec<Snapshot>, SnapshotReason> {
+        let (filter, mut values) = filter(query, text_match);
+        let mut sql = format!("SELECT body FROM snapshots{} ORDER BY timestamp_unix DESC, id DESC", filter);
+        if let Some(limit) = query.limit {
+            sql.push_str(" LIMIT ?");
+            values.push(Value::Integer(limit.min(i64::MAX as usize) as i64));
+        }
+        
+        let conn = self.conn();
+        let mut statement = conn.prepare(&sql).map_err(storage_error)?;
+        let bodies = statement
+            .query_map(params_from_iter(values), |row| row.get::<_, String>(0))
+            .map_err(storage_error)?;
+        bodies
+            .map(|body| {
+                let body = body.map_err(storage_error)?;
+                serde_json::from_str(&body).map_err(|_| SnapshotReason::R303_SNAPSHOT_SERIALIZE_ERROR)
+            })
+            .collect()
+    }
+}
+
+/// WHERE clause (empty without filters) and its values for the query filters
+/// and, if given, an FTS5 match on the snapshot terms
+fn filter(query: &SnapshotQuery, text_match: Option<String>) -> (String, Vec<Value>) {
+    let mut clauses: Vec<&str> = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+    if let Some(text_match) = text_match {
+        clauses.push("id IN (SELECT snapshot_id FROM snapshot_text WHERE snapshot_text MATCH ?)");
+        values.push(Value::Text(text_match));
+    }
+    if let Some(ref session) = query.session {
+        clauses.push("session_id = ?");
+        values.push(Value::Text(session.to_lowercase()));
+    }
+    if let Some(ref observer) = query.observer {
+        clauses.push("id IN (SELECT snapshot_id FROM snapshot_observers WHERE observer = ?)");
+        values.push(Value::Text(observer.clone()));
+    }
+    if let Some(since) = query.since {
+        clauses.push("timestamp_unix >= ?");
+        values.push(Value::Integer(since));
+    }
+    if let Some(until) = query.until {
+        clauses.push("timestamp_unix < ?");
+        values.push(Value::Integer(until));
+    }
+    if let Some(ref theme) = query.theme {
+        clauses.push("id IN (SELECT snapshot_id FROM snapshot_themes WHERE theme = ?)");
+        values.push(Value::Text(theme.to_lowercase()));
+    }
+    if let Some(category) = query.blind_spot {
+        clauses.push("id IN (SELECT snapshot_id FROM snapshot_blind_spots WHERE category = ?)");
+        values.push(Value::Text(category_key(category)));
+    }
+    
+    if clauses.is_empty() {
+        (String::new(), values)
+    } else {
+        (format!(" WHERE {}", clauses.join(" AND ")), values)
+    }
+}
+
+/// Category as stored in the index (its variant name)
+fn category_key(category: BlindSpotCategory) -> String {
+    format!("{:?}", category)
+}
+
+fn storage_error(_: rusqlite::Error) -> SnapshotReason {
+    SnapshotReason::R304_SNAPSHOT_STORAGE_ERROR
+}
+
+/// Copy every snapshot file in `dir` into `target`; the files are left in place
+pub fn migrate_dir(dir: &str, target: &dyn SnapshotStore) -> Result<MigrationReport, SnapshotReason> {
+    let scan = scan_dir(dir)?;
+    for (snapshot, _) in &scan.files {
+        target.save(snapshot)?;
+    }
+    Ok(MigrationReport { migrated: scan.files.len(), skipped: scan.skipped })
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::snapshot::test_snapshot;
+    
+    fn snapshot(id: &str, timestamp_unix: i64, session: u8, observers: &[&str], themes: &[&str], spots: &[BlindSpotCategory]) -> Snapshot {
+        Snapshot {
+            session_id: [session; 16],
+            observers: observers.iter().map(|o| o.to_string()).collect(),
+            ..test_snapshot(id, timestamp_unix, themes, spots)
+        }
+    }
+    
+    fn fill(store: &dyn SnapshotStore) {
+        use BlindSpotCategory::*;
+        for s in [
+            snapshot("s1", 1_000, 1, &["Anna", "Ben"], &["Garden", "work"], &[FutureAbsent]),
+            snapshot("s2", 2_000, 1, &["Anna", "Ben"], &["work"], &[ConflictAvoided, FutureAbsent]),
+            snapshot("s3", 3_000, 2, &["Anna", "Cas"], &["garden"], &[]),
+        ] {
+            store.save(&s).unwrap();
+        }
+    }
+    
+    fn ids(store: &dyn SnapshotStore, query: SnapshotQuery) -> Vec<String> {
+        store.query(&query).unwrap().into_iter().map(|s| s.id).collect()
+    }
+    
+    /// The same queries must give the same answers on every backend
+    fn check_queries(store: &dyn SnapshotStore) {
+        assert_eq!(ids(store, SnapshotQuery::default()), ["s3", "s2", "s1"]);
+        assert_eq!(ids(store, SnapshotQuery { session: Some("01".repeat(16)), ..Default::default() }), ["s2", "s1"]);
+        assert_eq!(ids(store, SnapshotQuery { observer: Some("Cas".into()), ..Default::default() }), ["s3"]);
+        assert_eq!(ids(store, SnapshotQuery { since: Some(2_000), until: Some(3_000), ..Default::default() }), ["s2"]);
+        assert_eq!(ids(store, SnapshotQuery { theme: Some("GARDEN".into()), ..Default::default() }), ["s3", "s1"]);
+        assert_eq!(ids(store, SnapshotQuery {
+            blind_spot: Some(BlindSpotCategory::FutureAbsent),
+            observer: Some("Ben".into()),
+            limit: Some(1),
+            ..Default::default()
+        }), ["s2"]);
+        
+        assert_eq!(store.load("s2").unwrap().blind_spots.len(), 2);
+        assert_eq!(store.load("missing").unwrap_err(), SnapshotReason::R306_SNAPSHOT_NOT_FOUND);
+    }
+    
+    fn check_search(store: &dyn SnapshotStore) {
+        let mut s4 = snapshot("s4", 4_000, 3, &["Ben"], &["family dinner"], &[]);
+        s4.seen.keywords = vec!["gardening".to_string(), "weekend".to_string()];
+        store.save(&s4).unwrap();
+        
+        let search = |text: &str, query: SnapshotQuery| -> Vec<(String, usize)> {
+            store.search(text, &query).unwrap().into_iter().map(|h| (h.snapshot.id, h.score)).collect()
+        };
+        // Prefix terms over themes and keywords; more terms found ranks first
+        assert_eq!(search("garden weekend", SnapshotQuery::default()), [
+            ("s4".to_string(), 2), ("s3".to_string(), 1), ("s1".to_string(), 1),
+        ]);
+        assert_eq!(search("DINNER", SnapshotQuery::default()), [("s4".to_string(), 1)]);
+        assert_eq!(search("garden", SnapshotQuery { observer: Some("Anna".into()), limit: Some(1), ..Default::default() }),
+            [("s3".to_string(), 1)]);
+        assert!(search("holiday", SnapshotQuery::default()).is_empty());
+        assert!(search("  ", SnapshotQuery::default()).is_empty());
+        
+        let hit = &store.search("garden", &SnapshotQuery::default()).unwrap()[0];
+        assert_eq!(hit.matched, ["gardening"]);
+    }
+    
+    fn check_delete(store: &dyn SnapshotStore) {
+        let before = store.count(&SnapshotQuery::default()).unwrap();
+        store.delete("s1").unwrap();
+        assert_eq!(store.count(&SnapshotQuery::default()).unwrap(), before - 1);
+        assert!(store.search("garden", &SnapshotQuery::default()).unwrap().iter().all(|h| h.snapshot.id != "s1"));
+        assert_eq!(store.delete("s1").unwrap_err(), SnapshotReason::R306_SNAPSHOT_NOT_FOUND);
+    }
+    
+    #[test]
+    fn test_sqlite_store_queries() {
+        let store = SqliteStore::open(":memory:").unwrap();
+        fill(&store);
+        check_queries(&store);
+        check_search(&store);
+        
+        // Saving again replaces the indexed fields too
+        store.save(&snapshot("s1", 1_000, 1, &["Anna"], &["travel"], &[])).unwrap();
+        assert_eq!(ids(&store, SnapshotQuery { theme: Some("garden".into()), ..Default::default() }), ["s3"]);
+        assert_eq!(ids(&store, SnapshotQuery::default()).len(), 4);
+        assert_eq!(store.count(&SnapshotQuery { theme: Some("work".into()), ..Default::default() }).unwrap(), 1);
+        check_delete(&store);
+    }
+    
+    #[test]
+    fn test_sqlite_search_index_is_backfilled() {
+        let path = std::env::temp_dir().join("soul0_store_backfill.db");
+        let _ = std::fs::remove_file(&path);
+        let path = path.to_str().unwrap();
+        let store = SqliteStore::open(path).unwrap();
+        fill(&store);
+        store.conn().execute("DELETE FROM snapshot_text", []).unwrap();
+        drop(store);
+        
+        let store = SqliteStore::open(path).unwrap();
+        let hits = store.search("work", &SnapshotQuery::default()).unwrap();
+        assert_eq!(hits.iter().map(|h| h.snapshot.id.as_str()).collect::<Vec<_>>(), ["s2", "s1"]);
+    }
+    
+    #[test]
+    fn test_dir_store_queries() {
+        let dir = std::env::temp_dir().join("soul0_store_dir");
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = DirStore::new(dir.to_str().unwrap());
+        fill(&store);
+        check_queries(&store);
+        check_search(&store);
+        assert_eq!(store.load("../s1").unwrap_err(), SnapshotReason::R306_SNAPSHOT_NOT_FOUND);
+        check_delete(&store);
+    }
+    
+    #[test]
+    fn test_migrate_dir_into_sqlite() {
+        let dir = std::env::temp_dir().join("soul0_store_migrate");
+        let _ = std::fs::remove_dir_all(&dir);
+        let dir = dir.to_str().unwrap();
+        fill(&DirStore::new(dir));
+        std::fs::write(format!("{}/notes.json", dir), "[]").unwrap();
+        
+        let db = SqliteStore::open(&format!("{}/index/snapshots.db", dir)).unwrap();
+        let report = migrate_dir(dir, &db).unwrap();
+        assert_eq!(report.migrated, 3);
+        assert_eq!(report.skipped, [format!("{}/notes.json", dir)]);
+        check_queries(&db);
+        
+        // Migrating twice does not duplicate anything
+        migrate_dir(dir, &db).unwrap();
+        assert_eq!(ids(&db, SnapshotQuery::default()).len(), 3);
+    }
+}
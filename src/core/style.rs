@@ -0,0 +1,196 @@
+//! Linguistic style matching (LSM) on function words, EN and NL
+//!
+//! Speakers who are in sync converge in how often they use pronouns,
+//! articles, prepositions and other function words, whatever the topic.
+//! Per category: LSM = 1 - |p1 - p2| / (p1 + p2 + 0.0001), with p the share
+//! of a speaker's words in that category; overall LSM is the category mean.
+//! A word counts in every category whose list contains it (EN and NL lists
+//! are merged, so "of" is an English preposition and a Dutch conjunction).
+
+use crate::types::{StyleMatch, StyleCategoryMatch};
+
+/// Function-word categories (name, EN + NL words)
+pub const STYLE_CATEGORIES: &[(&str, &[&str])] = &[
+    ("personal_pronouns", &[
+        "i", "me", "my", "mine", "myself", "you", "your", "yours", "yourself", "he", "him",
+        "his", "she", "her", "hers", "we", "us", "our", "ours", "they", "them", "their", "theirs",
+        "ik", "mij", "mijn", "jij", "je", "jou", "jouw", "u", "uw", "hij", "hem", "zij", "ze",
+        "haar", "wij", "ons", "onze", "jullie", "hun", "hen",
+    ]),
+    ("impersonal_pronouns", &[
+        "it", "its", "this", "that", "these", "those", "something", "anything", "nothing",
+        "everything", "someone", "anyone", "everyone", "somebody", "anybody", "nobody",
+        "dit", "dat", "deze", "die", "iets", "niets", "alles", "iemand", "niemand", "iedereen", "men",
+    ]),
+    ("articles", &["a", "an", "the", "de", "het", "een"]),
+    ("prepositions", &[
+        "in", "on", "at", "to", "from", "with", "about", "for", "of", "by", "into", "over",
+        "under", "after", "before", "between", "through", "during", "without", "around",
+        "op", "aan", "bij", "met", "van", "voor", "naar", "onder", "uit", "door", "na", "tot",
+        "tegen", "zonder", "tussen", "om", "rond",
+    ]),
+    ("auxiliary_verbs", &[
+        "am", "is", "are", "was", "were", "be", "been", "being", "have", "has", "had", "do",
+        "does", "did", "will", "would", "shall", "should", "can", "could", "may", "might", "must",
+        "ben", "bent", "zijn", "waren", "heb", "hebt", "heeft", "hebben", "hadden", "word",
+        "wordt", "worden", "werd", "zal", "zult", "zullen", "zou", "zouden", "kan", "kun", "kunt",
+        "kunnen", "kon", "moet", "moeten", "mag", "mogen", "wil", "wilt", "willen",
+    ]),
+    ("conjunctions", &[
+        "and", "but", "or", "so", "because", "if", "while", "although", "though", "nor",
+        "yet", "since", "unless", "en", "maar", "want", "dus", "omdat", "als", "terwijl",
+        "hoewel", "doordat", "zodat", "toen",
+    ]),
+    ("adverbs", &[
+        "very", "really", "just", "too", "also", "quite", "always", "often", "here", "there",
+        "now", "then", "still", "even", "only", "almost", "heel", "erg", "echt", "gewoon", "ook",
+        "nog", "al", "toch", "wel", "nu", "hier", "daar", "dan", "soms", "vaak", "altijd",
+        "bijna", "zelfs",
+    ]),
+    ("negations", &["no", "not", "never", "none", "niet", "nee", "geen", "nooit", "nergens"]),
+    ("quantifiers", &[
+        "all", "some", "many", "much", "few", "more", "most", "less", "lot", "every", "each",
+        "several", "any", "both", "enough", "alle", "allemaal", "sommige", "veel", "weinig",
+        "meer", "meest", "minder", "elk", "elke", "ieder", "iedere", "enkele", "beide", "genoeg",
+    ]),
+];
+
+/// Function-word counts of one or more texts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyleProfile {
+    /// Words per category, in `STYLE_CATEGORIES` order
+    counts: [usize; STYLE_CATEGORIES.len()],
+    /// All words
+    words: usize,
+}
+
+impl StyleProfile {
+    /// Profile of one text
+    pub fn from_text(text: &str) -> Self {
+        let mut profile = Self::default();
+        profile.add(text);
+        profile
+    }
+    
+    /// Add a text to the profile
+    pub fn add(&mut self, text: &str) {
+        for word in text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’')) {
+            let word = word.trim_matches(|c| c == '\'' || c == '’').replace('’', "'").to_lowercase();
+            if word.is_empty() {
+                continue;
+            }
+            self.words += 1;
+            
+            // "don't", "isn't": negation
+            let negated = word.ends_with("n't");
+            for (i, (name, list)) in STYLE_CATEGORIES.iter().enumerate() {
+                if list.contains(&word.as_str()) || (negated && *name == "negations") {
+                    self.counts[i] += 1;
+                }
+            }
+        }
+    }
+    
+    /// Merge another profile into this one
+    pub fn merge(&mut self, other: &StyleProfile) {
+        for (count, add) in self.counts.iter_mut().zip(other.counts) {
+            *count += add;
+        }
+        self.words += other.words;
+    }
+    
+    /// Take a profile merged before back out
+    pub fn unmerge(&mut self, other: &StyleProfile) {
+        for (count, sub) in self.counts.iter_mut().zip(other.counts) {
+            *count -= sub;
+        }
+        self.words -= other.words;
+    }
+    
+    /// Word count
+    pub fn words(&self) -> usize {
+        self.words
+    }
+    
+    /// Share of words in category `i`
+    fn rate(&self, i: usize) -> f64 {
+        if self.words == 0 {
+            0.0
+        } else {
+            self.counts[i] as f64 / self.words as f64
+        }
+    }
+}
+
+/// LSM of two profiles per category (None if either has no words)
+pub fn style_match(a: &StyleProfile, b: &StyleProfile) -> Option<StyleMatch> {
+    if a.words == 0 || b.words == 0 {
+        return None;
+    }
+    
+    let categories: Vec<StyleCategoryMatch> = STYLE_CATEGORIES.iter()
+        .enumerate()
+        .map(|(i, (name, _))| {
+            let (p1, p2) = (a.rate(i), b.rate(i));
+            StyleCategoryMatch {
+                category: name.to_string(),
+                lsm: 1.0 - (p1 - p2).abs() / (p1 + p2 + 0.0001),
+            }
+        })
+        .collect();
+    let lsm = categories.iter().map(|c| c.lsm).sum::<f64>() / categories.len() as f64;
+    
+    Some(StyleMatch { lsm, categories })
+}
+
+/// Overall LSM of two profiles (None if either has no words)
+pub fn lsm(a: &StyleProfile, b: &StyleProfile) -> Option<f64> {
+    style_match(a, b).map(|m| m.lsm)
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_profile_counts_categories() {
+        let profile = StyleProfile::from_text("I don't think the dog is in the garden");
+        assert_eq!(profile.words(), 9);
+        let rate = |name: &str| {
+            let i = STYLE_CATEGORIES.iter().position(|(n, _)| *n == name).unwrap();
+            profile.rate(i)
+        };
+        assert!((rate("articles") - 2.0 / 9.0).abs() < 1e-9);
+        assert!((rate("negations") - 1.0 / 9.0).abs() < 1e-9);
+        assert!((rate("personal_pronouns") - 1.0 / 9.0).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_same_style_different_topic_matches() {
+        let a = StyleProfile::from_text("I think the weather is nice and we should go to the beach");
+        let b = StyleProfile::from_text("I think the budget is fine and we should talk to the bank");
+        let c = StyleProfile::from_text("Budget fine. Bank talk. Deadline Friday, urgent!");
+        
+        let same_style = lsm(&a, &b).unwrap();
+        let other_style = lsm(&a, &c).unwrap();
+        assert!(same_style > 0.9, "got {}", same_style);
+        assert!(other_style < same_style);
+    }
+    
+    #[test]
+    fn test_dutch_and_empty() {
+        let a = StyleProfile::from_text("Ik denk dat het een mooie dag is en we kunnen naar het strand");
+        let b = StyleProfile::from_text("Ik vind dat het een goed plan is en we moeten naar de bank");
+        assert!(lsm(&a, &b).unwrap() > 0.8);
+        
+        assert!(lsm(&a, &StyleProfile::from_text("")).is_none());
+        
+        let mut merged = StyleProfile::from_text("I am");
+        merged.merge(&StyleProfile::from_text("you are"));
+        assert_eq!(merged.words(), 4);
+    }
+}
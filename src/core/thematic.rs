@@ -0,0 +1,484 @@
+//! Thematic model: TF-IDF term vectors for ΔC thematic drift
+//!
+//! Turns are reduced to stemmed content terms (EN/NL stopwords removed) and
+//! weighted by TF-IDF. Document frequencies come from the conversation window
+//! itself, or from a background corpus when one is supplied. Paraphrases that
+//! share content words ("sat on the mat" / "sitting on the mat!") stay close,
+//! where raw word-set overlap treats them as unrelated.
+
+use std::collections::{HashMap, HashSet};
+use lazy_static::lazy_static;
+
+pub use crate::types::ThematicModel;
+
+const EN_STOPWORDS: &[&str] = &[
+    "a", "about", "after", "again", "all", "also", "am", "an", "and", "any", "are", "as", "at",
+    "be", "been", "before", "being", "both", "but", "by", "can", "could", "did", "do", "does",
+    "doing", "don't", "down", "each", "few", "for", "from", "had", "has", "have", "having", "he",
+    "her", "here", "hers", "him", "his", "how", "i", "i'm", "if", "in", "into", "is", "it", "it's",
+    "its", "just", "me", "more", "most", "my", "no", "nor", "not", "now", "of", "off", "on",
+    "once", "only", "or", "other", "our", "ours", "out", "over", "own", "same", "she", "should",
+    "so", "some", "such", "than", "that", "the", "their", "them", "then", "there", "these",
+    "they", "this", "those", "through", "to", "too", "under", "until", "up", "very", "was", "we",
+    "were", "what", "when", "where", "which", "while", "who", "whom", "why", "will", "with",
+    "would", "yes", "you", "your", "yours",
+];
+
+const NL_STOPWORDS: &[&str] = &[
+    "aan", "al", "alles", "als", "bij", "dan", "dat", "de", "der", "deze", "die", "dit", "doch",
+    "doen", "door", "dus", "een", "en", "er", "ge", "geen", "had", "heb", "hebben", "heeft",
+    "hem", "het", "hier", "hij", "hoe", "hun", "iemand", "iets", "ik", "in", "is", "ja", "je",
+    "jij", "jou", "jouw", "kan", "kon", "kunnen", "maar", "me", "meer", "men", "met", "mij",
+    "mijn", "moet", "na", "naar", "nee", "niet", "niets", "nog", "nu", "of", "om", "omdat",
+    "ons", "onze", "ook", "op", "over", "te", "tegen", "toch", "toen", "tot", "u", "uit", "uw",
+    "van", "veel", "voor", "waren", "was", "wat", "we", "wel", "werd", "wie", "wij", "wil",
+    "worden", "wordt", "zal", "ze", "zei", "zelf", "zich", "zij", "zijn", "zo", "zou",
+];
+
+lazy_static! {
+    static ref STOPWORDS: HashSet<&'static str> =
+        EN_STOPWORDS.iter().chain(NL_STOPWORDS).copied().collect();
+}
+
+/// Is this lowercase word an EN/NL stopword?
+pub fn is_stopword(word: &str) -> bool {
+    STOPWORDS.contains(word)
+}
+
+/// Language whose suffix rules the stemmer applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Dutch,
+}
+
+impl Language {
+    /// Guess the language of a text from its stopwords (ties go to English)
+    pub fn detect(text: &str) -> Self {
+        let (mut en, mut nl) = (0usize, 0usize);
+        for word in text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’')) {
+            let lower = word.trim_matches(|c| c == '\'' || c == '’').to_lowercase();
+            match (EN_ONLY.contains(lower.as_str()), NL_ONLY.contains(lower.as_str())) {
+                (true, false) => en += 1,
+                (false, true) => nl += 1,
+                _ => {}
+            }
+        }
+        if nl > en { Language::Dutch } else { Language::English }
+    }
+}
+
+lazy_static! {
+    static ref EN_ONLY: HashSet<&'static str> =
+        EN_STOPWORDS.iter().filter(|w| !NL_STOPWORDS.contains(w)).copied().collect();
+    static ref NL_ONLY: HashSet<&'static str> =
+        NL_STOPWORDS.iter().filter(|w| !EN_STOPWORDS.contains(w)).copied().collect();
+}
+
+/// Lowercase content terms of a text: stopwords removed, stemmed
+pub fn content_terms(text: &str) -> Vec<String> {
+    let language = Language::detect(text);
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+        .map(|w| w.trim_matches(|c| c == '\'' || c == '’').to_lowercase())
+        .filter(|w| w.chars().count() > 1 && !STOPWORDS.contains(w.as_str()))
+        .map(|w| stem(&w, language))
+        .collect()
+}
+
+/// Light suffix-stripping stemmer for English and Dutch
+///
+/// Not a full Porter stemmer: it only needs to map singular and plural, and
+/// the common verb inflections, of one word to one term. Only the rules of
+/// `language` apply, so English "garden" keeps its "-en". Stems keep at least
+/// three characters, and "-ing"/"-ed" are only stripped from a stem that
+/// still has a vowel ("string" stays whole).
+pub fn stem(word: &str, language: Language) -> String {
+    if !word.is_ascii() || word.len() <= MIN_STEM + 1 {
+        return word.to_string();
+    }
+    let mut w = word.to_string();
+    
+    let undouble = match language {
+        Language::English => strip_english(&mut w),
+        Language::Dutch => strip_dutch(&mut w),
+    };
+    
+    if undouble {
+        let bytes = w.as_bytes();
+        let n = bytes.len();
+        if n > MIN_STEM
+            && bytes[n - 1] == bytes[n - 2]
+            && !matches!(bytes[n - 1], b'l' | b's' | b'z' | b'a' | b'e' | b'i' | b'o' | b'u')
+        {
+            w.truncate(n - 1);
+        }
+    }
+    
+    w
+}
+
+const MIN_STEM: usize = 3;
+
+/// Replace `suffix` when at least `MIN_STEM` characters remain
+fn strip(w: &mut String, suffix: &str, replacement: &str) -> bool {
+    if w.ends_with(suffix) && w.len() - suffix.len() >= MIN_STEM {
+        w.truncate(w.len() - suffix.len());
+        w.push_str(replacement);
+        true
+    } else {
+        false
+    }
+}
+
+/// `strip` for verb endings: the remaining stem must contain a vowel
+fn strip_verbal(w: &mut String, suffix: &str) -> bool {
+    w.ends_with(suffix)
+        && w[..w.len() - suffix.len()].contains(['a', 'e', 'i', 'o', 'u', 'y'])
+        && strip(w, suffix, "")
+}
+
+/// English inflections; returns whether a doubled consonant should go (running → run)
+fn strip_english(w: &mut String) -> bool {
+    if w.ends_with("eed") {
+        // agreed → agree, but speed and need stay whole
+        strip(w, "eed", "ee");
+        false
+    } else if strip(w, "sses", "ss") || strip(w, "ies", "y")
+        || ((w.ends_with("ches") || w.ends_with("shes") || w.ends_with("xes")) && strip(w, "es", ""))
+        || (w.len() >= 7 && strip(w, "oes", "o"))
+    {
+        // tomatoes → tomato, boxes → box; shoes keeps its "e"
+        false
+    } else if strip_verbal(w, "ingly") || strip_verbal(w, "edly")
+        || strip_verbal(w, "ing") || strip_verbal(w, "ed")
+    {
+        true
+    } else if strip(w, "ness", "") || strip(w, "ment", "") || strip(w, "ly", "") {
+        false
+    } else {
+        if w.ends_with('s') && !w.ends_with("ss") && !w.ends_with("us") && !w.ends_with("is") {
+            strip(w, "s", "");
+        }
+        false
+    }
+}
+
+/// Dutch derivations, diminutives and plurals; returns whether to undouble (katten → kat)
+fn strip_dutch(w: &mut String) -> bool {
+    if strip(w, "heden", "heid") || strip(w, "lijke", "lijk")
+        || strip(w, "tjes", "") || strip(w, "tje", "")
+    {
+        false
+    } else if w.len() >= 5 && strip(w, "en", "") {
+        // Plural / infinitive (werken → werk, katten → kat)
+        true
+    } else {
+        // Plural in -s (tafels → tafel), but not "-is"/"-us" words
+        if w.ends_with('s') && !w.ends_with("ss") && !w.ends_with("us") && !w.ends_with("is") {
+            strip(w, "s", "");
+        }
+        false
+    }
+}
+
+/// Document frequencies from a background corpus
+#[derive(Debug, Clone, Default)]
+pub struct IdfCorpus {
+    doc_freq: HashMap<String, usize>,
+    docs: usize,
+}
+
+impl IdfCorpus {
+    /// Build from documents (each document is one text)
+    pub fn from_documents<'a>(documents: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut corpus = Self::default();
+        for document in documents {
+            corpus.add_document(document);
+        }
+        corpus
+    }
+    
+    /// Add one document
+    pub fn add_document(&mut self, document: &str) {
+        self.add_terms(&content_terms(document));
+    }
+    
+    /// Add one document by its content terms
+    pub fn add_terms(&mut self, terms: &[String]) {
+        for term in terms.iter().collect::<HashSet<_>>() {
+            *self.doc_freq.entry(term.clone()).or_default() += 1;
+        }
+        self.docs += 1;
+    }
+    
+    /// Take out a document added with `add_terms`
+    pub fn remove_terms(&mut self, terms: &[String]) {
+        for term in terms.iter().collect::<HashSet<_>>() {
+            if let Some(df) = self.doc_freq.get_mut(term) {
+                *df -= 1;
+                if *df == 0 {
+                    self.doc_freq.remove(term);
+                }
+            }
+        }
+        self.docs = self.docs.saturating_sub(1);
+    }
+    
+    /// Number of documents
+    pub fn len(&self) -> usize {
+        self.docs
+    }
+    
+    /// Is the corpus empty?
+    pub fn is_empty(&self) -> bool {
+        self.docs == 0
+    }
+    
+    /// Smoothed inverse document frequency: ln((1 + N) / (1 + df)) + 1
+    pub fn idf(&self, term: &str) -> f64 {
+        let df = self.doc_freq.get(term).copied().unwrap_or(0);
+        ((1 + self.docs) as f64 / (1 + df) as f64).ln() + 1.0
+    }
+}
+
+/// Sparse TF-IDF vector
+pub type TermVector = HashMap<String, f64>;
+
+/// Build TF-IDF vectors for a list of term lists
+///
+/// IDF comes from `corpus` if given, otherwise from the documents themselves.
+pub fn tfidf_vectors<D: AsRef<[String]>>(documents: &[D], corpus: Option<&IdfCorpus>) -> Vec<TermVector> {
+    let window_corpus;
+    let corpus = match corpus {
+        Some(corpus) => corpus,
+        None => {
+            let mut built = IdfCorpus::default();
+            for terms in documents {
+                built.add_terms(terms.as_ref());
+            }
+            window_corpus = built;
+            &window_corpus
+        }
+    };
+    
+    documents.iter()
+        .map(|terms| tfidf_vector(terms.as_ref(), corpus))
+        .collect()
+}
+
+/// TF-IDF vector of one term list
+pub fn tfidf_vector(terms: &[String], corpus: &IdfCorpus) -> TermVector {
+    let mut vector = TermVector::new();
+    for term in terms {
+        *vector.entry(term.clone()).or_default() += 1.0;
+    }
+    for (term, weight) in vector.iter_mut() {
+        *weight *= corpus.idf(term);
+    }
+    vector
+}
+
+/// Cosine similarity of two sparse vectors (0.0 if either is empty)
+pub fn cosine(a: &TermVector, b: &TermVector) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let dot: f64 = small.iter()
+        .filter_map(|(term, w)| large.get(term).map(|v| w * v))
+        .sum();
+    let norm_a = a.values().map(|w| w * w).sum::<f64>().sqrt();
+    let norm_b = b.values().map(|w| w * w).sum::<f64>().sqrt();
+    
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        (dot / (norm_a * norm_b)).clamp(0.0, 1.0)
+    }
+}
+
+/// Sum of vectors (direction of the conversation so far)
+pub fn centroid<'a>(vectors: impl IntoIterator<Item = &'a TermVector>) -> TermVector {
+    let mut sum = TermVector::new();
+    for vector in vectors {
+        for (term, w) in vector {
+            *sum.entry(term.clone()).or_default() += w;
+        }
+    }
+    sum
+}
+
+/// `centroid` of a sliding window, kept up to date as vectors enter and leave
+///
+/// The squared norm is updated term by term, so adding, removing or comparing
+/// a vector costs as much as that vector, however many are in the sum.
+#[derive(Debug, Clone, Default)]
+pub struct RunningCentroid {
+    sum: TermVector,
+    /// Vectors in the sum that contain each term
+    vectors: HashMap<String, usize>,
+    norm_sq: f64,
+}
+
+impl RunningCentroid {
+    /// Add a vector to the sum
+    pub fn add(&mut self, vector: &TermVector) {
+        for (term, w) in vector {
+            *self.vectors.entry(term.clone()).or_default() += 1;
+            let sum = self.sum.entry(term.clone()).or_default();
+            self.norm_sq += (*sum + w).powi(2) - sum.powi(2);
+            *sum += w;
+        }
+    }
+    
+    /// Take a vector added before out of the sum
+    pub fn remove(&mut self, vector: &TermVector) {
+        for (term, w) in vector {
+            let Some(sum) = self.sum.get_mut(term) else {
+                continue;
+            };
+            let left = self.vectors.get_mut(term).map_or(0, |n| {
+                *n -= 1;
+                *n
+            });
+            if left == 0 {
+                self.norm_sq -= sum.powi(2);
+                self.sum.remove(term);
+                self.vectors.remove(term);
+            } else {
+                self.norm_sq += (*sum - w).powi(2) - sum.powi(2);
+                *sum -= w;
+            }
+        }
+        if self.sum.is_empty() {
+            self.norm_sq = 0.0;
+        }
+    }
+    
+    /// `cosine` of a vector with the sum
+    pub fn cosine(&self, vector: &TermVector) -> f64 {
+        let dot: f64 = vector.iter()
+            .filter_map(|(term, w)| self.sum.get(term).map(|v| w * v))
+            .sum();
+        let norm_a = vector.values().map(|w| w * w).sum::<f64>().sqrt();
+        let norm_b = self.norm_sq.max(0.0).sqrt();
+        
+        if norm_a == 0.0 || norm_b == 0.0 {
+            0.0
+        } else {
+            (dot / (norm_a * norm_b)).clamp(0.0, 1.0)
+        }
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_content_terms_strip_stopwords_and_punctuation() {
+        assert_eq!(content_terms("The cat sat on the mat."), vec!["cat", "sat", "mat"]);
+        assert_eq!(content_terms("A cat was sitting on the mat!"), vec!["cat", "sit", "mat"]);
+        assert_eq!(content_terms("De katten zaten op de mat"), vec!["kat", "zat", "mat"]);
+        assert!(content_terms("I am, and you are?").is_empty());
+    }
+    
+    #[test]
+    fn test_stem_inflections() {
+        use Language::{Dutch, English};
+        assert_eq!(stem("running", English), "run");
+        assert_eq!(stem("walked", English), "walk");
+        assert_eq!(stem("stories", English), "story");
+        assert_eq!(stem("trees", English), "tree");
+        assert_eq!(stem("glass", English), "glass");
+        assert_eq!(stem("werken", Dutch), "werk");
+        assert_eq!(stem("mogelijkheden", Dutch), "mogelijkheid");
+        assert_eq!(stem("huisje", Dutch), "huisje");
+        assert_eq!(stem("sky", English), "sky");
+    }
+    
+    #[test]
+    fn test_stem_singular_and_plural_agree() {
+        use Language::{Dutch, English};
+        let english = [
+            ("garden", "gardens"), ("token", "tokens"), ("speed", "speeds"),
+            ("kitchen", "kitchens"), ("string", "strings"), ("story", "stories"),
+            ("agree", "agreed"), ("tomato", "tomatoes"), ("box", "boxes"), ("shoe", "shoes"),
+            ("church", "churches"),
+        ];
+        for (singular, plural) in english {
+            assert_eq!(stem(singular, English), stem(plural, English), "{} / {}", singular, plural);
+        }
+        assert_eq!(stem("garden", English), "garden");
+        assert_eq!(stem("speed", English), "speed");
+        
+        for (singular, plural) in [("kat", "katten"), ("boek", "boeken"), ("tafel", "tafels")] {
+            assert_eq!(stem(singular, Dutch), stem(plural, Dutch), "{} / {}", singular, plural);
+        }
+    }
+    
+    #[test]
+    fn test_language_detection() {
+        assert_eq!(Language::detect("De katten zaten op de mat"), Language::Dutch);
+        assert_eq!(Language::detect("The gardens were lovely"), Language::English);
+        assert_eq!(Language::detect("gardens"), Language::English);
+    }
+    
+    #[test]
+    fn test_cosine_paraphrase_vs_unrelated() {
+        let docs = vec![
+            content_terms("The cat sat on the mat."),
+            content_terms("A cat was sitting on the mat!"),
+            content_terms("My car needs repairs"),
+        ];
+        let vectors = tfidf_vectors(&docs, None);
+        
+        assert!(cosine(&vectors[0], &vectors[1]) > 0.5);
+        assert_eq!(cosine(&vectors[0], &vectors[2]), 0.0);
+        assert!((cosine(&vectors[0], &vectors[0]) - 1.0).abs() < 1e-12);
+    }
+    
+    #[test]
+    fn test_background_corpus_downweights_common_terms() {
+        let corpus = IdfCorpus::from_documents([
+            "the weather today", "weather report", "weather is nice", "sailing boats",
+        ]);
+        assert_eq!(corpus.len(), 4);
+        assert!(corpus.idf("weather") < corpus.idf("sail"));
+        assert!(corpus.idf("unseen") > corpus.idf("sail"));
+        
+        // Sharing only a common word counts for less than sharing a rare one
+        let docs = vec![
+            content_terms("weather and sailing"),
+            content_terms("weather and boats"),
+            content_terms("sailing and boats"),
+        ];
+        let vectors = tfidf_vectors(&docs, Some(&corpus));
+        assert!(cosine(&vectors[0], &vectors[1]) < cosine(&vectors[0], &vectors[2]));
+    }
+    
+    #[test]
+    fn test_running_centroid_matches_centroid() {
+        let docs = vec![
+            content_terms("The garden needs new tomato plants"),
+            content_terms("Tomatoes need sun and water"),
+            content_terms("My car broke down again"),
+        ];
+        let vectors = tfidf_vectors(&docs, None);
+        
+        let mut running = RunningCentroid::default();
+        for vector in &vectors {
+            running.add(vector);
+        }
+        running.remove(&vectors[0]);
+        let rest = centroid(&vectors[1..]);
+        let probe = &vectors[0];
+        assert!((running.cosine(probe) - cosine(probe, &rest)).abs() < 1e-12);
+        
+        running.remove(&vectors[1]);
+        running.remove(&vectors[2]);
+        assert_eq!(running.cosine(probe), 0.0);
+    }
+}
@@ -0,0 +1,265 @@
+//! Turn timing: reply latency, its spread, and long silences
+//!
+//! Uses the turn timestamps the window already keeps for pruning. A reply
+//! latency is the gap before a turn by another speaker; a silence is any gap
+//! between consecutive turns longer than `LONG_SILENCE_MS`. Turns without a
+//! timestamp are skipped. Gaps are measured between logical turns, so they
+//! line up with `ConversationWindow::pair_indices`.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Instant;
+use crate::types::{ConversationWindow, TimingStats, SilenceEvent};
+
+/// Gap after which a pause counts as a long silence
+pub const LONG_SILENCE_MS: u64 = 10_000;
+
+/// Replies faster than this count as rapid-fire
+pub const RAPID_REPLY_MS: u64 = 1_000;
+
+/// Gap before each logical turn (None for the first turn or missing timestamps)
+pub fn turn_gaps(window: &ConversationWindow) -> Vec<Option<u64>> {
+    gaps_between(&window.logical_turns().iter().map(|t| t.timestamp).collect::<Vec<_>>())
+}
+
+/// Gap before each timestamp (None for the first one or when either is missing)
+pub fn gaps_between(timestamps: &[Option<Instant>]) -> Vec<Option<u64>> {
+    (0..timestamps.len())
+        .map(|i| gap_ms(*timestamps.get(i.checked_sub(1)?)?, timestamps[i]))
+        .collect()
+}
+
+/// Gap between two timestamps (None when either is missing)
+pub fn gap_ms(before: Option<Instant>, after: Option<Instant>) -> Option<u64> {
+    Some(after?.saturating_duration_since(before?).as_millis() as u64)
+}
+
+/// Timing statistics over the window (None if no reply has a timestamp)
+pub fn timing_stats(window: &ConversationWindow) -> Option<TimingStats> {
+    let gaps = turn_gaps(window);
+    let turns = window.logical_turns();
+    let speakers: Vec<&str> = turns.iter().map(|t| t.speaker.as_str()).collect();
+    
+    let latencies: Vec<u64> = (1..speakers.len())
+        .filter(|&i| speakers[i] != speakers[i - 1])
+        .filter_map(|i| gaps[i])
+        .collect();
+    if latencies.is_empty() {
+        return None;
+    }
+    
+    let n = latencies.len() as f64;
+    let mean = latencies.iter().sum::<u64>() as f64 / n;
+    let variance = latencies.iter()
+        .map(|&l| (l as f64 - mean).powi(2))
+        .sum::<f64>() / n;
+    
+    let long_silences: Vec<SilenceEvent> = gaps.iter()
+        .enumerate()
+        .filter_map(|(i, gap)| match gap {
+            Some(gap) if *gap >= LONG_SILENCE_MS => Some(SilenceEvent {
+                after_turn: i - 1,
+                broken_by: speakers[i].to_string(),
+                gap_ms: *gap,
+            }),
+            _ => None,
+        })
+        .collect();
+    
+    Some(TimingStats {
+        mean_latency_ms: mean,
+        latency_std_ms: variance.sqrt(),
+        max_gap_ms: gaps.iter().flatten().copied().max().unwrap_or(0),
+        gaps: gaps.iter().flatten().count(),
+        rapid_replies: latencies.iter().filter(|&&l| l < RAPID_REPLY_MS).count(),
+        long_silences,
+    })
+}
+
+/// `timing_stats` of a sliding window, kept up to date gap by gap
+///
+/// Each gap is added when the turn after it enters and removed when that
+/// turn or the one before it leaves, so the statistics of a long window
+/// cost no more than those of a short one. Turns are identified by ids
+/// that grow from the oldest turn to the newest.
+#[derive(Debug, Clone, Default)]
+pub struct TimingTracker {
+    /// Reply latencies: count, sum and sum of squares
+    replies: u64,
+    latency_sum: u128,
+    latency_sq_sum: u128,
+    rapid_replies: usize,
+    /// Every gap by length (for the longest)
+    gaps: BTreeMap<u64, usize>,
+    gap_count: usize,
+    /// Long silences, oldest first: (id of the turn that broke it, its speaker, gap)
+    silences: VecDeque<(u64, String, u64)>,
+}
+
+impl TimingTracker {
+    /// Add the gap before turn `turn` (`reply` if the speaker changed),
+    /// the newest or the second oldest turn
+    pub fn add(&mut self, turn: u64, speaker: &str, gap_ms: u64, reply: bool) {
+        if reply {
+            self.replies += 1;
+            self.latency_sum += gap_ms as u128;
+            self.latency_sq_sum += (gap_ms as u128).pow(2);
+            if gap_ms < RAPID_REPLY_MS {
+                self.rapid_replies += 1;
+            }
+        }
+        *self.gaps.entry(gap_ms).or_default() += 1;
+        self.gap_count += 1;
+        if gap_ms >= LONG_SILENCE_MS {
+            let silence = (turn, speaker.to_string(), gap_ms);
+            if self.silences.front().is_some_and(|s| s.0 > turn) {
+                self.silences.push_front(silence);
+            } else {
+                self.silences.push_back(silence);
+            }
+        }
+    }
+    
+    /// Remove a gap added before, of the oldest or the newest turn with one
+    pub fn remove(&mut self, turn: u64, gap_ms: u64, reply: bool) {
+        if reply {
+            self.replies -= 1;
+            self.latency_sum -= gap_ms as u128;
+            self.latency_sq_sum -= (gap_ms as u128).pow(2);
+            if gap_ms < RAPID_REPLY_MS {
+                self.rapid_replies -= 1;
+            }
+        }
+        if let Some(count) = self.gaps.get_mut(&gap_ms) {
+            *count -= 1;
+            if *count == 0 {
+                self.gaps.remove(&gap_ms);
+            }
+        }
+        self.gap_count -= 1;
+        if self.silences.front().is_some_and(|s| s.0 == turn) {
+            self.silences.pop_front();
+        } else if self.silences.back().is_some_and(|s| s.0 == turn) {
+            self.silences.pop_back();
+        }
+    }
+    
+    /// Reply latencies measured, and how many were rapid-fire
+    pub fn replies(&self) -> (usize, usize) {
+        (self.replies as usize, self.rapid_replies)
+    }
+    
+    /// Statistics as `timing_stats` gives them; `index_of` maps a turn id to
+    /// its window index (None if no reply has a timestamp)
+    pub fn stats(&self, index_of: impl Fn(u64) -> usize) -> Option<TimingStats> {
+        if self.replies == 0 {
+            return None;
+        }
+        
+        let n = self.replies as f64;
+        let mean = self.latency_sum as f64 / n;
+        // n·Σl² - (Σl)² is exact in integers and never negative
+        let spread = self.replies as u128 * self.latency_sq_sum - self.latency_sum.pow(2);
+        let variance = spread as f64 / (n * n);
+        
+        Some(TimingStats {
+            mean_latency_ms: mean,
+            latency_std_ms: variance.sqrt(),
+            max_gap_ms: self.gaps.keys().next_back().copied().unwrap_or(0),
+            gaps: self.gap_count,
+            rapid_replies: self.rapid_replies,
+            long_silences: self.silences.iter()
+                .map(|(turn, speaker, gap_ms)| SilenceEvent {
+                    after_turn: index_of(*turn) - 1,
+                    broken_by: speaker.clone(),
+                    gap_ms: *gap_ms,
+                })
+                .collect(),
+        })
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::types::Turn;
+    
+    /// Window with turns `secs_ago` seconds in the past
+    fn timed_window(turns: &[(&str, u64)]) -> ConversationWindow {
+        let now = Instant::now();
+        let mut window = ConversationWindow::with_duration(120);
+        for (speaker, secs_ago) in turns {
+            let at = now - Duration::from_secs(*secs_ago);
+            window.add_turn(Turn::new(*speaker, "hello there", 0.1).with_timestamp(at));
+        }
+        window
+    }
+    
+    #[test]
+    fn test_latency_and_spread() {
+        let window = timed_window(&[("A", 20), ("B", 18), ("A", 14), ("B", 12)]);
+        let stats = timing_stats(&window).unwrap();
+        
+        assert!((stats.mean_latency_ms - 2666.7).abs() < 1.0, "got {}", stats.mean_latency_ms);
+        assert!(stats.latency_std_ms > 900.0);
+        assert!(stats.long_silences.is_empty());
+        assert_eq!(stats.max_gap_ms, 4000);
+    }
+    
+    #[test]
+    fn test_long_silence_event() {
+        let window = timed_window(&[("A", 40), ("B", 38), ("A", 37), ("B", 15)]);
+        let stats = timing_stats(&window).unwrap();
+        
+        assert_eq!(stats.long_silences.len(), 1);
+        let silence = &stats.long_silences[0];
+        assert_eq!(silence.after_turn, 2);
+        assert_eq!(silence.broken_by, "B");
+        assert_eq!(silence.gap_ms, 22_000);
+        assert!(stats.irregularity() > 0.3);
+    }
+    
+    #[test]
+    fn test_rapid_fire_and_missing_timestamps() {
+        let window = timed_window(&[("A", 3), ("B", 3), ("A", 3)]);
+        let stats = timing_stats(&window).unwrap();
+        assert_eq!(stats.rapid_replies, 2);
+        assert_eq!(stats.irregularity(), 0.0);
+        
+        let mut window = ConversationWindow::new();
+        let mut turn = Turn::new("A", "hi", 0.1);
+        turn.timestamp = None;
+        window.add_turn(turn);
+        assert!(timing_stats(&window).is_none());
+    }
+    
+    #[test]
+    fn test_tracker_matches_timing_stats() {
+        let window = timed_window(&[("A", 60), ("B", 58), ("A", 57), ("B", 35), ("B", 34), ("A", 30)]);
+        let gaps = turn_gaps(&window);
+        let speakers: Vec<&str> = window.logical_turns().iter().map(|t| t.speaker.as_str()).collect();
+        let ids = window.turn_ids();
+        
+        // Turn 0 leaves: the gap before turn 1 goes with it
+        let mut tracker = TimingTracker::default();
+        for i in 1..speakers.len() {
+            tracker.add(ids[i], speakers[i], gaps[i].unwrap(), speakers[i] != speakers[i - 1]);
+        }
+        tracker.remove(ids[1], gaps[1].unwrap(), true);
+        
+        let rest = timed_window(&[("B", 58), ("A", 57), ("B", 35), ("B", 34), ("A", 30)]);
+        let expected = timing_stats(&rest).unwrap();
+        let stats = tracker.stats(|id| ids.iter().position(|&i| i == id).unwrap() - 1).unwrap();
+        assert_eq!(stats.max_gap_ms, expected.max_gap_ms);
+        assert_eq!(stats.gaps, expected.gaps);
+        assert_eq!(stats.rapid_replies, expected.rapid_replies);
+        assert_eq!(stats.long_silences, expected.long_silences);
+        assert!((stats.mean_latency_ms - expected.mean_latency_ms).abs() < 1e-9);
+        assert!((stats.latency_std_ms - expected.latency_std_ms).abs() < 1e-6);
+        assert_eq!(tracker.replies(), (3, 0));
+    }
+}
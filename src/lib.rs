@@ -32,6 +32,18 @@ pub const WAITING_MIN_MS: u64 = 10000;
 /// Timeout for DRIFT → WAITING (milliseconds)
 pub const DRIFT_TIMEOUT_MS: u64 = 60000;
 
+// =============================================================================
+// CONFIDENCE - Turn reliability gating
+// =============================================================================
+
+/// Minimum turn confidence to start or sustain a lock
+/// 0.10 = 5 words with the r-parser's word-count confidence
+pub const CONFIDENCE_MIN_LOCK: f64 = 0.10;
+
+/// Confidence at which a turn moves r with full weight
+/// Below this, the turn's r is blended with the previous r
+pub const CONFIDENCE_FULL_WEIGHT: f64 = 0.20;
+
 // =============================================================================
 // r-PARSER WEIGHTS [C] - Grok's empirically tuned values (sum = 18.5)
 // =============================================================================
@@ -45,9 +57,13 @@ pub const R_WEIGHT_COMPARISON: f64 = 2.2;
 pub const R_WEIGHT_JUDGMENT: f64 = 3.5;  // Highest weight
 pub const R_WEIGHT_URGENCY: f64 = 2.6;
 
-/// Sum of all weights for normalization
+/// Sum of the seven lexical weights for normalization
 pub const R_WEIGHT_SUM: f64 = 18.5;
 
+/// Default intensity weight (caps, !!!/?! runs, elongation, emphatic emoji)
+/// Off, so default scores stay the seven-signal r; opt in with `RParser::with_intensity_weight`
+pub const R_WEIGHT_INTENSITY: f64 = 0.0;
+
 // =============================================================================
 // VERSION
 // =============================================================================
@@ -3,15 +3,25 @@
 //! Usage:
 //!   soul0 --text "your text here"           # Single evaluation
 //!   soul0 --interactive                     # Interactive solo mode
-//!   soul0 --duo                             # Interactive duo mode (A: / B:)
+//!   soul0 --duo                             # Interactive duo mode (A: / B:, --speakers A,B,C,… for groups)
 //!   soul0 --serve                           # HTTP API server
+//!   soul0 analyze chat.txt                  # Analyze a transcript file
+//!   soul0 analyze call.json --rttm call.rttm # Analyze a diarized recording
+//!   soul0 --duo --audit-log s.ndjson        # Record a duo session
+//!   soul0 replay s.ndjson --speed 10x       # Replay it and diff the outputs
+//!   soul0 compact --dry-run                 # Preview rolling old snapshots into summaries
+//!   soul0 snapshots list --theme work       # List stored snapshots (also show, search, validate)
+//!   soul0 snapshots migrate snapshots.db    # Copy the snapshot directory into SQLite
 //!   soul0 --text "text" --json              # JSON output
 
-use clap::Parser;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
 use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use std::time::Instant;
 
-use soul0::core::{RParser, FacelockEngine, DcParser, ProofGenerator, SnapshotGenerator, save_snapshot, run_server};
-use soul0::types::{FacelockState, Turn, ConversationWindow, DcResult};
+use soul0::core::{DcScorerState, ThematicModel, Scorers, FacelockEngine, min_confidence_from, ProofGenerator, SnapshotGenerator, SnapshotStore, DirStore, SqliteStore, migrate_dir, validate_dir, plural, run_server_with_store, load_transcript, load_diarized, analyze_transcript, AnalyzeOptions, AuditLog, read_audit_log, replay, recorded_scorers, compact_store, spawn_compaction_job};
+use soul0::types::{RAggregation, GroupAggregation, DcWeights, FacelockState, Turn, MultiScaleWindow, WindowPolicy, DcResult, DcTimeline, TranscriptFormat, TranscriptReport, TurnReport, AuditConfig, ScorerConfig, ReplaySpeed, CompactionPolicy, Snapshot, SnapshotQuery, merge_gap_from_secs};
 use soul0::VERSION;
 
 #[derive(Parser, Debug)]
@@ -24,8 +34,12 @@ use soul0::VERSION;
                   and tracks state transitions toward Facelock (aligned perception).\n\n\
                   Modes:\n  \
                   --interactive  Solo mode (r only)\n  \
-                  --duo          Duo mode (r + ΔC, use A: and B: prefixes)\n  \
-                  --serve        HTTP API server mode\n\n\
+                  --duo          Duo mode (r + ΔC, use A: and B: prefixes; --speakers A,B,C,… for groups)\n  \
+                  --serve        HTTP API server mode\n  \
+                  analyze FILE   Replay a chat export, subtitle file or log\n  \
+                  replay LOG     Re-feed a recorded session and diff the outputs\n  \
+                  compact        Roll aging snapshots into daily / weekly / monthly summaries\n  \
+                  snapshots      List, show, search, validate or migrate stored snapshots\n\n\
                   States:\n  \
                   WAITING     - Not enough data yet\n  \
                   APPROACHING - Moving toward alignment\n  \
@@ -33,6 +47,9 @@ use soul0::VERSION;
                   DRIFT       - Alignment lost"
 )]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    
     /// Text to evaluate (single mode)
     #[arg(short, long)]
     text: Option<String>,
@@ -41,7 +58,7 @@ struct Args {
     #[arg(short, long)]
     interactive: bool,
     
-    /// Duo mode - two speakers with A: and B: prefixes (r + ΔC)
+    /// Duo mode - speakers with A: and B: prefixes, or those in --speakers (r + ΔC)
     #[arg(short, long)]
     duo: bool,
     
@@ -49,61 +66,317 @@ struct Args {
     #[arg(short, long)]
     serve: bool,
     
+    /// Speaker names accepted as "Name:" prefixes in duo mode (comma-separated)
+    #[arg(long, value_delimiter = ',', default_value = "A,B")]
+    speakers: Vec<String>,
+    
     /// Server address (default: 127.0.0.1:3000)
     #[arg(long, default_value = "127.0.0.1:3000")]
     addr: String,
     
     /// Output as JSON
-    #[arg(long)]
+    #[arg(long, global = true)]
     json: bool,
     
     /// Disable colors in output
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_color: bool,
     
     /// Show signal breakdown
-    #[arg(long)]
+    #[arg(long, global = true)]
     verbose: bool,
     
     /// Directory for snapshots (default: ./snapshots)
-    #[arg(long, default_value = "./snapshots")]
+    #[arg(long, default_value = "./snapshots", global = true)]
     snapshot_dir: String,
     
+    /// Store snapshots in this SQLite database instead of --snapshot-dir
+    #[arg(long, global = true)]
+    snapshot_db: Option<String>,
+    
     /// Disable automatic snapshot generation
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_snapshot: bool,
+    
+    /// Weight of the intensity signal (CAPS, !!!, emoji), e.g. 2.0; 0 = original 7-signal r
+    #[arg(long, default_value_t = soul0::R_WEIGHT_INTENSITY, global = true)]
+    intensity_weight: f64,
+    
+    /// Sentence aggregation for r: whole, mean, max, length-weighted, top-N
+    #[arg(long, default_value_t = RAggregation::Whole, global = true)]
+    r_aggregation: RAggregation,
+    
+    /// Thematic drift model for ΔC: tfidf or jaccard (original word overlap)
+    #[arg(long, default_value_t = ThematicModel::TfIdf, global = true)]
+    thematic_model: ThematicModel,
+    
+    /// Group ΔC with more than two speakers: mean, worst-pair or median
+    #[arg(long, default_value_t = GroupAggregation::Mean, global = true)]
+    group_aggregation: GroupAggregation,
+    
+    /// Weight of turn-taking balance in ΔC, e.g. 0.1; 0 = original 5-signal ΔC
+    #[arg(long, default_value_t = soul0::types::DC_WEIGHT_BALANCE, global = true)]
+    balance_weight: f64,
+    
+    /// Share of the thematic weight given to style mismatch (LSM), e.g. 0.3; 0 = topic only
+    #[arg(long, default_value_t = soul0::types::DC_STYLE_SHARE, global = true)]
+    style_share: f64,
+    
+    /// Weight of turn timing (latency spread, long silences) in ΔC; 0 = report only
+    #[arg(long, default_value_t = soul0::types::DC_WEIGHT_TIMING, global = true)]
+    timing_weight: f64,
+    
+    /// Window policy: comma-separated time=SECS, turns=N, pairs=N, per-speaker=N (or standard)
+    #[arg(long, default_value_t = WindowPolicy::standard(), global = true)]
+    window: WindowPolicy,
+    
+    /// Merge consecutive turns by one speaker within this many seconds into one logical turn
+    #[arg(long, global = true, value_parser = parse_merge_gap)]
+    merge_gap: Option<std::time::Duration>,
+    
+    /// Background corpus for TF-IDF (text file, one document per line)
+    #[arg(long, global = true)]
+    corpus: Option<String>,
+    
+    /// Minimum turn confidence to start or sustain a lock (0.0-1.0)
+    #[arg(long, default_value_t = soul0::CONFIDENCE_MIN_LOCK, global = true, value_parser = parse_min_confidence)]
+    min_confidence: f64,
+    
+    /// Record the duo session to this NDJSON audit log (see `soul0 replay`)
+    #[arg(long)]
+    audit_log: Option<String>,
+    
+    /// Record every API session to <DIR>/<session_id>.ndjson
+    #[arg(long)]
+    audit_dir: Option<String>,
+    
+    /// Snapshot compaction ages in days: daily=N, weekly=N, monthly=N (or standard, off)
+    #[arg(long, default_value_t = CompactionPolicy::standard(), global = true)]
+    compaction: CompactionPolicy,
+    
+    /// Compact the snapshot directory every this many seconds while serving
+    #[arg(long)]
+    compact_every: Option<u64>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Replay a transcript through r / ΔC / Facelock on its own timestamps
+    Analyze {
+        /// WhatsApp or Signal text export, SRT / WebVTT subtitles, a "Name: text" log, or segment JSON
+        file: String,
+        
+        /// Transcript format: whatsapp, signal, srt, vtt, log or diarized (default: detect)
+        #[arg(long)]
+        format: Option<TranscriptFormat>,
+        
+        /// RTTM speaker turns for a segment JSON file (who spoke when)
+        #[arg(long)]
+        rttm: Option<String>,
+    },
+    /// Re-feed a recorded session (--audit-log / --audit-dir) and diff the outputs
+    Replay {
+        /// NDJSON audit log
+        log: String,
+        
+        /// Replay speed: original, instant or a factor like 10x (results do not depend on it)
+        #[arg(long, default_value_t = ReplaySpeed::Instant)]
+        speed: ReplaySpeed,
+        
+        /// Score with this command's parser flags instead of the recorded settings
+        #[arg(long)]
+        current_scorers: bool,
+    },
+    /// Roll aging snapshots in --snapshot-dir or --snapshot-db into summaries (see --compaction)
+    Compact {
+        /// Show what would be compacted without writing or removing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Manage stored snapshots
+    Snapshots {
+        #[command(subcommand)]
+        action: SnapshotsCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SnapshotsCommand {
+    /// List snapshots, newest first
+    List {
+        #[command(flatten)]
+        filter: SnapshotFilter,
+    },
+    /// Print one snapshot with its blind spots and horizon questions
+    Show {
+        /// Snapshot ID
+        id: String,
+    },
+    /// Full-text search over seen themes and keywords (prefix match, best first)
+    Search {
+        /// Search terms
+        #[arg(required = true)]
+        terms: Vec<String>,
+        
+        #[command(flatten)]
+        filter: SnapshotFilter,
+    },
+    /// Check every snapshot file with its proof link; exits 1 on failures
+    Validate {
+        /// Directory to check (default: --snapshot-dir)
+        dir: Option<String>,
+    },
+    /// Copy every snapshot in --snapshot-dir into a SQLite database (files are kept)
+    Migrate {
+        /// SQLite database to create or add to
+        db: String,
+    },
+}
+
+/// `--merge-gap` in seconds, rejecting negative, non-finite and huge values
+fn parse_merge_gap(value: &str) -> Result<std::time::Duration, String> {
+    let secs: f64 = value.trim().parse().map_err(|_| format!("'{}' is not a number of seconds", value))?;
+    merge_gap_from_secs(secs)
+}
+
+/// `--min-confidence`, rejecting non-finite values and anything outside 0.0-1.0
+fn parse_min_confidence(value: &str) -> Result<f64, String> {
+    let value: f64 = value.trim().parse().map_err(|_| format!("'{}' is not a number", value))?;
+    min_confidence_from(value)
+}
+
+/// Filters shared by `snapshots list` and `snapshots search`
+#[derive(clap::Args, Debug)]
+struct SnapshotFilter {
+    /// From this date on (YYYY-MM-DD, UTC)
+    #[arg(long)]
+    since: Option<NaiveDate>,
+    
+    /// Up to and including this date (YYYY-MM-DD, UTC)
+    #[arg(long)]
+    until: Option<NaiveDate>,
+    
+    /// With this observer
+    #[arg(long)]
+    observer: Option<String>,
+    
+    /// With this theme (case-insensitive)
+    #[arg(long)]
+    theme: Option<String>,
+    
+    /// At most this many results
+    #[arg(long)]
+    limit: Option<usize>,
+}
+
+impl SnapshotFilter {
+    fn query(&self) -> SnapshotQuery {
+        let midnight = |date: NaiveDate| date.and_hms_opt(0, 0, 0).map(|t| t.and_utc().timestamp());
+        SnapshotQuery {
+            observer: self.observer.clone(),
+            since: self.since.and_then(midnight),
+            until: self.until.and_then(|date| date.succ_opt()).and_then(midnight),
+            theme: self.theme.clone(),
+            limit: self.limit,
+            ..Default::default()
+        }
+    }
+}
+
+impl Args {
+    /// Balance and style weights as passed on the command line
+    fn dc_weights(&self) -> DcWeights {
+        DcWeights {
+            balance: self.balance_weight.max(0.0),
+            style_share: self.style_share.clamp(0.0, 1.0),
+            timing: self.timing_weight.max(0.0),
+        }
+    }
+    
+    /// The SQLite store with --snapshot-db, the snapshot directory otherwise
+    fn snapshot_store(&self) -> Arc<dyn SnapshotStore> {
+        match self.snapshot_db {
+            Some(ref path) => match SqliteStore::open(path) {
+                Ok(store) => Arc::new(store),
+                Err(e) => {
+                    eprintln!("Cannot open snapshot database {}: {}", path, e);
+                    std::process::exit(1);
+                }
+            },
+            None => Arc::new(DirStore::new(self.snapshot_dir.clone())),
+        }
+    }
+    
+    /// Parser settings as passed on the command line
+    fn scorer_config(&self) -> ScorerConfig {
+        ScorerConfig {
+            intensity_weight: self.intensity_weight.max(0.0),
+            r_aggregation: self.r_aggregation,
+            thematic_model: self.thematic_model,
+            corpus: self.corpus.clone(),
+            corpus_sha256: None,
+            group_aggregation: self.group_aggregation,
+            weights: self.dc_weights(),
+        }
+    }
+    
+    /// Session settings written to audit logs
+    fn audit_config(&self, scorers: &Scorers) -> AuditConfig {
+        AuditConfig {
+            window: self.window,
+            merge_gap_ms: self.merge_gap.map(|gap| gap.as_millis() as u64),
+            min_confidence: self.min_confidence,
+            scorers: scorers.config.clone(),
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
+    let scorers = match Scorers::from_config(&args.scorer_config()) {
+        Ok(scorers) => scorers,
+        Err(e) => {
+            eprintln!("Cannot read corpus {}: {}", args.corpus.as_deref().unwrap_or(""), e);
+            std::process::exit(1);
+        }
+    };
     
-    if args.serve {
-        run_serve(&args).await;
+    if let Some(Command::Analyze { ref file, format, ref rttm }) = args.command {
+        run_analyze(file, format, rttm.as_deref(), &args, &scorers);
+    } else if let Some(Command::Replay { ref log, speed, current_scorers }) = args.command {
+        run_replay(log, speed, current_scorers, &args, &scorers);
+    } else if let Some(Command::Compact { dry_run }) = args.command {
+        run_compact(dry_run, &args);
+    } else if let Some(Command::Snapshots { ref action }) = args.command {
+        run_snapshots(action, &args);
+    } else if args.serve {
+        run_serve(&args, &scorers).await;
     } else if args.duo {
-        run_duo(&args);
+        run_duo(&args, &scorers);
     } else if args.interactive {
-        run_interactive(&args);
+        run_interactive(&args, &scorers);
     } else if let Some(ref text) = args.text {
-        run_single(text, &args);
+        run_single(text, &args, &scorers);
     } else {
         // Default to interactive if no mode specified
-        run_interactive(&args);
+        run_interactive(&args, &scorers);
     }
 }
 
 /// Run single text evaluation
-fn run_single(text: &str, args: &Args) {
-    let parser = RParser::new();
-    let mut engine = FacelockEngine::new();
+fn run_single(text: &str, args: &Args, scorers: &Scorers) {
+    let parser = scorers.r.as_ref();
+    let mut engine = FacelockEngine::with_min_confidence(args.min_confidence);
     
     let r_value = parser.parse(text);
-    let output = engine.update(r_value.value);
+    let output = engine.update_with_confidence(r_value.value, r_value.confidence)
+        .with_sentences(r_value.sentences.clone());
     
     if args.json {
         println!("{}", serde_json::to_string_pretty(&output).unwrap());
     } else if args.verbose {
-        print_verbose_solo(&r_value, &output, args.no_color);
+        print_verbose_solo(&r_value, &output, args.intensity_weight, args.no_color);
     } else {
         if args.no_color {
             println!("{}", output.to_parseable_string());
@@ -114,9 +387,9 @@ fn run_single(text: &str, args: &Args) {
 }
 
 /// Run interactive solo mode (Slice 1)
-fn run_interactive(args: &Args) {
-    let parser = RParser::new();
-    let mut engine = FacelockEngine::new();
+fn run_interactive(args: &Args, scorers: &Scorers) {
+    let parser = scorers.r.as_ref();
+    let mut engine = FacelockEngine::with_min_confidence(args.min_confidence);
     
     print_header("Solo Mode", args.no_color);
     println!("Type text and press Enter to measure r. Type 'quit' to exit.");
@@ -148,12 +421,13 @@ fn run_interactive(args: &Args) {
         }
         
         let r_value = parser.parse(line);
-        let output = engine.update(r_value.value);
+        let output = engine.update_with_confidence(r_value.value, r_value.confidence)
+            .with_sentences(r_value.sentences.clone());
         
         if args.json {
             println!("{}", serde_json::to_string(&output).unwrap());
         } else if args.verbose {
-            print_verbose_solo(&r_value, &output, args.no_color);
+            print_verbose_solo(&r_value, &output, args.intensity_weight, args.no_color);
         } else if args.no_color {
             println!("{}", output.to_parseable_string());
         } else {
@@ -164,13 +438,15 @@ fn run_interactive(args: &Args) {
 }
 
 /// Run duo mode (Slice 2+3+4) - two speakers with A: and B: prefixes
-fn run_duo(args: &Args) {
-    let r_parser = RParser::new();
-    let dc_parser = DcParser::new();
-    let mut engine = FacelockEngine::new();
-    let mut window = ConversationWindow::new();
+fn run_duo(args: &Args, scorers: &Scorers) {
+    let r_parser = scorers.r.as_ref();
+    let dc_parser = scorers.dc.as_ref();
+    let mut engine = FacelockEngine::with_min_confidence(args.min_confidence);
+    let mut windows = MultiScaleWindow::with_primary(args.window);
+    windows.set_merge_gap(args.merge_gap);
+    let mut dc_states: Vec<DcScorerState> = windows.scales().map(|_| DcScorerState::default()).collect();
     let proof_gen = ProofGenerator::new_random();
-    let snap_gen = SnapshotGenerator::new();
+    let snap_gen = SnapshotGenerator::with_scorer(scorers.r.clone());
     
     // Generate session ID
     let session_id: [u8; 16] = {
@@ -187,15 +463,28 @@ fn run_duo(args: &Args) {
     // Track if we've generated a proof this session
     let mut proof_generated = false;
     let mut observers: Vec<String> = Vec::new();
+    let mut timeline = DcTimeline::new();
+    
+    let mut audit = args.audit_log.as_ref().map(|path| {
+        let id: String = session_id.iter().map(|b| format!("{:02x}", b)).collect();
+        AuditLog::create(path, &id, "duo", args.audit_config(scorers)).unwrap_or_else(|e| {
+            eprintln!("Cannot create audit log {}: {}", path, e);
+            std::process::exit(1);
+        })
+    });
     
     print_header("Duo Mode", args.no_color);
-    println!("Two speakers mode. Prefix each line with A: or B:");
+    println!("Prefix each line with a speaker: {}: (--speakers A,B,C,… for groups)", args.speakers.join(":, "));
     println!("Example: A: The sky is blue");
     println!("         B: Yes, very blue today");
     println!();
     println!("Goal: reach LOCKED state (r < 0.15 AND ΔC < 0.10 for 8 seconds)");
-    if !args.no_snapshot {
-        println!("Snapshots will be saved to: {}", args.snapshot_dir);
+    let store = (!args.no_snapshot).then(|| args.snapshot_store());
+    if let Some(ref store) = store {
+        println!("Snapshots will be saved to: {}", store.location());
+    }
+    if let Some(ref log) = audit {
+        println!("Recording to audit log: {}", log.path());
     }
     println!("Type 'quit' to exit.");
     println!();
@@ -204,7 +493,7 @@ fn run_duo(args: &Args) {
     let mut stdout = io::stdout();
     
     loop {
-        let dc_result = dc_parser.calculate(&window);
+        let dc_result = dc_parser.calculate_with(windows.primary(), &mut dc_states[0]);
         let prompt = format_prompt_duo(&engine, &dc_result, args.no_color);
         print!("{}", prompt);
         stdout.flush().unwrap();
@@ -218,7 +507,10 @@ fn run_duo(args: &Args) {
         
         let line = line.trim();
         if line.eq_ignore_ascii_case("quit") || line.eq_ignore_ascii_case("exit") {
-            println!("\nSession ended. Turns: {}", window.len());
+            println!("\nSession ended. Turns: {}", timeline.len());
+            if let Some((point, rise)) = timeline.biggest_rise() {
+                println!("Largest ΔC rise: +{:.3} at turn {} ({})", rise, point.turn, point.speaker);
+            }
             break;
         }
         if line.is_empty() {
@@ -226,13 +518,14 @@ fn run_duo(args: &Args) {
         }
         
         // Parse speaker prefix
-        let (speaker, text) = parse_speaker_prefix(line);
-        if speaker.is_empty() {
-            println!("{}⚠ Please prefix with A: or B: (e.g., 'A: Hello'){}", 
+        let Some((speaker, text)) = parse_speaker_prefix(line, &args.speakers) else {
+            println!("{}⚠ Please prefix with a speaker from --speakers ({}:, e.g. '{}: Hello'){}", 
                 if args.no_color { "" } else { "\x1b[33m" },
+                args.speakers.join(":, "),
+                args.speakers.first().map_or("A", String::as_str),
                 if args.no_color { "" } else { "\x1b[0m" });
             continue;
-        }
+        };
         
         // Track observers
         if !observers.contains(&speaker) {
@@ -242,12 +535,15 @@ fn run_duo(args: &Args) {
         // Calculate r for this turn
         let r_value = r_parser.parse(text);
         
-        // Add turn to window
-        let turn = Turn::new(speaker.clone(), text, r_value.value);
-        window.add_turn(turn);
+        // Add turn to every window scale (one timestamp for window, engine and audit log)
+        let now = Instant::now();
+        let turn = Turn::new(speaker.clone(), text, r_value.value).with_timestamp(now);
+        windows.add_turn(turn);
         
-        // Calculate ΔC
-        let dc_result = dc_parser.calculate(&window);
+        // Calculate ΔC per scale; the short one drives state transitions
+        let scales = dc_parser.calculate_scales(&windows, &mut dc_states);
+        let dc_result = scales[0].dc.clone();
+        timeline.record(speaker.clone(), &dc_result);
         
         // Update engine with combined metric
         // In duo mode, we use the higher of r or ΔC for state transitions
@@ -256,13 +552,20 @@ fn run_duo(args: &Args) {
         } else {
             r_value.value
         };
-        let output = engine.update(effective_r);
+        let output = engine.update_at(effective_r, r_value.confidence, now)
+            .with_sentences(r_value.sentences.clone())
+            .with_scales(scales);
+        audit_write(&mut audit, |log| {
+            log.record_turn(&speaker, text, now)?;
+            log.record_state(&output)?;
+            log.record_dc(&dc_result)
+        });
         
         // Print output
         if args.json {
             print_json_duo(&output, &dc_result);
         } else if args.verbose {
-            print_verbose_duo(&r_value, &dc_result, &output, &speaker, args.no_color);
+            print_verbose_duo(&r_value, &dc_result, &timeline, &output, &speaker, &args.dc_weights(), args.no_color);
         } else {
             print_output_duo(&output, &dc_result, &speaker, args.no_color);
         }
@@ -281,39 +584,39 @@ fn run_duo(args: &Args) {
                 output.stable_ms as f64 / 1000.0,
                 output.r,
                 &dc_result,
-                &window,
+                windows.primary(),
                 mock_sign,
             );
             
             if let Some(proof) = proof_result.proof {
                 proof_generated = true;
+                audit_write(&mut audit, |log| log.record_proof(&proof.to_hex()));
                 
                 // Print proof
                 println!();
-                println!("{}╔═══════════════════════════════════════════════════════════╗{}", 
-                    "\x1b[32m", "\x1b[0m");
-                println!("{}║  PROOF GENERATED - 248 bytes                              ║{}", 
-                    "\x1b[32m", "\x1b[0m");
-                println!("{}╚═══════════════════════════════════════════════════════════╝{}", 
-                    "\x1b[32m", "\x1b[0m");
+                println!("\x1b[32m╔═══════════════════════════════════════════════════════════╗\x1b[0m");
+                println!("\x1b[32m║  PROOF GENERATED - 248 bytes                              ║\x1b[0m");
+                println!("\x1b[32m╚═══════════════════════════════════════════════════════════╝\x1b[0m");
                 println!("  {}", &proof.to_hex()[0..64]);
                 println!("  ...");
                 
                 // Generate snapshot (1-op-1 coupling with proof)
-                if !args.no_snapshot {
-                    let snap_result = snap_gen.generate(&proof, &window, observers.clone());
+                if let Some(ref store) = store {
+                    let snap_result = snap_gen.generate(&proof, windows.longest(), observers.clone());
                     
                     if let Some(snapshot) = snap_result.snapshot {
-                        match save_snapshot(&snapshot, &args.snapshot_dir) {
+                        match store.save(&snapshot) {
                             Ok(path) => {
+                                audit_write(&mut audit, |log| log.record_snapshot(&snapshot.id, Some(&path)));
                                 println!();
-                                println!("{}  SNAPSHOT SAVED: {}{}", "\x1b[36m", path, "\x1b[0m");
-                                println!("{}  Themes: {:?}{}", "\x1b[90m", snapshot.seen.themes, "\x1b[0m");
-                                println!("{}  Blind spots: {}{}", "\x1b[90m", snapshot.blind_spots.len(), "\x1b[0m");
-                                println!("{}  Horizon items: {}{}", "\x1b[90m", snapshot.horizon.len(), "\x1b[0m");
+                                println!("\x1b[36m  SNAPSHOT SAVED: {}\x1b[0m", path);
+                                println!("\x1b[90m  Themes: {:?}\x1b[0m", snapshot.seen.themes);
+                                println!("\x1b[90m  Blind spots: {}\x1b[0m", snapshot.blind_spots.len());
+                                println!("\x1b[90m  Horizon items: {}\x1b[0m", snapshot.horizon.len());
                             }
                             Err(e) => {
-                                println!("{}  Snapshot save failed: {}{}", "\x1b[31m", e, "\x1b[0m");
+                                audit_write(&mut audit, |log| log.record_snapshot(&snapshot.id, None));
+                                println!("\x1b[31m  Snapshot save failed: {}\x1b[0m", e);
                             }
                         }
                     }
@@ -329,6 +632,379 @@ fn run_duo(args: &Args) {
     }
 }
 
+/// Write to the audit log; on failure warn once and stop recording
+fn audit_write(audit: &mut Option<AuditLog>, write: impl FnOnce(&mut AuditLog) -> io::Result<()>) {
+    if let Some(log) = audit.as_mut() {
+        if let Err(e) = write(log) {
+            eprintln!("Audit log {} failed, recording stopped: {}", log.path(), e);
+            *audit = None;
+        }
+    }
+}
+
+/// Analyze a transcript file and print a per-turn report
+fn run_analyze(file: &str, format: Option<TranscriptFormat>, rttm: Option<&str>, args: &Args, scorers: &Scorers) {
+    let imported = match rttm {
+        Some(rttm) => load_diarized(file, Some(rttm)),
+        None => load_transcript(file, format),
+    };
+    let transcript = match imported {
+        Ok(transcript) => transcript,
+        Err(e) => {
+            eprintln!("Cannot import {}: {}", file, e);
+            std::process::exit(1);
+        }
+    };
+    let options = AnalyzeOptions {
+        window: args.window,
+        merge_gap: args.merge_gap,
+        min_confidence: args.min_confidence,
+    };
+    let analysis = analyze_transcript(&transcript, scorers, &options, mock_sign);
+    let report = &analysis.report;
+    
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(report).unwrap());
+    } else {
+        print_header("Analyze", args.no_color);
+        println!("{} ({}, {} turns, {}) {}", file, report.format, report.turns.len(),
+            if report.timed { format_offset(report.duration_ms) } else { "untimed".to_string() },
+            report.import.code());
+        println!();
+        print_report_turns(report, args.verbose, args.no_color);
+        print_report_summary(report);
+    }
+    
+    if !args.no_snapshot && !analysis.snapshots.is_empty() {
+        let store = args.snapshot_store();
+        for snapshot in &analysis.snapshots {
+            match store.save(snapshot) {
+                Ok(path) if !args.json => println!("Snapshot saved: {}", path),
+                Ok(_) => {}
+                Err(e) => eprintln!("Snapshot save failed: {}", e),
+            }
+        }
+    }
+}
+
+/// Replay an audit log and report where the outputs differ from the recording
+fn run_replay(log: &str, speed: ReplaySpeed, current_scorers: bool, args: &Args, scorers: &Scorers) {
+    let recording = match read_audit_log(log) {
+        Ok(recording) => recording,
+        Err(e) => {
+            eprintln!("Cannot read {}: {}", log, e);
+            std::process::exit(1);
+        }
+    };
+    
+    if !args.json {
+        print_header("Replay", args.no_color);
+        println!("{} ({} session {}, {} turns, recorded with {}, speed {})", log, recording.mode,
+            recording.session_id, recording.turns.len(), recording.version, speed);
+        println!();
+    }
+    let mut speakers: Vec<String> = Vec::new();
+    for turn in &recording.turns {
+        if !speakers.contains(&turn.speaker) {
+            speakers.push(turn.speaker.clone());
+        }
+    }
+    let width = speaker_width(&speakers);
+    let recorded = if current_scorers { Ok(scorers.clone()) } else { recorded_scorers(&recording, scorers) };
+    let scorers = match recorded {
+        Ok(scorers) => scorers,
+        Err(e) => {
+            eprintln!("Cannot rebuild the recorded scorers: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let report = replay(&recording, &scorers, speed, mock_sign, |_, step| {
+        if !args.json {
+            print_report_turn(&step.report, width, args.verbose, args.no_color);
+        }
+    });
+    
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        println!();
+        println!("Final state: {}", report.final_state);
+        if report.is_clean() {
+            println!("Replay matches the recording.");
+        } else {
+            println!("Differences: {}", report.diffs.len());
+            for diff in &report.diffs {
+                println!("  turn {:>4}  {:<10} recorded {}  replayed {}", diff.seq, diff.field, diff.recorded, diff.replayed);
+            }
+        }
+    }
+    if !report.is_clean() {
+        std::process::exit(1);
+    }
+}
+
+/// Compact the snapshot store and list the summaries
+fn run_compact(dry_run: bool, args: &Args) {
+    let now = chrono::Utc::now().timestamp();
+    let store = args.snapshot_store();
+    let report = match compact_store(store.as_ref(), &args.compaction, now, dry_run) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Cannot compact {}: {}", store.location(), e);
+            std::process::exit(1);
+        }
+    };
+    
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+        return;
+    }
+    print_header("Compaction", args.no_color);
+    println!("{} (policy {}){}", store.location(), args.compaction, if dry_run { ", dry run" } else { "" });
+    println!();
+    for (i, summary) in report.summaries.iter().enumerate() {
+        let start = chrono::DateTime::from_timestamp(summary.period_start, 0).unwrap_or_default();
+        println!("{:<8} {}  {} snapshots  r̄ {:.3}  ΔC̄ {:.3}", summary.period, start.format("%Y-%m-%d"),
+            summary.snapshot_count, summary.avg_r, summary.avg_dc);
+        if !summary.recurring_themes.is_empty() {
+            println!("         themes: {}", summary.recurring_themes.join(", "));
+        }
+        for resolved in &summary.resolved_blind_spots {
+            println!("         resolved: {}", resolved);
+        }
+        for pattern in &summary.patterns {
+            println!("         {}", pattern);
+        }
+        if let Some(path) = report.paths.get(i) {
+            println!("         → {}", path);
+        }
+    }
+    if !report.summaries.is_empty() {
+        println!();
+    }
+    let verb = if dry_run { "Would compact" } else { "Compacted" };
+    println!("{} {} snapshots into {} summaries; {} kept.", verb, report.compacted, report.summaries.len(), report.kept);
+    if report.merged > 0 {
+        println!("{} older summaries folded into coarser ones.", report.merged);
+    }
+}
+
+/// Run a `soul0 snapshots` action
+fn run_snapshots(action: &SnapshotsCommand, args: &Args) {
+    match action {
+        SnapshotsCommand::List { filter } => {
+            let store = args.snapshot_store();
+            let snapshots = store.query(&filter.query()).unwrap_or_else(|e| {
+                eprintln!("Cannot read snapshots from {}: {}", store.location(), e);
+                std::process::exit(1);
+            });
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&snapshots).unwrap());
+                return;
+            }
+            for snapshot in &snapshots {
+                println!("{}", snapshot_line(snapshot));
+            }
+            println!("{} in {}", plural(snapshots.len(), "snapshot"), store.location());
+        }
+        SnapshotsCommand::Show { id } => {
+            let store = args.snapshot_store();
+            match store.load(id) {
+                Ok(snapshot) if args.json => println!("{}", serde_json::to_string_pretty(&snapshot).unwrap()),
+                Ok(snapshot) => print_snapshot(&snapshot, args.no_color),
+                Err(e) => {
+                    eprintln!("Cannot load snapshot {} from {}: {}", id, store.location(), e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        SnapshotsCommand::Search { terms, filter } => {
+            let store = args.snapshot_store();
+            let hits = store.search(&terms.join(" "), &filter.query()).unwrap_or_else(|e| {
+                eprintln!("Cannot search {}: {}", store.location(), e);
+                std::process::exit(1);
+            });
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&hits).unwrap());
+                return;
+            }
+            for hit in &hits {
+                println!("{}", snapshot_line(&hit.snapshot));
+                println!("    matched: {}", hit.matched.join(", "));
+            }
+            println!("{} found", plural(hits.len(), "snapshot"));
+        }
+        SnapshotsCommand::Validate { dir } => {
+            let dir = dir.as_deref().unwrap_or(&args.snapshot_dir);
+            let report = validate_dir(dir).unwrap_or_else(|e| {
+                eprintln!("Cannot read {}: {}", dir, e);
+                std::process::exit(1);
+            });
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&report).unwrap());
+            } else {
+                for failure in &report.failures {
+                    println!("✗ {}  {}", failure.path, failure.reason);
+                }
+                println!("{} valid, {} invalid ({} with a broken proof link)",
+                    report.valid, report.failures.len(), report.proof_link_failures());
+            }
+            if !report.failures.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        SnapshotsCommand::Migrate { db } => {
+            let target = match SqliteStore::open(db) {
+                Ok(store) => store,
+                Err(e) => {
+                    eprintln!("Cannot open snapshot database {}: {}", db, e);
+                    std::process::exit(1);
+                }
+            };
+            match migrate_dir(&args.snapshot_dir, &target) {
+                Ok(report) if args.json => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+                Ok(report) => {
+                    println!("Migrated {} snapshots from {} to {}", report.migrated, args.snapshot_dir, db);
+                    for path in &report.skipped {
+                        println!("Skipped (not a snapshot): {}", path);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Migration failed: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+/// One-line snapshot summary: time, ID, observers, r, ΔC, themes
+fn snapshot_line(snapshot: &Snapshot) -> String {
+    let created = chrono::DateTime::from_timestamp(snapshot.timestamp_unix, 0).unwrap_or_default();
+    let mut line = format!("{}  {}  r {:.3}  ΔC {:.3}  {}", created.format("%Y-%m-%d %H:%M"), snapshot.id,
+        snapshot.r_final, snapshot.dc_final, snapshot.observers.join(", "));
+    if !snapshot.seen.themes.is_empty() {
+        line.push_str(&format!("  [{}]", snapshot.seen.themes.join(", ")));
+    }
+    if !snapshot.blind_spots.is_empty() {
+        line.push_str(&format!("  {}", plural(snapshot.blind_spots.len(), "blind spot")));
+    }
+    line
+}
+
+/// Print a snapshot: what was seen, its blind spots and horizon questions
+fn print_snapshot(snapshot: &Snapshot, no_color: bool) {
+    let (bold, dim, reset) = if no_color { ("", "", "") } else { ("\x1b[1m", "\x1b[90m", "\x1b[0m") };
+    let created = chrono::DateTime::from_timestamp(snapshot.timestamp_unix, 0).unwrap_or_default();
+    
+    print_header("Snapshot", no_color);
+    println!("{}{}{}", bold, snapshot.id, reset);
+    println!("  Created:   {}", created.format("%Y-%m-%d %H:%M:%S UTC"));
+    println!("  Session:   {}", snapshot.session_hex());
+    println!("  Proof:     {}", snapshot.proof_hash_hex());
+    println!("  Observers: {}", snapshot.observers.join(", "));
+    println!("  r {:.3}  ΔC {:.3}  locked {}s  {} turns",
+        snapshot.r_final, snapshot.dc_final, snapshot.lock_duration_secs, snapshot.turn_count);
+    
+    println!();
+    println!("{}Seen{}", bold, reset);
+    println!("  Themes:   {}", snapshot.seen.themes.join(", "));
+    println!("  Keywords: {}", snapshot.seen.keywords.join(", "));
+    if let Some(ref emotion) = snapshot.seen.emotion {
+        println!("  Emotion:  {}", emotion);
+    }
+    if let Some(ref summary) = snapshot.seen.summary {
+        println!("  Summary:  {}", summary);
+    }
+    
+    println!();
+    println!("{}Blind spots{}", bold, reset);
+    if snapshot.blind_spots.is_empty() {
+        println!("  (none)");
+    }
+    for spot in &snapshot.blind_spots {
+        println!("  • {} {}({:.0}%){}", spot.description, dim, spot.confidence * 100.0, reset);
+    }
+    
+    println!();
+    println!("{}Horizon{}", bold, reset);
+    if snapshot.horizon.is_empty() {
+        println!("  (none)");
+    }
+    for item in &snapshot.horizon {
+        println!("  ? {}", item.question);
+        println!("    {}{}{}", dim, item.reason, reset);
+        if let Some(ref trigger) = item.potential_trigger {
+            println!("    {}→ {}{}", dim, trigger, reset);
+        }
+    }
+}
+
+/// Transcript time as MM:SS (H:MM:SS past an hour)
+fn format_offset(ms: u64) -> String {
+    let secs = ms / 1000;
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Print one line per analyzed turn
+fn print_report_turns(report: &TranscriptReport, verbose: bool, no_color: bool) {
+    let width = speaker_width(&report.speakers);
+    for turn in &report.turns {
+        print_report_turn(turn, width, verbose, no_color);
+    }
+}
+
+/// Column width for speaker names
+fn speaker_width(speakers: &[String]) -> usize {
+    speakers.iter().map(|s| s.chars().count()).max().unwrap_or(1).min(16)
+}
+
+/// Print one analyzed or replayed turn
+fn print_report_turn(turn: &TurnReport, width: usize, verbose: bool, no_color: bool) {
+    let color = if no_color { "" } else { turn.state.color_code() };
+    let reset = if no_color { "" } else { FacelockState::color_reset() };
+    let mut text: String = turn.text.replace('\n', " ").chars().take(48).collect();
+    if text.chars().count() < turn.text.chars().count() {
+        text.push('…');
+    }
+    println!(
+        "{}{:>4} {:>8}  {:<width$}  r={:.3}  ΔC={:<7}  {:<11} stable={:>5.1}s{}  {}{}",
+        color,
+        turn.turn,
+        format_offset(turn.offset_ms),
+        turn.speaker,
+        turn.r,
+        turn.dc.map_or("UNKNOWN".to_string(), |v| format!("{:.3}", v)),
+        turn.state.to_string(),
+        turn.stable_ms as f64 / 1000.0,
+        if turn.proof.is_some() { "  [PROOF]" } else { "" },
+        text,
+        reset,
+        width = width,
+    );
+    if verbose {
+        println!("{}{:>15}└─ {} | {}{}",
+            if no_color { "" } else { "\x1b[90m" }, "", turn.reason.code(), turn.dc_reason, reset);
+    }
+}
+
+/// Print totals after the per-turn report
+fn print_report_summary(report: &TranscriptReport) {
+    println!();
+    println!("Speakers:    {}", report.speakers.join(", "));
+    println!("Final state: {}", report.final_state());
+    println!("Locked:      {:.1}s", report.locked_ms as f64 / 1000.0);
+    println!("Proofs:      {}", report.proof_count);
+    if let Some(peak) = report.peak_dc() {
+        println!("Peak ΔC:     {:.3} at turn {} ({}, {})",
+            peak.dc.unwrap_or(0.0), peak.turn, peak.speaker, format_offset(peak.offset_ms));
+    }
+}
+
 /// Mock sign function for proof generation
 fn mock_sign(data: &[u8]) -> [u8; 64] {
     use sha2::{Sha256, Digest};
@@ -337,7 +1013,7 @@ fn mock_sign(data: &[u8]) -> [u8; 64] {
     let h1: [u8; 32] = hasher.finalize().into();
     
     let mut hasher = Sha256::new();
-    hasher.update(&h1);
+    hasher.update(h1);
     let h2: [u8; 32] = hasher.finalize().into();
     
     let mut sig = [0u8; 64];
@@ -346,31 +1022,17 @@ fn mock_sign(data: &[u8]) -> [u8; 64] {
     sig
 }
 
-/// Parse speaker prefix (A: or B:)
-fn parse_speaker_prefix(line: &str) -> (String, &str) {
+/// Parse a "Name:" prefix naming one of the configured speakers (case insensitive)
+///
+/// "Q:", "Note:" and other prefixes that are not in `--speakers` are no speaker.
+fn parse_speaker_prefix<'a>(line: &'a str, speakers: &[String]) -> Option<(String, &'a str)> {
     let line = line.trim();
+    let (prefix, text) = line.split_once(':')?;
+    let prefix = prefix.trim();
     
-    // Check for A: or B: prefix (case insensitive)
-    if line.len() >= 2 {
-        let prefix = &line[..2].to_uppercase();
-        if prefix == "A:" || prefix == "B:" {
-            let speaker = prefix[..1].to_string();
-            let text = line[2..].trim();
-            return (speaker, text);
-        }
-    }
-    
-    // Also accept "A :" or "B :" with space
-    if line.len() >= 3 {
-        let first_char = line.chars().next().unwrap().to_ascii_uppercase();
-        if (first_char == 'A' || first_char == 'B') && line[1..].trim_start().starts_with(':') {
-            let colon_pos = line.find(':').unwrap();
-            let text = line[colon_pos + 1..].trim();
-            return (first_char.to_string(), text);
-        }
-    }
-    
-    (String::new(), line)
+    speakers.iter()
+        .find(|s| s.trim().eq_ignore_ascii_case(prefix))
+        .map(|speaker| (speaker.trim().to_string(), text.trim()))
 }
 
 /// Print header
@@ -446,6 +1108,13 @@ fn print_output_duo(output: &soul0::types::StateOutput, dc: &DcResult, speaker:
             if no_color { "" } else { "\x1b[90m" },
             dc.reason,
             reset);
+    } else if dc.speaker_count > 2 {
+        if let Some(worst) = dc.matrix.iter().max_by(|a, b| a.dc.total_cmp(&b.dc)) {
+            println!("{}  └─ group ΔC ({}), worst pair {}–{} {:.3}{}",
+                if no_color { "" } else { "\x1b[90m" },
+                dc.aggregation, worst.speakers[0], worst.speakers[1], worst.dc,
+                reset);
+        }
     }
     
     print_state_message(output, true);
@@ -458,10 +1127,8 @@ fn print_state_message(output: &soul0::types::StateOutput, is_duo: bool) {
             let extra = if is_duo { " (r + ΔC)" } else { "" };
             println!("\x1b[32m  ✓ FACELOCK ACHIEVED{} - Proof available\x1b[0m", extra);
         }
-        FacelockState::Drift => {
-            if output.reason == soul0::types::ReasonCode::R005_TRANSITION_TO_DRIFT {
-                println!("\x1b[31m  ⚠ Alignment lost - return to stillness\x1b[0m");
-            }
+        FacelockState::Drift if output.reason == soul0::types::ReasonCode::R005_TRANSITION_TO_DRIFT => {
+            println!("\x1b[31m  ⚠ Alignment lost - return to stillness\x1b[0m");
         }
         _ => {}
     }
@@ -480,7 +1147,12 @@ fn print_json_duo(output: &soul0::types::StateOutput, dc: &DcResult) {
 }
 
 /// Print verbose solo output
-fn print_verbose_solo(r_value: &soul0::types::RValue, output: &soul0::types::StateOutput, no_color: bool) {
+fn print_verbose_solo(
+    r_value: &soul0::types::RValue,
+    output: &soul0::types::StateOutput,
+    intensity_weight: f64,
+    no_color: bool,
+) {
     let color = if no_color { "" } else { output.state.color_code() };
     let reset = if no_color { "" } else { FacelockState::color_reset() };
     
@@ -496,6 +1168,8 @@ fn print_verbose_solo(r_value: &soul0::types::RValue, output: &soul0::types::Sta
     println!("{}│   comparison:    {:.4} (w=2.2){}", color, r_value.signals.comparison, reset);
     println!("{}│   judgment:      {:.4} (w=3.5){}", color, r_value.signals.judgment, reset);
     println!("{}│   urgency:       {:.4} (w=2.6){}", color, r_value.signals.urgency, reset);
+    println!("{}│   intensity:     {:.4} (w={:.1}){}", color, r_value.signals.intensity, intensity_weight, reset);
+    print_sentences(r_value, color, reset);
     println!("{}├─────────────────────────────────────┤{}", color, reset);
     println!("{}│ State: {} | Stable: {:.1}s{}", 
         color, output.state, output.stable_ms as f64 / 1000.0, reset);
@@ -503,12 +1177,31 @@ fn print_verbose_solo(r_value: &soul0::types::RValue, output: &soul0::types::Sta
     println!("{}└─────────────────────────────────────┘{}", color, reset);
 }
 
+/// Print per-sentence r (sentence-level aggregation only)
+fn print_sentences(r_value: &soul0::types::RValue, color: &str, reset: &str) {
+    if r_value.sentences.is_empty() {
+        return;
+    }
+    
+    println!("{}├─────────────────────────────────────┤{}", color, reset);
+    println!("{}│ Sentences:{}", color, reset);
+    for sentence in &r_value.sentences {
+        let mut text: String = sentence.text.chars().take(40).collect();
+        if text.len() < sentence.text.len() {
+            text.push('…');
+        }
+        println!("{}│   {:.4}  {}{}", color, sentence.r.value, text, reset);
+    }
+}
+
 /// Print verbose duo output
 fn print_verbose_duo(
     r_value: &soul0::types::RValue, 
     dc: &DcResult, 
+    timeline: &DcTimeline,
     output: &soul0::types::StateOutput,
     speaker: &str,
+    weights: &DcWeights,
     no_color: bool
 ) {
     let color = if no_color { "" } else { output.state.color_code() };
@@ -520,6 +1213,8 @@ fn print_verbose_duo(
     println!("{}│ r Signals:{}", color, reset);
     println!("{}│   first_person:  {:.4}{}", color, r_value.signals.first_person, reset);
     println!("{}│   judgment:      {:.4}{}", color, r_value.signals.judgment, reset);
+    println!("{}│   intensity:     {:.4}{}", color, r_value.signals.intensity, reset);
+    print_sentences(r_value, color, reset);
     println!("{}├─────────────────────────────────────┤{}", color, reset);
     println!("{}│ ΔC = {}{}", color, dc.display_value(), reset);
     if dc.is_known() {
@@ -529,9 +1224,74 @@ fn print_verbose_duo(
         println!("{}│   logical:       {:.4} (w=0.22){}", color, dc.signals.logical_breaks, reset);
         println!("{}│   qa_mismatch:   {:.4} (w=0.12){}", color, dc.signals.qa_mismatch, reset);
         println!("{}│   ref_decay:     {:.4} (w=0.07){}", color, dc.signals.reference_decay, reset);
+        println!("{}│   style:         {:.4} ({:.0}% of thematic){}", color, dc.signals.style_mismatch, weights.style_share * 100.0, reset);
+        println!("{}│   imbalance:     {:.4} (w={:.2}, on top){}", color, dc.signals.turn_imbalance, weights.balance, reset);
+        println!("{}│   timing:        {:.4} (w={:.2}, on top){}", color, dc.signals.timing_irregularity, weights.timing, reset);
+        if let Some(timing) = &dc.timing {
+            println!("{}│     reply {:.1}s ± {:.1}s | rapid {} | silences {} (max gap {:.1}s){}",
+                color, timing.mean_latency_ms / 1000.0, timing.latency_std_ms / 1000.0,
+                timing.rapid_replies, timing.long_silences.len(), timing.max_gap_ms as f64 / 1000.0, reset);
+        }
+        if let Some(balance) = dc.balance.as_ref().filter(|b| b.imbalance() > 0.0) {
+            println!("{}│     {} has {:.0}% of words | length asym {:.2} | rapid alternation {:.2}{}",
+                color, balance.dominant.as_deref().unwrap_or("?"), balance.dominant_share * 100.0,
+                balance.length_asymmetry, balance.interruptions, reset);
+        }
+        if let Some(style) = &dc.style {
+            let least: Vec<String> = {
+                let mut categories: Vec<_> = style.categories.iter().collect();
+                categories.sort_by(|a, b| a.lsm.total_cmp(&b.lsm));
+                categories.iter().take(2).map(|c| format!("{} {:.2}", c.category, c.lsm)).collect()
+            };
+            println!("{}│ Style match (LSM): {:.2}  lowest: {}{}", color, style.lsm, least.join(", "), reset);
+        }
+        if !dc.turn_sentiment.is_empty() {
+            println!("{}│ Mood (last turns):{}", color, reset);
+            let skip = dc.turn_sentiment.len().saturating_sub(4);
+            for mood in dc.turn_sentiment.iter().skip(skip) {
+                println!("{}│   {:<8} valence {:+.2}  arousal {:.2}{}",
+                    color, mood.speaker, mood.valence, mood.arousal, reset);
+            }
+        }
+        if !dc.dropped_threads.is_empty() {
+            let dropped: Vec<String> = dc.dropped_threads.iter()
+                .map(|t| format!("{} ({})", t.referent, t.introduced_by))
+                .collect();
+            println!("{}│ Dropped threads: {}{}", color, dropped.join(", "), reset);
+        }
+        if dc.speaker_count > 2 {
+            println!("{}│ Group ({}):{}", color, dc.aggregation, reset);
+            for pair in &dc.matrix {
+                println!("{}│   {}–{}  ΔC {:.3}  n={}{}",
+                    color, pair.speakers[0], pair.speakers[1], pair.dc, pair.exchanges, reset);
+            }
+            for member in &dc.speaker_coherence {
+                println!("{}│   {:<8} ΔC {:.3} with group{}", color, member.speaker, member.dc, reset);
+            }
+        }
+        println!("{}│ Pairs (ΔC share):{}", color, reset);
+        let skip = dc.pairs.len().saturating_sub(4);
+        for pair in dc.pairs.iter().skip(skip) {
+            let text: String = pair.second_text.chars().take(24).collect();
+            println!("{}│   {}→{} {:+.3}  th {:.2} em {:.2} lo {:.2} qa {:.2}  \"{}\"{}",
+                color, pair.first_speaker, pair.second_speaker, pair.contribution,
+                pair.signals.thematic_drift, pair.signals.emotional_volatility,
+                pair.signals.logical_breaks, pair.signals.qa_mismatch, text, reset);
+        }
     } else {
         println!("{}│   Reason: {}{}", color, dc.reason, reset);
     }
+    let recent: Vec<String> = timeline.points.iter()
+        .skip(timeline.len().saturating_sub(8))
+        .map(|p| p.dc.map_or("—".to_string(), |v| format!("{:.2}", v)))
+        .collect();
+    println!("{}│ Timeline: {}{}", color, recent.join(" "), reset);
+    if output.scales.len() > 1 {
+        let scales: Vec<String> = output.scales.iter()
+            .map(|s| format!("{} {} ({})", s.scale, s.dc.display_value(), s.turn_count))
+            .collect();
+        println!("{}│ Scales: {}{}", color, scales.join(" | "), reset);
+    }
     println!("{}├─────────────────────────────────────┤{}", color, reset);
     println!("{}│ State: {} | Stable: {:.1}s{}", 
         color, output.state, output.stable_ms as f64 / 1000.0, reset);
@@ -541,7 +1301,7 @@ fn print_verbose_duo(
 }
 
 /// Run HTTP API server (Slice 5)
-async fn run_serve(args: &Args) {
+async fn run_serve(args: &Args, scorers: &Scorers) {
     println!();
     println!("╔═══════════════════════════════════════════════════════════╗");
     println!("║  🜂 PhaseLock Soul-0 API Server                            ║");
@@ -549,7 +1309,13 @@ async fn run_serve(args: &Args) {
     println!("╚═══════════════════════════════════════════════════════════╝");
     println!();
     
-    if let Err(e) = run_server(&args.addr, args.snapshot_dir.clone()).await {
+    let store = args.snapshot_store();
+    if let Some(secs) = args.compact_every.filter(|&secs| secs > 0) {
+        println!("Compacting {} every {}s (policy {})", store.location(), secs, args.compaction);
+        spawn_compaction_job(store.clone(), args.compaction, std::time::Duration::from_secs(secs));
+    }
+    
+    if let Err(e) = run_server_with_store(&args.addr, store, scorers.clone(), args.audit_dir.clone()).await {
         eprintln!("Server error: {}", e);
         std::process::exit(1);
     }
@@ -0,0 +1,245 @@
+//! Audit log types: NDJSON session recordings and replay results
+//!
+//! A recording is one JSON object per line. The first line is a `session`
+//! header with what is needed to rebuild the session; then, per turn, the
+//! input `turn` followed by the `state` and `dc` it produced, and `proof` /
+//! `snapshot` events when those were generated. Events of one turn share
+//! its sequence number.
+
+use serde::{Deserialize, Serialize};
+use crate::R_WEIGHT_INTENSITY;
+use crate::types::{
+    DcResult, StateOutput, WindowPolicy, FacelockState, RAggregation, ThematicModel,
+    GroupAggregation, DcWeights,
+};
+
+/// Session settings a replay needs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// Primary window policy
+    pub window: WindowPolicy,
+    /// Merge gap for same-speaker turns, in ms
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_gap_ms: Option<u64>,
+    /// Minimum turn confidence to start or sustain a lock
+    pub min_confidence: f64,
+    /// Scorer settings (None: custom scorers, or a log from before they were recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scorers: Option<ScorerConfig>,
+}
+
+/// Settings of the built-in r and ΔC parsers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScorerConfig {
+    /// Weight of the r intensity signal
+    pub intensity_weight: f64,
+    /// Sentence aggregation for r
+    pub r_aggregation: RAggregation,
+    /// Thematic drift model
+    pub thematic_model: ThematicModel,
+    /// Background IDF corpus file (one document per line)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corpus: Option<String>,
+    /// SHA-256 of the corpus file (hex), checked when the corpus is loaded again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corpus_sha256: Option<String>,
+    /// Group ΔC aggregation
+    pub group_aggregation: GroupAggregation,
+    /// Balance, style and timing weights
+    pub weights: DcWeights,
+}
+
+impl Default for ScorerConfig {
+    fn default() -> Self {
+        Self {
+            intensity_weight: R_WEIGHT_INTENSITY,
+            r_aggregation: RAggregation::default(),
+            thematic_model: ThematicModel::default(),
+            corpus: None,
+            corpus_sha256: None,
+            group_aggregation: GroupAggregation::default(),
+            weights: DcWeights::default(),
+        }
+    }
+}
+
+/// One line of an audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// First line: session identity and settings
+    Session {
+        session_id: String,
+        /// "duo" or "api"
+        mode: String,
+        /// soul0 version that recorded the log
+        version: String,
+        /// Wall-clock start (Unix ms)
+        started_unix_ms: i64,
+        config: Box<AuditConfig>,
+    },
+    /// Input turn, timed from the session start
+    Turn {
+        seq: u64,
+        offset_us: u64,
+        speaker: String,
+        text: String,
+    },
+    /// Engine output after the turn
+    State {
+        seq: u64,
+        output: Box<StateOutput>,
+    },
+    /// ΔC of the primary window after the turn
+    Dc {
+        seq: u64,
+        dc: Box<DcResult>,
+    },
+    /// Proof generated at the turn
+    Proof {
+        seq: u64,
+        proof_hex: String,
+    },
+    /// Snapshot generated with the proof
+    Snapshot {
+        seq: u64,
+        snapshot_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
+}
+
+/// A turn from a recording with the outputs recorded for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedTurn {
+    pub seq: u64,
+    /// Microseconds since the session start
+    pub offset_us: u64,
+    pub speaker: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<StateOutput>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dc: Option<DcResult>,
+    /// A proof was generated at this turn
+    pub proof: bool,
+}
+
+/// A parsed audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecording {
+    pub session_id: String,
+    pub mode: String,
+    pub version: String,
+    pub started_unix_ms: i64,
+    pub config: AuditConfig,
+    pub turns: Vec<RecordedTurn>,
+}
+
+/// Reason codes for reading audit logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub enum AuditReason {
+    /// Log could not be read
+    R500_AUDIT_READ_ERROR,
+    /// A line is not a valid audit event
+    R501_AUDIT_INVALID_EVENT,
+    /// The log does not start with a session header
+    R502_AUDIT_NO_SESSION,
+}
+
+impl AuditReason {
+    /// Get code string
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::R500_AUDIT_READ_ERROR => "R500_AUDIT_READ_ERROR",
+            Self::R501_AUDIT_INVALID_EVENT => "R501_AUDIT_INVALID_EVENT",
+            Self::R502_AUDIT_NO_SESSION => "R502_AUDIT_NO_SESSION",
+        }
+    }
+
+    /// Get description
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::R500_AUDIT_READ_ERROR => "Failed to read audit log",
+            Self::R501_AUDIT_INVALID_EVENT => "Invalid audit event",
+            Self::R502_AUDIT_NO_SESSION => "Audit log has no session header",
+        }
+    }
+}
+
+impl std::fmt::Display for AuditReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code(), self.description())
+    }
+}
+
+/// How fast a replay re-feeds turns (results do not depend on it)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ReplaySpeed {
+    /// Original pacing
+    Original,
+    /// N times faster than recorded
+    Factor(f64),
+    /// No waiting between turns
+    #[default]
+    Instant,
+}
+
+impl std::fmt::Display for ReplaySpeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplaySpeed::Original => write!(f, "original"),
+            ReplaySpeed::Factor(factor) => write!(f, "{}x", factor),
+            ReplaySpeed::Instant => write!(f, "instant"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReplaySpeed {
+    type Err = String;
+
+    /// Parse "original", "instant" or a factor ("10x", "2.5")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "original" | "realtime" | "1x" | "1" => Ok(ReplaySpeed::Original),
+            "instant" | "max" => Ok(ReplaySpeed::Instant),
+            other => match other.trim_end_matches('x').parse::<f64>() {
+                Ok(factor) if factor > 0.0 && factor.is_finite() => Ok(ReplaySpeed::Factor(factor)),
+                _ => Err(format!(
+                    "unknown replay speed '{}' (expected original, instant or a factor like 10x)", other
+                )),
+            },
+        }
+    }
+}
+
+/// One regenerated output that differs from the recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayDiff {
+    /// Turn sequence number
+    pub seq: u64,
+    /// "state", "reason", "r", "stable_ms", "dc", "dc_reason" or "proof"
+    pub field: String,
+    pub recorded: String,
+    pub replayed: String,
+}
+
+/// Result of replaying a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayReport {
+    pub session_id: String,
+    /// Turns re-fed
+    pub turns: usize,
+    /// Final state after the replay
+    pub final_state: FacelockState,
+    /// Every difference from the recorded outputs
+    pub diffs: Vec<ReplayDiff>,
+}
+
+impl ReplayReport {
+    /// Did the replay reproduce every recorded output?
+    pub fn is_clean(&self) -> bool {
+        self.diffs.is_empty()
+    }
+}
@@ -2,7 +2,9 @@
 //!
 //! Measures how aligned multiple speakers are in a conversation.
 
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
+use crate::types::WindowPolicy;
 
 /// ΔC signal weights from LLD (sum = 1.0)
 pub const DC_WEIGHT_THEMATIC: f64 = 0.31;
@@ -11,24 +13,85 @@ pub const DC_WEIGHT_LOGICAL: f64 = 0.22;
 pub const DC_WEIGHT_QA_MISMATCH: f64 = 0.12;
 pub const DC_WEIGHT_REFERENCE: f64 = 0.07;
 
+/// Turn-taking balance weight, added on top of the five LLD weights
+/// (result divided by 1.0 + weight); 0.0 (default) leaves ΔC unchanged and
+/// only reports the balance
+pub const DC_WEIGHT_BALANCE: f64 = 0.0;
+
+/// Turn-timing weight (latency spread, long silences), added on top like
+/// balance; 0.0 (default) leaves ΔC unchanged and only reports timing
+pub const DC_WEIGHT_TIMING: f64 = 0.0;
+
+/// Share of the thematic weight given to style mismatch (LSM), so speakers
+/// in sync on a changing topic are not scored as incoherent; 0.0 (default)
+/// keeps the topic-only thematic signal and only reports the style match
+pub const DC_STYLE_SHARE: f64 = 0.0;
+
+/// Parts of the turn-taking imbalance score (sum = 1.0)
+pub const BALANCE_WEIGHT_WORD_SHARE: f64 = 0.5;
+pub const BALANCE_WEIGHT_LENGTH: f64 = 0.3;
+pub const BALANCE_WEIGHT_INTERRUPTIONS: f64 = 0.2;
+
 /// ΔC thresholds
 pub const DC_THRESHOLD_LOCKED: f64 = 0.10;
 pub const DC_THRESHOLD_APPROACHING: f64 = 0.15;
 pub const DC_THRESHOLD_DRIFT: f64 = 0.20;
 
-/// Raw signals for ΔC calculation (5 signals per LLD)
+/// Raw signals for ΔC calculation (5 signals per LLD + balance, style and timing)
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DcSignals {
     /// Topic consistency across turns (weight: 0.31)
     pub thematic_drift: f64,
-    /// Sentiment swings between turns (weight: 0.28)
+    /// Valence/arousal swings between paired turns (weight: 0.28)
     pub emotional_volatility: f64,
     /// Abrupt topic switches (weight: 0.22)
     pub logical_breaks: f64,
     /// Questions without relevant answers (weight: 0.12)
     pub qa_mismatch: f64,
-    /// Topics that disappear (weight: 0.07)
+    /// Referents raised and never picked up (weight: 0.07)
     pub reference_decay: f64,
+    /// One speaker dominating the turn-taking (weight: 0.0, on top)
+    #[serde(default)]
+    pub turn_imbalance: f64,
+    /// 1 - function-word style matching (shares the thematic weight: 0% by default)
+    #[serde(default)]
+    pub style_mismatch: f64,
+    /// Irregular reply latency and long silences (weight: 0.0, on top)
+    #[serde(default)]
+    pub timing_irregularity: f64,
+}
+
+/// Weights of the signals added after the LLD
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DcWeights {
+    /// Turn-taking imbalance, on top of the five LLD weights
+    pub balance: f64,
+    /// Share of the thematic weight given to style mismatch (0.0-1.0)
+    pub style_share: f64,
+    /// Turn-timing irregularity, on top of the five LLD weights
+    #[serde(default)]
+    pub timing: f64,
+}
+
+impl Default for DcWeights {
+    fn default() -> Self {
+        Self {
+            balance: DC_WEIGHT_BALANCE,
+            style_share: DC_STYLE_SHARE,
+            timing: DC_WEIGHT_TIMING,
+        }
+    }
+}
+
+impl DcWeights {
+    /// Only the five LLD signals (original ΔC)
+    pub fn lld() -> Self {
+        Self {
+            balance: 0.0,
+            style_share: 0.0,
+            timing: 0.0,
+        }
+    }
 }
 
 impl DcSignals {
@@ -37,13 +100,31 @@ impl DcSignals {
         Self::default()
     }
     
-    /// Calculate weighted sum
+    /// Calculate weighted sum (default weights)
     pub fn weighted_sum(&self) -> f64 {
-        self.thematic_drift * DC_WEIGHT_THEMATIC
+        self.weighted_sum_with(&DcWeights::default())
+    }
+    
+    /// Calculate weighted sum with custom balance, style and timing weights
+    pub fn weighted_sum_with(&self, weights: &DcWeights) -> f64 {
+        let share = weights.style_share.clamp(0.0, 1.0);
+        let thematic = if share == 0.0 {
+            self.thematic_drift
+        } else {
+            (1.0 - share) * self.thematic_drift + share * self.style_mismatch
+        };
+        let lld = thematic * DC_WEIGHT_THEMATIC
             + self.emotional_volatility * DC_WEIGHT_EMOTIONAL
             + self.logical_breaks * DC_WEIGHT_LOGICAL
             + self.qa_mismatch * DC_WEIGHT_QA_MISMATCH
-            + self.reference_decay * DC_WEIGHT_REFERENCE
+            + self.reference_decay * DC_WEIGHT_REFERENCE;
+        let extra_weight = weights.balance + weights.timing;
+        if extra_weight == 0.0 {
+            return lld;
+        }
+        let extra = self.turn_imbalance * weights.balance
+            + self.timing_irregularity * weights.timing;
+        (lld + extra) / (1.0 + extra_weight)
     }
 }
 
@@ -60,6 +141,361 @@ pub struct DcResult {
     pub pair_count: usize,
     /// Number of speakers
     pub speaker_count: usize,
+    /// Sentiment per logical turn in the window, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub turn_sentiment: Vec<TurnSentiment>,
+    /// Topics raised by one speaker that nobody else picked up
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dropped_threads: Vec<ReferentThread>,
+    /// Per-pair breakdown, oldest pair first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pairs: Vec<PairContribution>,
+    /// How `value` was aggregated over speaker pairs
+    #[serde(default)]
+    pub aggregation: GroupAggregation,
+    /// ΔC per speaker pair (coherence matrix), in order of first exchange
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matrix: Vec<SpeakerPairDc>,
+    /// ΔC of each speaker with the group, in order of first turn
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub speaker_coherence: Vec<SpeakerCoherence>,
+    /// Turn-taking balance behind `signals.turn_imbalance`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<TurnBalance>,
+    /// Style matching between speakers over the whole window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<StyleMatch>,
+    /// Reply latency and silences (None without timestamps)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<TimingStats>,
+}
+
+/// Reply latency and silences over the window
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimingStats {
+    /// Mean gap before a reply by another speaker
+    pub mean_latency_ms: f64,
+    /// Standard deviation of reply gaps
+    pub latency_std_ms: f64,
+    /// Longest gap between any two turns
+    pub max_gap_ms: u64,
+    /// Gaps measured (consecutive turns with timestamps)
+    pub gaps: usize,
+    /// Replies faster than one second
+    pub rapid_replies: usize,
+    /// Gaps of ten seconds or more
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub long_silences: Vec<SilenceEvent>,
+}
+
+impl TimingStats {
+    /// 0.0 (steady rhythm) … 1.0 (erratic replies, long silences):
+    /// half latency spread (coefficient of variation, capped at 1),
+    /// half share of gaps that were long silences
+    pub fn irregularity(&self) -> f64 {
+        let spread = if self.mean_latency_ms < 1.0 {
+            0.0
+        } else {
+            (self.latency_std_ms / self.mean_latency_ms).min(1.0)
+        };
+        let silences = if self.gaps == 0 {
+            0.0
+        } else {
+            self.long_silences.len() as f64 / self.gaps as f64
+        };
+        (0.5 * spread + 0.5 * silences).clamp(0.0, 1.0)
+    }
+}
+
+/// A long pause in the conversation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SilenceEvent {
+    /// Window index of the turn before the pause
+    pub after_turn: usize,
+    /// Speaker who broke the silence
+    pub broken_by: String,
+    /// Length of the pause
+    pub gap_ms: u64,
+}
+
+/// Function-word style matching (LSM) between speakers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StyleMatch {
+    /// Mean over categories: 0.0 (different style) … 1.0 (same style)
+    pub lsm: f64,
+    /// LSM per function-word category
+    pub categories: Vec<StyleCategoryMatch>,
+}
+
+/// LSM of one function-word category
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StyleCategoryMatch {
+    /// Category name ("articles", "negations", …)
+    pub category: String,
+    /// 0.0 … 1.0
+    pub lsm: f64,
+}
+
+/// Who does the talking: parts of the turn-taking imbalance
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TurnBalance {
+    /// Most talkative speaker's word share above an even split (0.0-1.0)
+    pub word_share: f64,
+    /// 1 - shortest / longest mean turn length per speaker
+    pub length_asymmetry: f64,
+    /// Share of timed speaker changes that came within a second (rapid alternation)
+    pub interruptions: f64,
+    /// Speaker with the most words
+    pub dominant: Option<String>,
+    /// That speaker's share of all words
+    pub dominant_share: f64,
+}
+
+impl TurnBalance {
+    /// Combined imbalance: 0.0 (even) … 1.0 (one-sided)
+    pub fn imbalance(&self) -> f64 {
+        (self.word_share * BALANCE_WEIGHT_WORD_SHARE
+            + self.length_asymmetry * BALANCE_WEIGHT_LENGTH
+            + self.interruptions * BALANCE_WEIGHT_INTERRUPTIONS)
+            .clamp(0.0, 1.0)
+    }
+}
+
+/// How thematic drift is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThematicModel {
+    /// TF-IDF cosine between turns and against the window so far
+    #[default]
+    TfIdf,
+    /// Original heuristic: Jaccard overlap of raw lowercased words (> 2 chars)
+    Jaccard,
+}
+
+impl std::fmt::Display for ThematicModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThematicModel::TfIdf => write!(f, "tfidf"),
+            ThematicModel::Jaccard => write!(f, "jaccard"),
+        }
+    }
+}
+
+impl std::str::FromStr for ThematicModel {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "tfidf" | "tf-idf" => Ok(ThematicModel::TfIdf),
+            "jaccard" => Ok(ThematicModel::Jaccard),
+            other => Err(format!("unknown thematic model '{}' (expected tfidf or jaccard)", other)),
+        }
+    }
+}
+
+/// How ΔC of a group (more than two speakers) is aggregated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupAggregation {
+    /// Mean over all exchanges (original ΔC)
+    #[default]
+    Mean,
+    /// ΔC of the least coherent speaker pair
+    WorstPair,
+    /// Median of speaker-pair ΔC
+    Median,
+}
+
+impl std::fmt::Display for GroupAggregation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupAggregation::Mean => write!(f, "mean"),
+            GroupAggregation::WorstPair => write!(f, "worst-pair"),
+            GroupAggregation::Median => write!(f, "median"),
+        }
+    }
+}
+
+impl std::str::FromStr for GroupAggregation {
+    type Err = String;
+    
+    /// Parse "mean", "worst-pair" or "median"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "mean" => Ok(GroupAggregation::Mean),
+            "worst-pair" | "worst_pair" | "worst" | "max" => Ok(GroupAggregation::WorstPair),
+            "median" => Ok(GroupAggregation::Median),
+            other => Err(format!(
+                "unknown group aggregation '{}' (expected mean, worst-pair or median)", other
+            )),
+        }
+    }
+}
+
+/// ΔC between two speakers, over the exchanges between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerPairDc {
+    /// The two speakers, in order of first appearance
+    pub speakers: [String; 2],
+    /// ΔC over their exchanges (0.0-1.0)
+    pub dc: f64,
+    /// Exchanges between them in the window (either direction)
+    pub exchanges: usize,
+    /// Mean signals over those exchanges
+    pub signals: DcSignals,
+}
+
+impl SpeakerPairDc {
+    /// Is this the pair of `a` and `b` (either order)?
+    pub fn is_pair(&self, a: &str, b: &str) -> bool {
+        (self.speakers[0] == a && self.speakers[1] == b)
+            || (self.speakers[0] == b && self.speakers[1] == a)
+    }
+}
+
+/// ΔC of one speaker with the rest of the group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerCoherence {
+    /// Speaker ID
+    pub speaker: String,
+    /// ΔC over every exchange the speaker took part in (0.0-1.0)
+    pub dc: f64,
+    /// Exchanges the speaker took part in
+    pub exchanges: usize,
+}
+
+/// One exchange's signals and its share of ΔC
+///
+/// Turn positions are indices into the window's logical turns (the raw
+/// turns unless the window merges same-speaker bursts). Reference decay, turn
+/// imbalance and timing are measured over the whole window, so every pair
+/// carries the same values. Every signal is clamped per pair, so the
+/// contributions of all pairs add up to the window's ΔC (mean aggregation).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairContribution {
+    /// Window index of the first turn
+    pub first: usize,
+    /// Window index of the reply
+    pub second: usize,
+    pub first_speaker: Arc<str>,
+    pub second_speaker: Arc<str>,
+    pub first_text: Arc<str>,
+    pub second_text: Arc<str>,
+    /// The signals for this pair alone
+    pub signals: DcSignals,
+    /// This pair's share of ΔC
+    pub contribution: f64,
+    /// Gap before the reply (None without timestamps)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+}
+
+/// One point of a session's ΔC history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DcTimelinePoint {
+    /// Session turn number (1 = first turn)
+    pub turn: usize,
+    /// Speaker of that turn
+    pub speaker: String,
+    /// ΔC after the turn, or None if UNKNOWN
+    pub dc: Option<f64>,
+    /// Reason code after the turn
+    pub reason: DcReason,
+}
+
+/// ΔC after every turn of a session (not limited to the rolling window)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DcTimeline {
+    pub points: Vec<DcTimelinePoint>,
+}
+
+impl DcTimeline {
+    /// Create empty timeline
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Record ΔC after a turn
+    pub fn record(&mut self, speaker: impl Into<String>, dc: &DcResult) {
+        self.points.push(DcTimelinePoint {
+            turn: self.points.len() + 1,
+            speaker: speaker.into(),
+            dc: dc.value,
+            reason: dc.reason,
+        });
+    }
+    
+    /// Number of recorded turns
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+    
+    /// Is the timeline empty?
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+    
+    /// Turn with the highest known ΔC
+    pub fn peak(&self) -> Option<&DcTimelinePoint> {
+        self.points.iter()
+            .filter(|p| p.dc.is_some())
+            .max_by(|a, b| a.dc.unwrap_or(0.0).total_cmp(&b.dc.unwrap_or(0.0)))
+    }
+    
+    /// Largest ΔC rise from one turn to the next (the turn that drove it up)
+    pub fn biggest_rise(&self) -> Option<(&DcTimelinePoint, f64)> {
+        self.points.windows(2)
+            .filter_map(|w| match (w[0].dc, w[1].dc) {
+                (Some(before), Some(after)) if after > before => Some((&w[1], after - before)),
+                _ => None,
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+/// ΔC over one scale of a `MultiScaleWindow`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleDc {
+    /// Scale name ("short", "medium", "session")
+    pub scale: String,
+    /// Which turns the scale keeps
+    pub policy: WindowPolicy,
+    /// Turns in the scale's window
+    pub turn_count: usize,
+    /// ΔC over that window
+    pub dc: DcResult,
+}
+
+/// A referent (content noun or name) followed through the window
+///
+/// Turn positions are indices into the window's logical turns, oldest = 0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferentThread {
+    /// The referent as first written
+    pub referent: String,
+    /// Speaker who raised it
+    pub introduced_by: String,
+    /// Turn where it was raised
+    pub introduced_at: usize,
+    /// Speaker who mentioned it last
+    pub last_mentioned_by: String,
+    /// Turn where it was mentioned last
+    pub last_mentioned_at: usize,
+    /// Number of turns mentioning it
+    pub mentions: usize,
+    /// Mentioned by a speaker other than the one who raised it
+    pub picked_up: bool,
+}
+
+/// Mood of one turn, to see who shifted it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnSentiment {
+    /// Speaker ID
+    pub speaker: Arc<str>,
+    /// Signed mood: -1.0 (negative) … +1.0 (positive)
+    pub valence: f64,
+    /// Agitation: 0.0 (calm) … 1.0 (agitated)
+    pub arousal: f64,
 }
 
 impl DcResult {
@@ -67,8 +503,6 @@ impl DcResult {
     pub fn success(value: f64, signals: DcSignals, pair_count: usize, speaker_count: usize) -> Self {
         let reason = if value < DC_THRESHOLD_LOCKED {
             DcReason::R015_DC_LOW_COHERENT
-        } else if value < DC_THRESHOLD_APPROACHING {
-            DcReason::R010_DC_COMPUTED
         } else if value < DC_THRESHOLD_DRIFT {
             DcReason::R010_DC_COMPUTED
         } else {
@@ -81,6 +515,15 @@ impl DcResult {
             reason,
             pair_count,
             speaker_count,
+            turn_sentiment: Vec::new(),
+            dropped_threads: Vec::new(),
+            pairs: Vec::new(),
+            aggregation: GroupAggregation::default(),
+            matrix: Vec::new(),
+            speaker_coherence: Vec::new(),
+            balance: None,
+            style: None,
+            timing: None,
         }
     }
     
@@ -92,6 +535,15 @@ impl DcResult {
             reason,
             pair_count: 0,
             speaker_count: 0,
+            turn_sentiment: Vec::new(),
+            dropped_threads: Vec::new(),
+            pairs: Vec::new(),
+            aggregation: GroupAggregation::default(),
+            matrix: Vec::new(),
+            speaker_coherence: Vec::new(),
+            balance: None,
+            style: None,
+            timing: None,
         }
     }
     
@@ -105,6 +557,11 @@ impl DcResult {
         self.value.unwrap_or(0.0)
     }
     
+    /// ΔC between two speakers, if they exchanged turns in the window
+    pub fn pair_dc(&self, a: &str, b: &str) -> Option<f64> {
+        self.matrix.iter().find(|p| p.is_pair(a, b)).map(|p| p.dc)
+    }
+    
     /// Format for display
     pub fn display_value(&self) -> String {
         match self.value {
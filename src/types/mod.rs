@@ -8,12 +8,16 @@ mod turn;
 mod dc;
 mod proof;
 mod snapshot;
+mod transcript;
+mod audit;
 
 pub use state::FacelockState;
-pub use signals::{RSignals, RValue, LanguageHits};
+pub use signals::{RSignals, RValue, LanguageHits, SentenceR, RAggregation};
 pub use output::StateOutput;
 pub use reason::ReasonCode;
-pub use turn::{Turn, TurnPair, ConversationWindow, WINDOW_DURATION_SECS, MAX_TURNS_PER_SPEAKER};
-pub use dc::{DcSignals, DcResult, DcReason, DC_THRESHOLD_LOCKED, DC_THRESHOLD_APPROACHING, DC_THRESHOLD_DRIFT};
+pub use turn::{Turn, TurnPair, ConversationWindow, WindowPolicy, WindowScale, MultiScaleWindow, MEDIUM_WINDOW_SECS, SESSION_WINDOW_TURNS, WINDOW_DURATION_SECS, MAX_TURNS_PER_SPEAKER, MERGED_TURN_SEPARATOR, MAX_MERGE_GAP_SECS, merge_gap_from_secs};
+pub use dc::{DcSignals, DcResult, DcReason, TurnSentiment, ReferentThread, PairContribution, DcTimeline, DcTimelinePoint, ScaleDc, GroupAggregation, ThematicModel, SpeakerPairDc, SpeakerCoherence, TurnBalance, StyleMatch, StyleCategoryMatch, DcWeights, TimingStats, SilenceEvent, DC_WEIGHT_BALANCE, DC_WEIGHT_TIMING, DC_STYLE_SHARE, DC_THRESHOLD_LOCKED, DC_THRESHOLD_APPROACHING, DC_THRESHOLD_DRIFT};
 pub use proof::{Proof, ProofPayload, ProofResult, ProofReason};
-pub use snapshot::{Snapshot, SeenContent, BlindSpot, BlindSpotCategory, HorizonItem, SnapshotResult, SnapshotReason, CompactionSummary};
+pub use snapshot::{Snapshot, SeenContent, BlindSpot, BlindSpotCategory, ConflictAvoidance, CONFLICT_WEIGHT_TOPIC_ESCAPE, CONFLICT_WEIGHT_HEDGED, CONFLICT_WEIGHT_DROPPED, CONFLICT_MAX_CONFIDENCE, CONFLICT_SINGLE_CUE_MAX, HorizonItem, SnapshotResult, SnapshotReason, CompactionSummary, CompactionPeriod, CompactionPolicy, CompactionReport, SnapshotQuery, MigrationReport, SearchHit, ValidationFailure, ValidationReport};
+pub use transcript::{Transcript, TranscriptTurn, TranscriptFormat, TranscriptReport, TurnReport, ImportReason, RttmTurn, SpeechSegment, SpeechWord, UNTIMED_TURN_SPACING_MS};
+pub use audit::{AuditConfig, ScorerConfig, AuditEvent, AuditRecording, RecordedTurn, AuditReason, ReplaySpeed, ReplayDiff, ReplayReport};
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-use crate::types::{FacelockState, ReasonCode};
+use crate::types::{FacelockState, ReasonCode, SentenceR, ScaleDc};
 
 /// Output structure for each state update
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +19,12 @@ pub struct StateOutput {
     pub reason: ReasonCode,
     /// Is proof available?
     pub proof_available: bool,
+    /// Per-sentence r of the last input (sentence-level aggregation only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sentences: Vec<SentenceR>,
+    /// ΔC per window scale, primary first (duo mode)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scales: Vec<ScaleDc>,
 }
 
 impl StateOutput {
@@ -36,9 +42,23 @@ impl StateOutput {
             stable_ms,
             reason,
             proof_available: state == FacelockState::Locked,
+            sentences: Vec::new(),
+            scales: Vec::new(),
         }
     }
     
+    /// Attach the per-sentence r breakdown of the input
+    pub fn with_sentences(mut self, sentences: Vec<SentenceR>) -> Self {
+        self.sentences = sentences;
+        self
+    }
+    
+    /// Attach ΔC of every window scale
+    pub fn with_scales(mut self, scales: Vec<ScaleDc>) -> Self {
+        self.scales = scales;
+        self
+    }
+    
     /// Format for terminal display (with colors)
     pub fn to_terminal_string(&self) -> String {
         let color = self.state.color_code();
@@ -60,6 +60,14 @@ pub enum ReasonCode {
     R005_TRANSITION_RECOVERING,
     /// Staying in current state
     R005_STATE_MAINTAINED,
+    
+    // =========================================================================
+    // R006: Confidence
+    // =========================================================================
+    /// Turn confidence below minimum, cannot start or sustain lock
+    R006_CONFIDENCE_BELOW_MIN,
+    /// Low-confidence turn damped, transition withheld
+    R006_CONFIDENCE_DAMPED,
 }
 
 impl ReasonCode {
@@ -83,6 +91,8 @@ impl ReasonCode {
             Self::R005_TRANSITION_TO_DRIFT => "R005_TRANSITION_TO_DRIFT",
             Self::R005_TRANSITION_RECOVERING => "R005_TRANSITION_RECOVERING",
             Self::R005_STATE_MAINTAINED => "R005_STATE_MAINTAINED",
+            Self::R006_CONFIDENCE_BELOW_MIN => "R006_CONFIDENCE_BELOW_MIN",
+            Self::R006_CONFIDENCE_DAMPED => "R006_CONFIDENCE_DAMPED",
         }
     }
     
@@ -106,6 +116,8 @@ impl ReasonCode {
             Self::R005_TRANSITION_TO_DRIFT => "Entering DRIFT state",
             Self::R005_TRANSITION_RECOVERING => "Recovering from drift",
             Self::R005_STATE_MAINTAINED => "State unchanged",
+            Self::R006_CONFIDENCE_BELOW_MIN => "Turn too short to start or sustain lock",
+            Self::R006_CONFIDENCE_DAMPED => "Low-confidence turn, transition withheld",
         }
     }
 }
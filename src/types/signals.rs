@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
-/// Raw signals extracted from text (7 signals per LLD)
+/// Raw signals extracted from text (7 lexical signals per LLD + intensity)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RSignals {
     /// Density of I/me/my/mine (weight: 2.8)
@@ -20,6 +20,9 @@ pub struct RSignals {
     pub judgment: f64,
     /// now/immediately/quickly/hurry (weight: 2.6)
     pub urgency: f64,
+    /// CAPS, !!!/?! runs, sooo elongation, emphatic emoji (weight: 2.0)
+    #[serde(default)]
+    pub intensity: f64,
     /// Debug: language pattern hits (EN vs NL)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language_hits: Option<LanguageHits>,
@@ -43,6 +46,7 @@ impl RSignals {
             comparison: 0.0,
             judgment: 0.0,
             urgency: 0.0,
+            intensity: 0.0,
             language_hits: None,
         }
     }
@@ -61,6 +65,108 @@ pub struct RValue {
     pub timestamp: DateTime<Utc>,
     /// Word count of input
     pub word_count: usize,
+    /// Per-sentence breakdown (only with a sentence-level aggregation)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sentences: Vec<SentenceR>,
+}
+
+/// r of one sentence within a turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentenceR {
+    /// Sentence text (trimmed)
+    pub text: String,
+    /// r value and signals of this sentence alone
+    pub r: RValue,
+}
+
+/// How per-sentence r values combine into the r of a turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RAggregation {
+    /// Score the turn as one bag of words (original behaviour)
+    #[default]
+    Whole,
+    /// Mean of sentence r
+    Mean,
+    /// Highest sentence r
+    Max,
+    /// Mean of sentence r weighted by word count
+    LengthWeighted,
+    /// Mean of the k highest sentence r
+    TopK(usize),
+}
+
+impl RAggregation {
+    /// Is the turn split into sentences?
+    pub fn is_sentence_level(&self) -> bool {
+        *self != RAggregation::Whole
+    }
+    
+    /// Combine sentence r values (with word counts) into one r
+    pub fn combine(&self, sentences: &[(f64, usize)]) -> f64 {
+        if sentences.is_empty() {
+            return 0.0;
+        }
+        let n = sentences.len() as f64;
+        
+        match *self {
+            RAggregation::Whole | RAggregation::Mean => {
+                sentences.iter().map(|(r, _)| r).sum::<f64>() / n
+            }
+            RAggregation::Max => {
+                sentences.iter().map(|(r, _)| *r).fold(0.0, f64::max)
+            }
+            RAggregation::LengthWeighted => {
+                let words: usize = sentences.iter().map(|(_, wc)| wc).sum();
+                if words == 0 {
+                    return 0.0;
+                }
+                sentences.iter().map(|(r, wc)| r * *wc as f64).sum::<f64>() / words as f64
+            }
+            RAggregation::TopK(k) => {
+                let mut values: Vec<f64> = sentences.iter().map(|(r, _)| *r).collect();
+                values.sort_by(|a, b| b.total_cmp(a));
+                let k = k.clamp(1, values.len());
+                values[..k].iter().sum::<f64>() / k as f64
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for RAggregation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RAggregation::Whole => write!(f, "whole"),
+            RAggregation::Mean => write!(f, "mean"),
+            RAggregation::Max => write!(f, "max"),
+            RAggregation::LengthWeighted => write!(f, "length-weighted"),
+            RAggregation::TopK(k) => write!(f, "top-{}", k),
+        }
+    }
+}
+
+impl std::str::FromStr for RAggregation {
+    type Err = String;
+    
+    /// Parse "whole", "mean", "max", "length-weighted" or "top-k" (e.g. "top-2")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        match s.as_str() {
+            "whole" => Ok(RAggregation::Whole),
+            "mean" => Ok(RAggregation::Mean),
+            "max" => Ok(RAggregation::Max),
+            "length-weighted" | "length_weighted" | "weighted" => Ok(RAggregation::LengthWeighted),
+            _ => s
+                .strip_prefix("top-")
+                .or_else(|| s.strip_prefix("top"))
+                .and_then(|k| k.parse::<usize>().ok())
+                .filter(|k| *k > 0)
+                .map(RAggregation::TopK)
+                .ok_or_else(|| format!(
+                    "unknown aggregation '{}' (expected whole, mean, max, length-weighted or top-N)", s
+                )),
+        }
+    }
 }
 
 impl RValue {
@@ -72,6 +178,7 @@ impl RValue {
             confidence,
             timestamp: Utc::now(),
             word_count,
+            sentences: Vec::new(),
         }
     }
 }
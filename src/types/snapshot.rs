@@ -5,7 +5,9 @@
 //! - Contains: seen, blind_spots, horizon
 //! - Compaction rules for aging data
 
+use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
+use crate::types::TimingStats;
 
 /// A complete snapshot of a Facelock moment
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,10 +36,29 @@ pub struct Snapshot {
     pub observers: Vec<String>,
     /// Number of turns in window
     pub turn_count: u32,
+    /// Reply latency and silences in the window (None without timestamps)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<TimingStats>,
+}
+
+impl Snapshot {
+    /// Session ID as hex
+    pub fn session_hex(&self) -> String {
+        hex(&self.session_id)
+    }
+    
+    /// Proof hash as hex
+    pub fn proof_hash_hex(&self) -> String {
+        hex(&self.proof_hash)
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
 
 /// Content that was observed
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SeenContent {
     /// Main themes discussed
     pub themes: Vec<String>,
@@ -49,17 +70,6 @@ pub struct SeenContent {
     pub summary: Option<String>,
 }
 
-impl Default for SeenContent {
-    fn default() -> Self {
-        Self {
-            themes: Vec::new(),
-            emotion: None,
-            keywords: Vec::new(),
-            summary: None,
-        }
-    }
-}
-
 /// Something structurally invisible during the session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlindSpot {
@@ -105,6 +115,8 @@ pub enum BlindSpotCategory {
     NoSensoryDetail,
     /// No reflection on the conversation itself
     NoMetaAwareness,
+    /// One speaker does most of the talking
+    OneVoiceDominant,
 }
 
 impl BlindSpotCategory {
@@ -125,6 +137,7 @@ impl BlindSpotCategory {
             Self::HighAbstraction => "High abstraction, no concrete examples",
             Self::NoSensoryDetail => "No sensory details mentioned",
             Self::NoMetaAwareness => "No meta-awareness of the conversation",
+            Self::OneVoiceDominant => "One voice dominates the turn-taking",
         }
     }
     
@@ -145,6 +158,59 @@ impl BlindSpotCategory {
             Self::HighAbstraction => "How does this truth appear in everyday life?",
             Self::NoSensoryDetail => "What sights, sounds, or textures accompanied this?",
             Self::NoMetaAwareness => "What is aware of this awareness?",
+            Self::OneVoiceDominant => "What would the quieter voice have said?",
+        }
+    }
+}
+
+/// Evidence per disagreement answered with a change of topic
+pub const CONFLICT_WEIGHT_TOPIC_ESCAPE: f64 = 0.85;
+/// Evidence per challenge that was hedged or left unanswered
+pub const CONFLICT_WEIGHT_HEDGED: f64 = 1.61;
+/// Evidence per judgment spike nobody took up
+pub const CONFLICT_WEIGHT_DROPPED: f64 = 0.92;
+/// Highest confidence the lexical cues can support
+pub const CONFLICT_MAX_CONFIDENCE: f64 = 0.89;
+/// Cues needed before the confidence can pass `CONFLICT_SINGLE_CUE_MAX`
+const CONFLICT_MIN_CUES: usize = 2;
+/// Highest confidence of a single cue (below the snapshot's reporting threshold, 0.5)
+pub const CONFLICT_SINGLE_CUE_MAX: f64 = 0.375;
+
+/// Signs that a disagreement was raised and then sidestepped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictAvoidance {
+    /// Disagreements answered with an abrupt change of topic
+    pub topic_escapes: usize,
+    /// Challenges answered with a hedge ("maybe", "laat maar") or not answered
+    pub hedged_challenges: usize,
+    /// Judgment spikes no other speaker took up
+    pub dropped_judgments: usize,
+}
+
+impl ConflictAvoidance {
+    /// Cues found, of every kind
+    pub fn cues(&self) -> usize {
+        self.topic_escapes + self.hedged_challenges + self.dropped_judgments
+    }
+    
+    /// Confidence that conflict was avoided: 0.0 (no cues) … 0.89
+    ///
+    /// Each cue adds evidence and the evidence saturates (noisy-OR). The
+    /// weights and both caps are fitted to the labelled EN/NL conversations in
+    /// `core::conflict`'s tests: a cue's weight is `-ln(1 - p)`, with `p` the
+    /// share of conversations showing the cue that were labelled sidestepped
+    /// (Laplace-smoothed), and the caps are that share for one cue (0.375)
+    /// and for two or more (0.89). Two cues give 0.8 or more. The set is
+    /// small, so treat the values as a first calibration.
+    pub fn confidence(&self) -> f64 {
+        let evidence = self.topic_escapes as f64 * CONFLICT_WEIGHT_TOPIC_ESCAPE
+            + self.hedged_challenges as f64 * CONFLICT_WEIGHT_HEDGED
+            + self.dropped_judgments as f64 * CONFLICT_WEIGHT_DROPPED;
+        let confidence = (1.0 - (-evidence).exp()).min(CONFLICT_MAX_CONFIDENCE);
+        if self.cues() < CONFLICT_MIN_CUES {
+            confidence.min(CONFLICT_SINGLE_CUE_MAX)
+        } else {
+            confidence
         }
     }
 }
@@ -208,6 +274,8 @@ pub enum SnapshotReason {
     R304_SNAPSHOT_STORAGE_ERROR,
     /// Invalid proof link (corrupt or tampered)
     R305_SNAPSHOT_INVALID_PROOF_LINK,
+    /// No snapshot with this ID in the store
+    R306_SNAPSHOT_NOT_FOUND,
 }
 
 impl SnapshotReason {
@@ -220,6 +288,7 @@ impl SnapshotReason {
             Self::R303_SNAPSHOT_SERIALIZE_ERROR => "R303_SNAPSHOT_SERIALIZE_ERROR",
             Self::R304_SNAPSHOT_STORAGE_ERROR => "R304_SNAPSHOT_STORAGE_ERROR",
             Self::R305_SNAPSHOT_INVALID_PROOF_LINK => "R305_SNAPSHOT_INVALID_PROOF_LINK",
+            Self::R306_SNAPSHOT_NOT_FOUND => "R306_SNAPSHOT_NOT_FOUND",
         }
     }
     
@@ -232,6 +301,7 @@ impl SnapshotReason {
             Self::R303_SNAPSHOT_SERIALIZE_ERROR => "Failed to serialize snapshot",
             Self::R304_SNAPSHOT_STORAGE_ERROR => "Failed to store snapshot",
             Self::R305_SNAPSHOT_INVALID_PROOF_LINK => "Invalid proof link (corrupt or tampered)",
+            Self::R306_SNAPSHOT_NOT_FOUND => "Snapshot not found",
         }
     }
 }
@@ -245,6 +315,9 @@ impl std::fmt::Display for SnapshotReason {
 /// Compaction summary (for aging snapshots)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompactionSummary {
+    /// Day, week or month rolled up
+    #[serde(default)]
+    pub period: CompactionPeriod,
     /// Period covered
     pub period_start: i64,
     pub period_end: i64,
@@ -260,4 +333,228 @@ pub struct CompactionSummary {
     pub resolved_blind_spots: Vec<String>,
     /// New patterns detected
     pub patterns: Vec<String>,
+    /// IDs of the compacted snapshots
+    #[serde(default)]
+    pub snapshot_ids: Vec<String>,
+    /// Proof hashes of the compacted snapshots (hex), so the summary stays traceable
+    #[serde(default)]
+    pub proof_hashes: Vec<String>,
+    /// Snapshots per seen theme (lowercase), for folding into a coarser summary
+    #[serde(default)]
+    pub theme_counts: BTreeMap<String, u32>,
+    /// Snapshots per blind-spot category, in order of first appearance
+    #[serde(default)]
+    pub blind_spot_counts: Vec<(BlindSpotCategory, u32)>,
+    /// Blind spots of the latest snapshot (those not resolved by the period's end)
+    #[serde(default)]
+    pub final_blind_spots: Vec<BlindSpotCategory>,
+}
+
+/// Length of the period a compaction summary covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompactionPeriod {
+    /// One UTC day
+    #[default]
+    Daily,
+    /// One ISO week (Monday to Sunday, UTC)
+    Weekly,
+    /// One calendar month (UTC)
+    Monthly,
+}
+
+impl std::fmt::Display for CompactionPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompactionPeriod::Daily => write!(f, "daily"),
+            CompactionPeriod::Weekly => write!(f, "weekly"),
+            CompactionPeriod::Monthly => write!(f, "monthly"),
+        }
+    }
+}
+
+/// From what age (in days) snapshots are rolled into each period
+///
+/// A period is only compacted once all of it is past its age, so a summary
+/// never misses snapshots that arrive later in the same day, week or month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactionPolicy {
+    /// Roll into daily summaries after this many days
+    pub daily_after_days: Option<u32>,
+    /// Roll into weekly summaries after this many days
+    pub weekly_after_days: Option<u32>,
+    /// Roll into monthly summaries after this many days
+    pub monthly_after_days: Option<u32>,
+}
+
+impl CompactionPolicy {
+    /// Daily after a week, weekly after a month, monthly after a quarter
+    pub fn standard() -> Self {
+        Self {
+            daily_after_days: Some(7),
+            weekly_after_days: Some(30),
+            monthly_after_days: Some(90),
+        }
+    }
+    
+    /// Age for a period, if that period is used
+    pub fn after_days(&self, period: CompactionPeriod) -> Option<u32> {
+        match period {
+            CompactionPeriod::Daily => self.daily_after_days,
+            CompactionPeriod::Weekly => self.weekly_after_days,
+            CompactionPeriod::Monthly => self.monthly_after_days,
+        }
+    }
+}
+
+impl Default for CompactionPolicy {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl std::fmt::Display for CompactionPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = [
+            self.daily_after_days.map(|v| format!("daily={}", v)),
+            self.weekly_after_days.map(|v| format!("weekly={}", v)),
+            self.monthly_after_days.map(|v| format!("monthly={}", v)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        
+        if parts.is_empty() {
+            write!(f, "off")
+        } else {
+            write!(f, "{}", parts.join(","))
+        }
+    }
+}
+
+impl std::str::FromStr for CompactionPolicy {
+    type Err = String;
+    
+    /// Parse comma-separated ages in days, e.g. "daily=7,weekly=30,monthly=90" or "weekly=14";
+    /// "standard" is 7 / 30 / 90, "off" never compacts
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        match s.as_str() {
+            "standard" | "default" => return Ok(Self::standard()),
+            "off" | "none" => return Ok(Self { daily_after_days: None, weekly_after_days: None, monthly_after_days: None }),
+            _ => {}
+        }
+        
+        let mut policy = Self { daily_after_days: None, weekly_after_days: None, monthly_after_days: None };
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("compaction age '{}' is not period=days", part))?;
+            let value: u32 = value
+                .trim()
+                .trim_end_matches('d')
+                .parse()
+                .map_err(|_| format!("compaction age '{}' needs a whole number of days", part))?;
+            match key.trim() {
+                "daily" | "day" => policy.daily_after_days = Some(value),
+                "weekly" | "week" => policy.weekly_after_days = Some(value),
+                "monthly" | "month" => policy.monthly_after_days = Some(value),
+                other => return Err(format!(
+                    "unknown compaction period '{}' (expected daily, weekly or monthly)", other
+                )),
+            }
+        }
+        Ok(policy)
+    }
+}
+
+/// What a compaction run did
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompactionReport {
+    /// Summaries written (or that would be written on a dry run)
+    pub summaries: Vec<CompactionSummary>,
+    /// Where each summary was written (empty on a dry run)
+    pub paths: Vec<String>,
+    /// Snapshots rolled into a summary
+    pub compacted: usize,
+    /// Older summaries folded into a coarser one
+    #[serde(default)]
+    pub merged: usize,
+    /// Snapshots left in place (too young, or their period is not over)
+    pub kept: usize,
+}
+
+/// Filter for snapshot store queries; every set field must match
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotQuery {
+    /// Session ID as 32 hex characters
+    pub session: Option<String>,
+    /// Observer name (exact)
+    pub observer: Option<String>,
+    /// Created at or after (Unix timestamp)
+    pub since: Option<i64>,
+    /// Created before (Unix timestamp)
+    pub until: Option<i64>,
+    /// Seen theme (case-insensitive)
+    pub theme: Option<String>,
+    /// Blind spot category present
+    pub blind_spot: Option<BlindSpotCategory>,
+    /// At most this many results (newest first)
+    pub limit: Option<usize>,
+}
+
+impl SnapshotQuery {
+    /// Does the snapshot pass every filter (ignores `limit`)?
+    pub fn matches(&self, snapshot: &Snapshot) -> bool {
+        self.session.as_ref().is_none_or(|s| s.eq_ignore_ascii_case(&snapshot.session_hex()))
+            && self.observer.as_ref().is_none_or(|o| snapshot.observers.contains(o))
+            && self.since.is_none_or(|t| snapshot.timestamp_unix >= t)
+            && self.until.is_none_or(|t| snapshot.timestamp_unix < t)
+            && self.theme.as_ref().is_none_or(|t| snapshot.seen.themes.iter().any(|theme| theme.to_lowercase() == t.to_lowercase()))
+            && self.blind_spot.is_none_or(|c| snapshot.blind_spots.iter().any(|b| b.category == c))
+    }
+}
+
+/// What a migration between snapshot stores did
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrationReport {
+    /// Snapshots copied into the target store
+    pub migrated: usize,
+    /// Files that could not be read as snapshots
+    pub skipped: Vec<String>,
+}
+
+/// A snapshot found by full-text search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    /// Number of search terms found
+    pub score: usize,
+    /// Themes and keywords that matched
+    pub matched: Vec<String>,
+    pub snapshot: Snapshot,
+}
+
+/// A snapshot file that failed validation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationFailure {
+    pub path: String,
+    pub reason: SnapshotReason,
+}
+
+/// Outcome of validating every snapshot file in a directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// Files that passed
+    pub valid: usize,
+    /// Files that did not, in path order
+    pub failures: Vec<ValidationFailure>,
+}
+
+impl ValidationReport {
+    /// Failures with a missing or corrupt proof link (R305)
+    pub fn proof_link_failures(&self) -> usize {
+        self.failures.iter()
+            .filter(|f| f.reason == SnapshotReason::R305_SNAPSHOT_INVALID_PROOF_LINK)
+            .count()
+    }
 }
@@ -0,0 +1,273 @@
+//! Transcript types for offline analysis
+//!
+//! An imported transcript is a list of speaker turns with optional offsets
+//! from the start of the conversation. `soul0 analyze` replays it through
+//! the r / ΔC / Facelock pipeline on transcript time and reports per turn.
+
+use serde::{Deserialize, Serialize};
+use crate::types::{DcReason, FacelockState, ReasonCode};
+
+/// Spacing given to turns without a timestamp (ms after the previous turn)
+pub const UNTIMED_TURN_SPACING_MS: u64 = 3000;
+
+/// Supported transcript formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    /// WhatsApp text export ("12/03/2024, 14:05 - Name: text" or "[12/03/2024, 14:05:33] Name: text")
+    WhatsApp,
+    /// Signal text export ("[2024-03-12 14:05] Name: text")
+    Signal,
+    /// SubRip subtitles with speaker tags
+    Srt,
+    /// WebVTT subtitles with `<v Name>` voice tags or speaker prefixes
+    WebVtt,
+    /// Plain "Name: text" log with optional timestamps
+    Log,
+    /// Speech-to-text segment JSON, optionally combined with an RTTM speaker file
+    Diarized,
+}
+
+impl std::fmt::Display for TranscriptFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranscriptFormat::WhatsApp => write!(f, "whatsapp"),
+            TranscriptFormat::Signal => write!(f, "signal"),
+            TranscriptFormat::Srt => write!(f, "srt"),
+            TranscriptFormat::WebVtt => write!(f, "vtt"),
+            TranscriptFormat::Log => write!(f, "log"),
+            TranscriptFormat::Diarized => write!(f, "diarized"),
+        }
+    }
+}
+
+impl std::str::FromStr for TranscriptFormat {
+    type Err = String;
+    
+    /// Parse "whatsapp", "signal", "srt", "vtt", "log" or "diarized"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "whatsapp" | "wa" => Ok(TranscriptFormat::WhatsApp),
+            "signal" => Ok(TranscriptFormat::Signal),
+            "srt" | "subrip" => Ok(TranscriptFormat::Srt),
+            "vtt" | "webvtt" => Ok(TranscriptFormat::WebVtt),
+            "log" | "text" | "txt" => Ok(TranscriptFormat::Log),
+            "diarized" | "json" | "segments" => Ok(TranscriptFormat::Diarized),
+            other => Err(format!(
+                "unknown transcript format '{}' (expected whatsapp, signal, srt, vtt, log or diarized)", other
+            )),
+        }
+    }
+}
+
+/// One imported speaker turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptTurn {
+    /// Speaker name as written in the transcript
+    pub speaker: String,
+    /// Turn text (multi-line messages joined with newlines)
+    pub text: String,
+    /// Milliseconds since the first timestamp in the transcript (None if untimed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset_ms: Option<u64>,
+}
+
+impl TranscriptTurn {
+    /// Create an untimed turn
+    pub fn new(speaker: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            speaker: speaker.into(),
+            text: text.into(),
+            offset_ms: None,
+        }
+    }
+    
+    /// Set the offset from the start of the transcript
+    pub fn at_ms(mut self, offset_ms: u64) -> Self {
+        self.offset_ms = Some(offset_ms);
+        self
+    }
+}
+
+/// An imported transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transcript {
+    /// Format it was parsed as
+    pub format: TranscriptFormat,
+    /// Turns in order
+    pub turns: Vec<TranscriptTurn>,
+}
+
+impl Transcript {
+    /// Speakers in order of first appearance
+    pub fn speakers(&self) -> Vec<String> {
+        let mut speakers: Vec<String> = Vec::new();
+        for turn in &self.turns {
+            if !speakers.contains(&turn.speaker) {
+                speakers.push(turn.speaker.clone());
+            }
+        }
+        speakers
+    }
+    
+    /// Does any turn carry a timestamp?
+    pub fn is_timed(&self) -> bool {
+        self.turns.iter().any(|t| t.offset_ms.is_some())
+    }
+}
+
+/// One speaker turn from an RTTM diarization file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RttmTurn {
+    /// Speaker label ("SPEAKER_00", "spk1", …)
+    pub speaker: String,
+    /// Start in ms from the start of the recording
+    pub start_ms: u64,
+    /// End in ms from the start of the recording
+    pub end_ms: u64,
+}
+
+impl RttmTurn {
+    /// Milliseconds this turn shares with `start..end`
+    pub fn overlap_ms(&self, start_ms: u64, end_ms: u64) -> u64 {
+        self.end_ms.min(end_ms).saturating_sub(self.start_ms.max(start_ms))
+    }
+}
+
+/// One transcribed word with timing (speech-to-text word alignment)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeechWord {
+    /// The word, as transcribed
+    #[serde(alias = "text")]
+    pub word: String,
+    /// Start in seconds (missing for unaligned words)
+    #[serde(default)]
+    pub start: Option<f64>,
+    /// End in seconds
+    #[serde(default)]
+    pub end: Option<f64>,
+}
+
+/// One speech-to-text segment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeechSegment {
+    /// Start in seconds from the start of the recording
+    pub start: f64,
+    /// End in seconds
+    pub end: f64,
+    /// Transcribed text
+    pub text: String,
+    /// Speaker label, when the transcriber diarized itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    /// Word timings, when aligned
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<SpeechWord>,
+}
+
+/// Reason codes for transcript import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub enum ImportReason {
+    /// Transcript imported
+    R400_IMPORT_COMPLETE,
+    /// File could not be read
+    R401_IMPORT_READ_ERROR,
+    /// No speaker turns found in the given format
+    R402_IMPORT_NO_TURNS,
+    /// Segment JSON is malformed
+    R403_IMPORT_INVALID_SEGMENTS,
+}
+
+impl ImportReason {
+    /// Get code string
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::R400_IMPORT_COMPLETE => "R400_IMPORT_COMPLETE",
+            Self::R401_IMPORT_READ_ERROR => "R401_IMPORT_READ_ERROR",
+            Self::R402_IMPORT_NO_TURNS => "R402_IMPORT_NO_TURNS",
+            Self::R403_IMPORT_INVALID_SEGMENTS => "R403_IMPORT_INVALID_SEGMENTS",
+        }
+    }
+    
+    /// Get description
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::R400_IMPORT_COMPLETE => "Transcript imported",
+            Self::R401_IMPORT_READ_ERROR => "Failed to read transcript",
+            Self::R402_IMPORT_NO_TURNS => "No speaker turns found",
+            Self::R403_IMPORT_INVALID_SEGMENTS => "Segment JSON could not be parsed",
+        }
+    }
+}
+
+impl std::fmt::Display for ImportReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code(), self.description())
+    }
+}
+
+/// Pipeline output for one transcript turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnReport {
+    /// 1-based turn number
+    pub turn: usize,
+    /// Transcript time of the turn (ms since start)
+    pub offset_ms: u64,
+    /// Speaker
+    pub speaker: String,
+    /// Turn text
+    pub text: String,
+    /// r of this turn
+    pub r: f64,
+    /// r confidence of this turn
+    pub confidence: f64,
+    /// ΔC of the primary window after this turn (None = UNKNOWN)
+    pub dc: Option<f64>,
+    /// ΔC reason
+    pub dc_reason: DcReason,
+    /// Facelock state after this turn
+    pub state: FacelockState,
+    /// Engine reason
+    pub reason: ReasonCode,
+    /// Lock stability in ms
+    pub stable_ms: u64,
+    /// Proof generated at this turn (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<String>,
+}
+
+/// Pipeline output for a whole transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptReport {
+    /// Import outcome (R400_IMPORT_COMPLETE; failed imports yield no report)
+    pub import: ImportReason,
+    /// Format the transcript was parsed as
+    pub format: TranscriptFormat,
+    /// Speakers in order of first appearance
+    pub speakers: Vec<String>,
+    /// Transcript duration (ms from first to last turn)
+    pub duration_ms: u64,
+    /// Whether the transcript carried its own timestamps
+    pub timed: bool,
+    /// Per-turn results
+    pub turns: Vec<TurnReport>,
+    /// Total time spent LOCKED (transcript time, ms)
+    pub locked_ms: u64,
+    /// Number of proofs generated
+    pub proof_count: usize,
+}
+
+impl TranscriptReport {
+    /// Final state after the last turn
+    pub fn final_state(&self) -> FacelockState {
+        self.turns.last().map_or(FacelockState::Waiting, |t| t.state)
+    }
+    
+    /// Turn with the highest known ΔC
+    pub fn peak_dc(&self) -> Option<&TurnReport> {
+        self.turns.iter()
+            .filter(|t| t.dc.is_some())
+            .max_by(|a, b| a.dc.unwrap_or(0.0).total_cmp(&b.dc.unwrap_or(0.0)))
+    }
+}
@@ -3,7 +3,22 @@
 //! Implements TURN_MODEL_v1.0.md:
 //! - Turn = one speaker's contribution
 //! - Pair = two consecutive turns from different speakers
-//! - Window = 30 seconds sliding
+//! - Window = 30 seconds sliding, at most 10 turns per speaker (default policy)
+//!
+//! A `MultiScaleWindow` keeps several windows over the same turns (30 s,
+//! 5 min, the session's last `SESSION_WINDOW_TURNS` turns) so short- and
+//! long-term ΔC can be compared. The longer scales are capped in turns too,
+//! so a session's memory and per-turn cost stay bounded however long it runs.
+//!
+//! A `WindowPolicy` replaces the default with any mix of limits: the last T
+//! seconds, the last N turns, the last N pairs, and a per-speaker cap. A
+//! turn-count policy without a time limit suits async chat, where replies
+//! come minutes apart and a 30 s window would never hold a pair.
+//!
+//! With a merge gap set, consecutive turns by one speaker that follow each
+//! other within the gap are coalesced into one logical turn for pairing
+//! (chat bursts: "hey" / "so about tomorrow" / "are you free?"). `turns()`
+//! keeps returning the raw turns for display.
 
 use std::collections::{VecDeque, HashMap, HashSet};
 use std::time::{Instant, Duration};
@@ -15,6 +30,26 @@ pub const WINDOW_DURATION_SECS: u64 = 30;
 /// Maximum turns per speaker in window
 pub const MAX_TURNS_PER_SPEAKER: usize = 10;
 
+/// Horizon of the medium scale of a `MultiScaleWindow`
+pub const MEDIUM_WINDOW_SECS: u64 = 300;
+
+/// Most turns kept by the medium and session scales of a `MultiScaleWindow`
+pub const SESSION_WINDOW_TURNS: usize = 200;
+
+/// Separator between the messages of a merged logical turn
+pub const MERGED_TURN_SEPARATOR: &str = "\n";
+
+/// Longest merge gap accepted from users (one day)
+pub const MAX_MERGE_GAP_SECS: f64 = 86_400.0;
+
+/// Merge gap from user input in seconds: finite, 0 to `MAX_MERGE_GAP_SECS`
+pub fn merge_gap_from_secs(secs: f64) -> Result<Duration, String> {
+    if !(0.0..=MAX_MERGE_GAP_SECS).contains(&secs) {
+        return Err(format!("merge gap must be between 0 and {} seconds", MAX_MERGE_GAP_SECS));
+    }
+    Duration::try_from_secs_f64(secs).map_err(|e| e.to_string())
+}
+
 /// A single turn from one speaker
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Turn {
@@ -40,6 +75,12 @@ impl Turn {
         }
     }
     
+    /// Set when the turn was made (for replays and imported timings)
+    pub fn with_timestamp(mut self, timestamp: Instant) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+    
     /// Get age in milliseconds
     pub fn age_ms(&self) -> u64 {
         self.timestamp
@@ -48,18 +89,172 @@ impl Turn {
     }
 }
 
-/// A pair of consecutive turns from different speakers
-#[derive(Debug, Clone)]
-pub struct TurnPair {
-    pub first: Turn,
-    pub second: Turn,
+/// A pair of consecutive turns from different speakers, borrowed from the window
+#[derive(Debug, Clone, Copy)]
+pub struct TurnPair<'a> {
+    pub first: &'a Turn,
+    pub second: &'a Turn,
+}
+
+/// Which turns a window keeps; every limit that is set applies
+///
+/// Turn counts are raw turns, pair counts are pairs of logical turns (see
+/// `ConversationWindow::logical_turns`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowPolicy {
+    /// Drop turns older than this many seconds
+    pub max_age_secs: Option<u64>,
+    /// Keep at most this many turns
+    pub max_turns: Option<usize>,
+    /// Keep at most this many pairs
+    pub max_pairs: Option<usize>,
+    /// Keep at most this many turns per speaker (most recent)
+    pub max_turns_per_speaker: Option<usize>,
+}
+
+impl WindowPolicy {
+    /// Original window: last 30 seconds, at most 10 turns per speaker
+    pub fn standard() -> Self {
+        Self::time(WINDOW_DURATION_SECS).with_speaker_cap(MAX_TURNS_PER_SPEAKER)
+    }
+    
+    /// Last `secs` seconds
+    pub fn time(secs: u64) -> Self {
+        Self { max_age_secs: Some(secs), ..Self::default() }
+    }
+    
+    /// Last `count` turns, however old
+    pub fn turns(count: usize) -> Self {
+        Self { max_turns: Some(count), ..Self::default() }
+    }
+    
+    /// Last `count` pairs, however old
+    pub fn pairs(count: usize) -> Self {
+        Self { max_pairs: Some(count), ..Self::default() }
+    }
+    
+    /// Also drop turns older than `secs` seconds
+    pub fn with_max_age(mut self, secs: u64) -> Self {
+        self.max_age_secs = Some(secs);
+        self
+    }
+    
+    /// Also keep at most `count` turns
+    pub fn with_max_turns(mut self, count: usize) -> Self {
+        self.max_turns = Some(count);
+        self
+    }
+    
+    /// Also keep at most `count` pairs
+    pub fn with_max_pairs(mut self, count: usize) -> Self {
+        self.max_pairs = Some(count);
+        self
+    }
+    
+    /// Also keep at most `count` turns per speaker
+    pub fn with_speaker_cap(mut self, count: usize) -> Self {
+        self.max_turns_per_speaker = Some(count);
+        self
+    }
+    
+    /// Age limit as a duration
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age_secs.map(Duration::from_secs)
+    }
+    
+    /// Does the policy set no limit at all (keeps every turn)?
+    pub fn is_unbounded(&self) -> bool {
+        *self == Self::default()
+    }
+    
+    /// The same policy keeping at most `max_turns` turns, with every count limit capped at it
+    pub fn capped(self, max_turns: usize) -> Self {
+        let cap = |limit: Option<usize>| limit.map(|n| n.min(max_turns));
+        Self {
+            max_age_secs: self.max_age_secs,
+            max_turns: Some(self.max_turns.map_or(max_turns, |n| n.min(max_turns))),
+            max_pairs: cap(self.max_pairs),
+            max_turns_per_speaker: cap(self.max_turns_per_speaker),
+        }
+    }
+}
+
+impl std::fmt::Display for WindowPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = [
+            self.max_age_secs.map(|v| format!("time={}", v)),
+            self.max_turns.map(|v| format!("turns={}", v)),
+            self.max_pairs.map(|v| format!("pairs={}", v)),
+            self.max_turns_per_speaker.map(|v| format!("per-speaker={}", v)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        
+        if parts.is_empty() {
+            write!(f, "unbounded")
+        } else {
+            write!(f, "{}", parts.join(","))
+        }
+    }
+}
+
+impl std::str::FromStr for WindowPolicy {
+    type Err = String;
+    
+    /// Parse comma-separated limits, e.g. "time=300,per-speaker=5" or "turns=20";
+    /// "standard" is the original 30 s window, "unbounded" keeps everything
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        match s.as_str() {
+            "standard" | "default" => return Ok(Self::standard()),
+            "unbounded" | "none" => return Ok(Self::default()),
+            _ => {}
+        }
+        
+        let mut policy = Self::default();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("window limit '{}' is not key=value", part))?;
+            let value: usize = value
+                .trim()
+                .parse()
+                .map_err(|_| format!("window limit '{}' needs a whole number", part))?;
+            match key.trim() {
+                "time" | "secs" => policy.max_age_secs = Some(value as u64),
+                "turns" => policy.max_turns = Some(value),
+                "pairs" => policy.max_pairs = Some(value),
+                "per-speaker" | "per_speaker" | "speaker" => policy.max_turns_per_speaker = Some(value),
+                other => return Err(format!(
+                    "unknown window limit '{}' (expected time, turns, pairs or per-speaker)", other
+                )),
+            }
+        }
+        Ok(policy)
+    }
 }
 
 /// Conversation window - sliding window for ΔC calculation
 #[derive(Debug)]
 pub struct ConversationWindow {
     turns: VecDeque<Turn>,
-    window_duration: Duration,
+    /// Logical turns (merging only; kept in step with `turns`)
+    logical: VecDeque<Turn>,
+    /// Raw turns in each logical turn
+    parts: VecDeque<usize>,
+    /// Id of each logical turn (see `turn_ids`)
+    ids: VecDeque<u64>,
+    /// Id for the next logical turn that enters or changes
+    next_id: u64,
+    /// Raw turns per speaker
+    speaker_turns: HashMap<String, usize>,
+    policy: WindowPolicy,
+    /// Coalesce same-speaker turns within this gap (None = raw turns)
+    merge_gap: Option<Duration>,
+    /// The time limit emptied the window and fewer than 2 turns came since
+    expired: bool,
 }
 
 impl Default for ConversationWindow {
@@ -71,60 +266,336 @@ impl Default for ConversationWindow {
 impl ConversationWindow {
     /// Create new window with default duration (30s)
     pub fn new() -> Self {
-        Self {
-            turns: VecDeque::new(),
-            window_duration: Duration::from_secs(WINDOW_DURATION_SECS),
-        }
+        Self::with_policy(WindowPolicy::standard())
     }
     
     /// Create window with custom duration
     pub fn with_duration(secs: u64) -> Self {
+        Self::with_policy(WindowPolicy::time(secs).with_speaker_cap(MAX_TURNS_PER_SPEAKER))
+    }
+    
+    /// Create window with the given policy
+    pub fn with_policy(policy: WindowPolicy) -> Self {
         Self {
             turns: VecDeque::new(),
-            window_duration: Duration::from_secs(secs),
+            logical: VecDeque::new(),
+            parts: VecDeque::new(),
+            ids: VecDeque::new(),
+            next_id: 0,
+            speaker_turns: HashMap::new(),
+            policy,
+            merge_gap: None,
+            expired: false,
         }
     }
     
+    /// Change the policy (applied from the next turn on)
+    pub fn set_policy(&mut self, policy: WindowPolicy) {
+        self.policy = policy;
+    }
+    
+    /// Which turns the window keeps
+    pub fn policy(&self) -> WindowPolicy {
+        self.policy
+    }
+    
+    /// Coalesce consecutive same-speaker turns within `gap` (None = raw turns)
+    pub fn set_merge_gap(&mut self, gap: Option<Duration>) {
+        self.merge_gap = gap;
+        self.rebuild_logical();
+    }
+    
+    /// Gap within which same-speaker turns are merged, if merging
+    pub fn merge_gap(&self) -> Option<Duration> {
+        self.merge_gap
+    }
+    
     /// Add a turn and prune old turns
     pub fn add_turn(&mut self, turn: Turn) {
+        *self.speaker_turns.entry(turn.speaker.clone()).or_insert(0) += 1;
+        if self.merge_gap.is_some() {
+            self.push_logical(&turn);
+        } else {
+            let id = self.fresh_id();
+            self.ids.push_back(id);
+        }
         self.turns.push_back(turn);
         self.prune();
     }
     
-    /// Prune turns outside window and enforce per-speaker limit
+    /// Prune turns outside the policy's limits
     fn prune(&mut self) {
-        let now = Instant::now();
+        let now = self.clock();
         
         // Remove turns older than window duration
-        while let Some(front) = self.turns.front() {
-            if let Some(ts) = front.timestamp {
-                if now.duration_since(ts) > self.window_duration {
-                    self.turns.pop_front();
+        if let Some(max_age) = self.policy.max_age() {
+            let mut expired = 0;
+            while let Some(front) = self.turns.front() {
+                match front.timestamp {
+                    Some(ts) if now.saturating_duration_since(ts) > max_age => {
+                        self.pop_front();
+                        expired += 1;
+                    }
+                    _ => break,
+                }
+            }
+            if expired > 0 && self.turns.len() <= 1 {
+                self.expired = true;
+            }
+        }
+        if self.turns.len() >= 2 {
+            self.expired = false;
+        }
+        
+        // Enforce per-speaker limit (keep most recent)
+        if let Some(cap) = self.policy.max_turns_per_speaker {
+            let over: Vec<String> = self.speaker_turns.iter()
+                .filter(|(_, &count)| count > cap)
+                .map(|(speaker, _)| speaker.clone())
+                .collect();
+            for speaker in over {
+                while self.speaker_turns.get(&speaker).is_some_and(|&count| count > cap) {
+                    let oldest = self.turns.iter().position(|t| t.speaker == speaker).unwrap();
+                    self.remove(oldest);
+                }
+            }
+        }
+        
+        if let Some(max_turns) = self.policy.max_turns {
+            while self.turns.len() > max_turns {
+                self.pop_front();
+            }
+        }
+        
+        // Drop whole logical turns up to the first turn of the last pair to go
+        if let Some(max_pairs) = self.policy.max_pairs {
+            let pairs = self.pair_indices();
+            if pairs.len() > max_pairs {
+                let last_dropped = pairs[pairs.len() - max_pairs - 1].0;
+                let raw = if self.merge_gap.is_some() {
+                    self.parts.iter().take(last_dropped + 1).sum()
                 } else {
-                    break;
+                    last_dropped + 1
+                };
+                for _ in 0..raw {
+                    self.pop_front();
+                }
+            }
+        }
+    }
+    
+    /// Current time for age limits: the wall clock, or the newest turn's
+    /// timestamp when that lies ahead of it (replayed transcripts)
+    fn clock(&self) -> Instant {
+        let now = Instant::now();
+        self.turns.back()
+            .and_then(|t| t.timestamp)
+            .map_or(now, |ts| ts.max(now))
+    }
+    
+    /// Drop the oldest raw turn, updating the logical turn it belonged to
+    fn pop_front(&mut self) {
+        let Some(turn) = self.turns.pop_front() else {
+            return;
+        };
+        self.forget_speaker(&turn.speaker);
+        
+        if self.merge_gap.is_none() {
+            self.ids.pop_front();
+        } else {
+            let remaining = self.parts.front().map_or(0, |parts| parts - 1);
+            if remaining == 0 {
+                self.logical.pop_front();
+                self.parts.pop_front();
+                self.ids.pop_front();
+            } else {
+                // Rebuild the front logical turn from its remaining parts
+                let mut front = self.turns[0].clone();
+                for i in 1..remaining {
+                    Self::merge_into(&mut front, i, &self.turns[i]);
                 }
+                self.logical[0] = front;
+                self.parts[0] = remaining;
+                self.ids[0] = self.fresh_id();
+            }
+        }
+    }
+    
+    /// Drop the raw turn at `index` (merges the logical turns around it again if merging)
+    fn remove(&mut self, index: usize) {
+        if index == 0 {
+            self.pop_front();
+            return;
+        }
+        if let Some(turn) = self.turns.remove(index) {
+            self.forget_speaker(&turn.speaker);
+            if self.merge_gap.is_none() {
+                self.ids.remove(index);
             } else {
-                break;
+                self.remerge(index);
             }
         }
+    }
+    
+    /// Merge the raw turns again around raw turn `index`, which was just removed
+    ///
+    /// Starts at the logical turn before the one that held it and stops where a
+    /// new logical turn starts at the same raw turn as an old one after it:
+    /// from there on the merge runs as before. Logical turns that come out
+    /// with the same raw turns keep their id.
+    fn remerge(&mut self, index: usize) {
+        let Some(gap) = self.merge_gap else {
+            return;
+        };
         
-        // Enforce per-speaker limit (keep most recent)
-        let mut counts: HashMap<String, usize> = HashMap::new();
-        let mut to_remove: Vec<usize> = Vec::new();
-        
-        // Count from back (newest) to front (oldest)
-        for (i, turn) in self.turns.iter().enumerate().rev() {
-            let count = counts.entry(turn.speaker.clone()).or_insert(0);
-            *count += 1;
-            if *count > MAX_TURNS_PER_SPEAKER {
-                to_remove.push(i);
+        // The logical turn that held it, one part fewer now
+        let (mut held, mut held_start) = (0, 0);
+        while held_start + self.parts[held] <= index {
+            held_start += self.parts[held];
+            held += 1;
+        }
+        self.parts[held] -= 1;
+        let first = held.saturating_sub(1);
+        let first_start = if held > 0 { held_start - self.parts[first] } else { 0 };
+        
+        // Merge from there until a new logical turn starts where an old one
+        // after `held` does
+        let (mut next, mut next_start) = (held + 1, held_start + self.parts[held]);
+        let mut end = self.logical.len();
+        let mut rebuilt: Vec<(Turn, usize)> = Vec::new();
+        for raw in first_start..self.turns.len() {
+            while next < self.logical.len() && next_start < raw {
+                next_start += self.parts[next];
+                next += 1;
+            }
+            let turn = &self.turns[raw];
+            match rebuilt.last_mut() {
+                Some((last, parts)) if Self::continues(gap, last, turn) => {
+                    Self::merge_into(last, *parts, turn);
+                    *parts += 1;
+                }
+                _ if next < self.logical.len() && next_start == raw => {
+                    end = next;
+                    break;
+                }
+                _ => rebuilt.push((turn.clone(), 1)),
+            }
+        }
+        
+        // Old logical turns that may come out the same, as (first raw turn, parts, id)
+        let mut old = Vec::new();
+        let mut start = first_start;
+        for i in first..end {
+            if i != held {
+                old.push((start, self.parts[i], self.ids[i]));
             }
+            start += self.parts[i];
         }
         
-        // Remove excess turns (oldest first)
-        for i in to_remove.into_iter().rev() {
-            self.turns.remove(i);
+        // Replace logical turns `first..end` by the rebuilt ones
+        for _ in first..end {
+            self.logical.remove(first);
+            self.parts.remove(first);
+            self.ids.remove(first);
+        }
+        let mut start = first_start;
+        for (i, (turn, parts)) in rebuilt.into_iter().enumerate() {
+            let id = old.iter()
+                .find(|&&(old_start, old_parts, _)| old_start == start && old_parts == parts)
+                .map_or_else(|| self.fresh_id(), |&(_, _, id)| id);
+            self.logical.insert(first + i, turn);
+            self.parts.insert(first + i, parts);
+            self.ids.insert(first + i, id);
+            start += parts;
+        }
+    }
+    
+    fn forget_speaker(&mut self, speaker: &str) {
+        if let Some(count) = self.speaker_turns.get_mut(speaker) {
+            *count -= 1;
+            if *count == 0 {
+                self.speaker_turns.remove(speaker);
+            }
+        }
+    }
+    
+    /// Append a raw turn to the logical turns (merging only)
+    fn push_logical(&mut self, turn: &Turn) {
+        if let (Some(gap), Some(last), Some(parts)) =
+            (self.merge_gap, self.logical.back_mut(), self.parts.back_mut())
+        {
+            if Self::continues(gap, last, turn) {
+                Self::merge_into(last, *parts, turn);
+                *parts += 1;
+                let id = self.fresh_id();
+                if let Some(last_id) = self.ids.back_mut() {
+                    *last_id = id;
+                }
+                return;
+            }
+        }
+        self.logical.push_back(turn.clone());
+        self.parts.push_back(1);
+        let id = self.fresh_id();
+        self.ids.push_back(id);
+    }
+    
+    /// Id for a logical turn that enters or changes
+    fn fresh_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+    
+    /// Does `turn` continue the logical turn `last` (same speaker, within `gap`)?
+    fn continues(gap: Duration, last: &Turn, turn: &Turn) -> bool {
+        let within_gap = match (last.timestamp, turn.timestamp) {
+            (Some(prev), Some(ts)) => ts.saturating_duration_since(prev) <= gap,
+            _ => true,
+        };
+        last.speaker == turn.speaker && within_gap
+    }
+    
+    /// Merge `turn` into a logical turn of `parts` raw turns
+    fn merge_into(last: &mut Turn, parts: usize, turn: &Turn) {
+        last.text.push_str(MERGED_TURN_SEPARATOR);
+        last.text.push_str(&turn.text);
+        last.r = (last.r * parts as f64 + turn.r) / (parts + 1) as f64;
+        last.timestamp = turn.timestamp.or(last.timestamp);
+    }
+    
+    /// Recompute all logical turns from the raw turns
+    fn rebuild_logical(&mut self) {
+        self.logical.clear();
+        self.parts.clear();
+        self.ids.clear();
+        if self.merge_gap.is_none() {
+            for _ in 0..self.turns.len() {
+                let id = self.fresh_id();
+                self.ids.push_back(id);
+            }
+            return;
+        }
+        let turns = std::mem::take(&mut self.turns);
+        for turn in &turns {
+            self.push_logical(turn);
         }
+        self.turns = turns;
+    }
+    
+    /// Did the time limit empty the window?
+    ///
+    /// True when every turn is older than the policy's age limit, or when
+    /// turns added since the window expired have nothing to pair with yet.
+    /// Always false without an age limit.
+    pub fn timed_out(&self) -> bool {
+        let Some(max_age) = self.policy.max_age() else {
+            return false;
+        };
+        let now = self.clock();
+        let all_expired = !self.turns.is_empty() && self.turns.iter().all(|t| {
+            t.timestamp.is_some_and(|ts| now.saturating_duration_since(ts) > max_age)
+        });
+        all_expired || self.expired
     }
     
     /// Get all turns (oldest first)
@@ -144,34 +615,75 @@ impl ConversationWindow {
     
     /// Get unique speakers in window
     pub fn speakers(&self) -> HashSet<String> {
-        self.turns.iter().map(|t| t.speaker.clone()).collect()
+        self.speaker_turns.keys().cloned().collect()
     }
     
     /// Get speaker count
     pub fn speaker_count(&self) -> usize {
-        self.speakers().len()
+        self.speaker_turns.len()
     }
     
-    /// Extract paired turns (consecutive turns from different speakers)
-    pub fn paired_turns(&self) -> Vec<TurnPair> {
-        let mut pairs = Vec::new();
-        let turns: Vec<_> = self.turns.iter().collect();
-        
-        for window in turns.windows(2) {
-            if window[0].speaker != window[1].speaker {
-                pairs.push(TurnPair {
-                    first: window[0].clone(),
-                    second: window[1].clone(),
-                });
-            }
+    /// Turns used for pairing, oldest first
+    ///
+    /// Without a merge gap these are the raw turns. With one, each run of
+    /// turns by one speaker with no more than the gap between consecutive
+    /// messages becomes one turn: texts joined by `MERGED_TURN_SEPARATOR`,
+    /// r the mean of the parts, timestamp of the last part. Turns without
+    /// a timestamp count as within the gap. Logical turns are kept up to
+    /// date as turns enter and leave, so this borrows rather than rebuilds.
+    pub fn logical_turns(&self) -> &VecDeque<Turn> {
+        if self.merge_gap.is_some() {
+            &self.logical
+        } else {
+            &self.turns
         }
-        
-        pairs
+    }
+    
+    /// Id of each logical turn, parallel to `logical_turns()`
+    ///
+    /// A turn gets a new id when it enters the window and whenever its text
+    /// changes (a merged part joins or leaves). Ids grow in turn order, except
+    /// for a turn rebuilt after it lost a part (at the front, or to the
+    /// per-speaker cap), so an incremental scorer can tell which turns left,
+    /// changed and entered since it last looked.
+    pub fn turn_ids(&self) -> &VecDeque<u64> {
+        &self.ids
+    }
+    
+    /// Extract paired turns (consecutive logical turns from different speakers)
+    pub fn paired_turns(&self) -> Vec<TurnPair<'_>> {
+        let turns = self.logical_turns();
+        self.pair_indices()
+            .into_iter()
+            .map(|(a, b)| TurnPair {
+                first: &turns[a],
+                second: &turns[b],
+            })
+            .collect()
+    }
+    
+    /// Indices (into `logical_turns()`) of each paired turn, oldest first
+    ///
+    /// Without a merge gap, `logical_turns()` and `turns()` are the same.
+    pub fn pair_indices(&self) -> Vec<(usize, usize)> {
+        let turns = self.logical_turns();
+        (1..turns.len())
+            .filter(|&i| turns[i - 1].speaker != turns[i].speaker)
+            .map(|i| (i - 1, i))
+            .collect()
+    }
+    
+    /// Number of paired turns
+    pub fn pair_count(&self) -> usize {
+        let turns = self.logical_turns();
+        (1..turns.len())
+            .filter(|&i| turns[i - 1].speaker != turns[i].speaker)
+            .count()
     }
     
     /// Check if we have enough data for ΔC calculation
     pub fn can_calculate_dc(&self) -> bool {
-        self.speaker_count() >= 2 && !self.paired_turns().is_empty()
+        self.speaker_count() >= 2 && self.pair_count() > 0
     }
     
     /// Get average r across all turns in window
@@ -201,6 +713,111 @@ impl ConversationWindow {
     /// Clear all turns
     pub fn clear(&mut self) {
         self.turns.clear();
+        self.logical.clear();
+        self.parts.clear();
+        self.ids.clear();
+        self.speaker_turns.clear();
+        self.expired = false;
+    }
+}
+
+/// A named window horizon
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowScale {
+    /// Name in outputs ("short", "medium", "session")
+    pub name: String,
+    /// Which turns this scale keeps
+    pub policy: WindowPolicy,
+}
+
+impl WindowScale {
+    /// Create a scale
+    pub fn new(name: impl Into<String>, policy: WindowPolicy) -> Self {
+        Self { name: name.into(), policy }
+    }
+}
+
+/// Several windows over the same turns, each with its own horizon
+///
+/// The first scale is the primary one: it drives state transitions and
+/// proofs. The longer scales feed snapshots and reports.
+#[derive(Debug)]
+pub struct MultiScaleWindow {
+    scales: Vec<(String, ConversationWindow)>,
+}
+
+impl Default for MultiScaleWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MultiScaleWindow {
+    /// Standard scales: 30 s (primary), 5 min, session (last `SESSION_WINDOW_TURNS` turns)
+    pub fn new() -> Self {
+        Self::with_primary(WindowPolicy::standard())
+    }
+    
+    /// Primary window with `policy`, plus the 5 min and session scales
+    pub fn with_primary(policy: WindowPolicy) -> Self {
+        Self::with_scales(vec![
+            WindowScale::new("short", policy),
+            WindowScale::new("medium", WindowPolicy::time(MEDIUM_WINDOW_SECS).with_max_turns(SESSION_WINDOW_TURNS)),
+            WindowScale::new("session", WindowPolicy::turns(SESSION_WINDOW_TURNS)),
+        ])
+    }
+    
+    /// Custom scales, primary first (no scales = the standard ones)
+    pub fn with_scales(scales: Vec<WindowScale>) -> Self {
+        if scales.is_empty() {
+            return Self::new();
+        }
+        Self {
+            scales: scales.into_iter()
+                .map(|s| (s.name, ConversationWindow::with_policy(s.policy)))
+                .collect(),
+        }
+    }
+    
+    /// Add a turn to every scale
+    pub fn add_turn(&mut self, turn: Turn) {
+        for (_, window) in self.scales.iter_mut() {
+            window.add_turn(turn.clone());
+        }
+    }
+    
+    /// Coalesce same-speaker turns within `gap` on every scale
+    pub fn set_merge_gap(&mut self, gap: Option<Duration>) {
+        for (_, window) in self.scales.iter_mut() {
+            window.set_merge_gap(gap);
+        }
+    }
+    
+    /// Window used for state transitions and proofs
+    pub fn primary(&self) -> &ConversationWindow {
+        &self.scales[0].1
+    }
+    
+    /// Window of the last scale (the longest for the standard scales)
+    pub fn longest(&self) -> &ConversationWindow {
+        &self.scales[self.scales.len() - 1].1
+    }
+    
+    /// Window of the scale called `name`
+    pub fn get(&self, name: &str) -> Option<&ConversationWindow> {
+        self.scales.iter().find(|(n, _)| n == name).map(|(_, w)| w)
+    }
+    
+    /// All scales with their names, primary first
+    pub fn scales(&self) -> impl Iterator<Item = (&str, &ConversationWindow)> {
+        self.scales.iter().map(|(n, w)| (n.as_str(), w))
+    }
+    
+    /// Clear all scales
+    pub fn clear(&mut self) {
+        for (_, window) in self.scales.iter_mut() {
+            window.clear();
+        }
     }
 }
 
@@ -266,6 +883,53 @@ mod tests {
         assert_eq!(window.paired_turns().len(), 2);
     }
     
+    #[test]
+    fn test_merge_gap_coalesces_bursts() {
+        let mut window = ConversationWindow::new();
+        window.add_turn(Turn::new("A", "hey", 0.1));
+        window.add_turn(Turn::new("A", "so about tomorrow", 0.2));
+        window.add_turn(Turn::new("A", "are you free?", 0.3));
+        window.add_turn(Turn::new("B", "Yes, after lunch", 0.1));
+        
+        // Raw: only the last A message pairs with B
+        let pairs = window.paired_turns();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].first.text, "are you free?");
+        
+        window.set_merge_gap(Some(Duration::from_secs(5)));
+        let pairs = window.paired_turns();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].first.text, "hey\nso about tomorrow\nare you free?");
+        assert!((pairs[0].first.r - 0.2).abs() < 1e-9);
+        assert_eq!(window.logical_turns().len(), 2);
+        assert_eq!(window.len(), 4, "raw turns are kept");
+    }
+    
+    #[test]
+    fn test_merge_gap_from_user_input() {
+        assert_eq!(merge_gap_from_secs(2.5), Ok(Duration::from_millis(2500)));
+        assert_eq!(merge_gap_from_secs(0.0), Ok(Duration::ZERO));
+        for bad in [-1.0, f64::INFINITY, f64::NAN, 1e300, MAX_MERGE_GAP_SECS + 1.0] {
+            assert!(merge_gap_from_secs(bad).is_err(), "{} accepted", bad);
+        }
+    }
+    
+    #[test]
+    fn test_merge_gap_respects_pauses() {
+        let now = Instant::now();
+        let mut window = ConversationWindow::new();
+        window.set_merge_gap(Some(Duration::from_secs(5)));
+        window.add_turn(Turn::new("A", "first", 0.1).with_timestamp(now - Duration::from_secs(20)));
+        window.add_turn(Turn::new("A", "much later", 0.1).with_timestamp(now - Duration::from_secs(10)));
+        window.add_turn(Turn::new("A", "right after", 0.1).with_timestamp(now - Duration::from_secs(8)));
+        window.add_turn(Turn::new("B", "reply", 0.1).with_timestamp(now));
+        
+        let turns = window.logical_turns();
+        assert_eq!(turns.len(), 3);
+        assert_eq!(turns[1].text, "much later\nright after");
+        assert_eq!(window.pair_indices(), vec![(1, 2)]);
+    }
+    
     #[test]
     fn test_can_calculate_dc() {
         let mut window = ConversationWindow::new();
@@ -300,6 +964,189 @@ mod tests {
         assert_eq!(window.turns().next().unwrap().text, "New");
     }
     
+    #[test]
+    fn test_incremental_logical_turns_match_rebuild() {
+        let policy = WindowPolicy::turns(5).with_speaker_cap(3);
+        let mut window = ConversationWindow::with_policy(policy);
+        window.set_merge_gap(Some(Duration::from_secs(5)));
+        for (i, speaker) in ["A", "A", "B", "A", "A", "A", "A", "B", "B", "A"].iter().enumerate() {
+            window.add_turn(Turn::new(*speaker, format!("m{}", i), i as f64 / 10.0));
+            
+            let mut rebuilt = ConversationWindow::with_policy(WindowPolicy::default());
+            for turn in window.turns() {
+                rebuilt.add_turn(turn.clone());
+            }
+            rebuilt.set_merge_gap(window.merge_gap());
+            let texts = |w: &ConversationWindow| -> Vec<(String, f64)> {
+                w.logical_turns().iter().map(|t| (t.text.clone(), t.r)).collect()
+            };
+            assert_eq!(texts(&window), texts(&rebuilt), "after turn {}", i);
+            assert_eq!(window.speaker_count(), rebuilt.speakers().len());
+        }
+    }
+    
+    #[test]
+    fn test_window_policy_limits() {
+        // Async chat: minutes apart, still paired under a turn-count policy
+        let now = Instant::now();
+        let mut window = ConversationWindow::with_policy(WindowPolicy::turns(3));
+        for (i, speaker) in ["A", "B", "A", "B"].iter().enumerate() {
+            let at = now - Duration::from_secs(600 - i as u64 * 120);
+            window.add_turn(Turn::new(*speaker, "message", 0.1).with_timestamp(at));
+        }
+        assert_eq!(window.len(), 3);
+        assert_eq!(window.paired_turns().len(), 2);
+        assert!(!window.timed_out());
+        
+        let mut window = ConversationWindow::with_policy(WindowPolicy::pairs(1));
+        for speaker in ["A", "B", "A", "A", "B"] {
+            window.add_turn(Turn::new(speaker, "message", 0.1));
+        }
+        assert_eq!(window.pair_indices().len(), 1);
+        assert_eq!(window.len(), 3, "the last pair plus the burst turn before it");
+        
+        // Merged bursts leave as a whole
+        let mut window = ConversationWindow::with_policy(WindowPolicy::pairs(2));
+        window.set_merge_gap(Some(Duration::from_secs(60)));
+        for speaker in ["A", "A", "B", "B", "A", "B", "B"] {
+            window.add_turn(Turn::new(speaker, "message", 0.1));
+        }
+        assert_eq!(window.pair_indices().len(), 2);
+        assert_eq!(window.len(), 5);
+        assert_eq!(window.logical_turns().len(), 3);
+        
+        let policy = WindowPolicy::time(300).with_speaker_cap(2);
+        let mut window = ConversationWindow::with_policy(policy);
+        for speaker in ["A", "A", "A", "B"] {
+            window.add_turn(Turn::new(speaker, "message", 0.1));
+        }
+        assert_eq!(window.len(), 3);
+    }
+    
+    #[test]
+    fn test_window_policy_parse() {
+        let policy: WindowPolicy = "time=300, per-speaker=5".parse().unwrap();
+        assert_eq!(policy, WindowPolicy::time(300).with_speaker_cap(5));
+        assert_eq!(policy.to_string().parse::<WindowPolicy>().unwrap(), policy);
+        assert_eq!("standard".parse::<WindowPolicy>().unwrap(), WindowPolicy::standard());
+        assert_eq!(WindowPolicy::default().to_string(), "unbounded");
+        assert!(WindowPolicy::default().is_unbounded());
+        assert!(!WindowPolicy::time(30).is_unbounded());
+        assert_eq!(WindowPolicy::time(30).capped(100), WindowPolicy::time(30).with_max_turns(100));
+        assert_eq!(WindowPolicy::pairs(1000).capped(100), WindowPolicy::pairs(100).with_max_turns(100));
+        assert!("turns".parse::<WindowPolicy>().is_err());
+        assert!("weeks=2".parse::<WindowPolicy>().is_err());
+    }
+    
+    #[test]
+    fn test_window_timed_out() {
+        let now = Instant::now();
+        let mut window = ConversationWindow::with_duration(30);
+        window.add_turn(Turn::new("A", "Old", 0.1).with_timestamp(now - Duration::from_secs(60)));
+        assert!(window.timed_out(), "the only turn expired on arrival");
+        
+        window.add_turn(Turn::new("B", "New", 0.1));
+        assert_eq!(window.len(), 1);
+        assert!(window.timed_out(), "the new turn found nothing left to pair with");
+        
+        window.add_turn(Turn::new("A", "Reply", 0.1));
+        assert!(!window.timed_out());
+        
+        let mut window = ConversationWindow::with_policy(WindowPolicy::turns(10));
+        window.add_turn(Turn::new("A", "Old", 0.1).with_timestamp(now - Duration::from_secs(3600)));
+        assert!(!window.timed_out());
+    }
+    
+    #[test]
+    fn test_multi_scale_window() {
+        let now = Instant::now();
+        let mut windows = MultiScaleWindow::new();
+        windows.add_turn(Turn::new("A", "Earlier", 0.1).with_timestamp(now - Duration::from_secs(120)));
+        windows.add_turn(Turn::new("B", "Also earlier", 0.1).with_timestamp(now - Duration::from_secs(110)));
+        windows.add_turn(Turn::new("A", "Now", 0.1));
+        
+        assert_eq!(windows.primary().len(), 1);
+        assert_eq!(windows.get("medium").unwrap().len(), 3);
+        assert_eq!(windows.longest().len(), 3);
+        let names: Vec<&str> = windows.scales().map(|(n, _)| n).collect();
+        assert_eq!(names, vec!["short", "medium", "session"]);
+        
+        // No standard scale grows without bound
+        assert!(windows.scales().all(|(_, w)| w.policy().max_turns.is_some() || w.policy().max_turns_per_speaker.is_some()));
+        let mut windows = MultiScaleWindow::new();
+        for i in 0..SESSION_WINDOW_TURNS + 50 {
+            windows.add_turn(Turn::new(if i % 2 == 0 { "A" } else { "B" }, "message", 0.1));
+        }
+        assert_eq!(windows.longest().len(), SESSION_WINDOW_TURNS);
+        
+        let windows = MultiScaleWindow::with_scales(vec![WindowScale::new("chat", WindowPolicy::turns(5))]);
+        assert_eq!(windows.primary().policy(), WindowPolicy::turns(5));
+        assert_eq!(windows.scales().count(), 1);
+    }
+    
+    #[test]
+    fn test_turn_ids() {
+        let base = Instant::now();
+        let mut window = ConversationWindow::with_policy(WindowPolicy::turns(3));
+        window.set_merge_gap(Some(Duration::from_secs(2)));
+        let at = |secs| base + Duration::from_secs(secs);
+        
+        window.add_turn(Turn::new("A", "One", 0.1).with_timestamp(at(0)));
+        window.add_turn(Turn::new("B", "Two", 0.1).with_timestamp(at(5)));
+        let before: Vec<u64> = window.turn_ids().iter().copied().collect();
+        assert_eq!(before.len(), 2);
+        assert!(before[0] < before[1]);
+        
+        // A merged part gives the turn a new id
+        window.add_turn(Turn::new("B", "more", 0.1).with_timestamp(at(6)));
+        assert_eq!(window.turn_ids()[0], before[0]);
+        assert!(window.turn_ids()[1] > before[1]);
+        let merged = window.turn_ids()[1];
+        
+        // Pruning a raw part renews the front turn's id; the others keep theirs
+        window.add_turn(Turn::new("A", "Three", 0.1).with_timestamp(at(10)));
+        let three = window.turn_ids()[1];
+        assert_eq!(window.turn_ids()[0], merged);
+        window.add_turn(Turn::new("B", "Four", 0.1).with_timestamp(at(15)));
+        assert_eq!(window.logical_turns()[0].text, "more");
+        assert_ne!(window.turn_ids()[0], merged);
+        assert_eq!(window.turn_ids()[1], three);
+        assert_eq!(window.turn_ids().len(), window.logical_turns().len());
+        
+        window.clear();
+        assert!(window.turn_ids().is_empty());
+    }
+    
+    #[test]
+    fn test_speaker_cap_keeps_other_turn_ids() {
+        let base = Instant::now();
+        let at = |secs| base + Duration::from_secs(secs);
+        let policy = WindowPolicy::turns(10).with_speaker_cap(2);
+        
+        // B's oldest turn goes; the A turns either side stay apart or merge
+        for (a2_at, merged) in [(20, false), (2, true)] {
+            let mut window = ConversationWindow::with_policy(policy);
+            window.set_merge_gap(Some(Duration::from_secs(5)));
+            for (speaker, text, secs) in [("A", "a1", 0), ("B", "b1", 1), ("A", "a2", a2_at), ("B", "b2", 30), ("C", "c1", 40)] {
+                window.add_turn(Turn::new(speaker, text, 0.1).with_timestamp(at(secs)));
+            }
+            let before: Vec<u64> = window.turn_ids().iter().copied().collect();
+            window.add_turn(Turn::new("B", "b3", 0.1).with_timestamp(at(50)));
+            
+            let texts: Vec<&str> = window.logical_turns().iter().map(|t| t.text.as_str()).collect();
+            let ids: Vec<u64> = window.turn_ids().iter().copied().collect();
+            if merged {
+                assert_eq!(texts, ["a1\na2", "b2", "c1", "b3"]);
+                assert!(!before.contains(&ids[0]));
+                assert_eq!(ids[1..3], before[3..5]);
+            } else {
+                assert_eq!(texts, ["a1", "a2", "b2", "c1", "b3"]);
+                assert_eq!(ids[..2], [before[0], before[2]]);
+                assert_eq!(ids[2..4], before[3..5]);
+            }
+        }
+    }
+    
     #[test]
     fn test_average_r() {
         let mut window = ConversationWindow::new();
@@ -5,8 +5,10 @@
 //! - UNKNOWN with reason when invalid
 //! - Three scenarios: clean, messy, adversarial
 
+#![allow(clippy::manual_range_contains)]
+
 use soul0::core::{RParser, DcParser};
-use soul0::types::{Turn, ConversationWindow, DcReason};
+use soul0::types::{Turn, ConversationWindow, DcReason, DcTimeline, GroupAggregation};
 
 fn make_turn(speaker: &str, text: &str) -> Turn {
     let r_parser = RParser::new();
@@ -288,3 +290,59 @@ fn test_dc_result_serialization() {
     assert!(json.contains("reason"));
     assert!(json.contains("signals"));
 }
+
+#[test]
+fn test_dc_timeline_finds_biggest_rise() {
+    let dc_parser = DcParser::new();
+    let mut window = ConversationWindow::new();
+    let mut timeline = DcTimeline::new();
+    
+    let turns = [
+        ("A", "How is the garden doing?"),
+        ("B", "The garden is full of roses."),
+        ("A", "Lovely, the roses smell great."),
+        ("B", "My car broke down on the highway."),
+    ];
+    for (speaker, text) in turns {
+        window.add_turn(make_turn(speaker, text));
+        timeline.record(speaker, &dc_parser.calculate(&window));
+    }
+    
+    assert_eq!(timeline.len(), 4);
+    assert!(timeline.points[0].dc.is_none(), "one turn has no ΔC");
+    
+    let (point, rise) = timeline.biggest_rise().unwrap();
+    let dcs: Vec<f64> = timeline.points.iter().filter_map(|p| p.dc).collect();
+    let largest = dcs.windows(2).map(|w| w[1] - w[0]).fold(f64::MIN, f64::max);
+    assert!((rise - largest).abs() < 1e-12);
+    let before = timeline.points[point.turn - 2].dc.unwrap();
+    assert!((point.dc.unwrap() - before - rise).abs() < 1e-12);
+    assert_eq!(timeline.peak().unwrap().turn, 4);
+}
+
+#[test]
+fn test_group_of_four_reports_matrix() {
+    let dc_parser = DcParser::with_group_aggregation("worst-pair".parse().unwrap());
+    let mut window = ConversationWindow::new();
+    
+    window.add_turn(make_turn("Ann", "Shall we walk in the park this afternoon?"));
+    window.add_turn(make_turn("Bo", "Yes, the park is lovely this afternoon."));
+    window.add_turn(make_turn("Cy", "A walk in the park sounds good."));
+    window.add_turn(make_turn("Di", "My phone bill doubled this month."));
+    
+    let result = dc_parser.calculate(&window);
+    
+    assert_eq!(result.speaker_count, 4);
+    assert_eq!(result.aggregation, GroupAggregation::WorstPair);
+    assert_eq!(result.matrix.len(), 3);
+    assert_eq!(result.speaker_coherence.len(), 4);
+    
+    let worst = result.matrix.iter().max_by(|a, b| a.dc.total_cmp(&b.dc)).unwrap();
+    assert!(worst.is_pair("Cy", "Di"));
+    assert_eq!(result.value, Some(worst.dc));
+    
+    let json = serde_json::to_string(&result).unwrap();
+    assert!(json.contains("\"matrix\""));
+    assert!(json.contains("\"worst_pair\""));
+    assert!("sideways".parse::<GroupAggregation>().is_err());
+}
@@ -6,6 +6,8 @@
 //! - Hash only paired turns in window
 //! - Proofs are permanent (no revocation API)
 
+#![allow(clippy::assertions_on_constants, clippy::needless_borrows_for_generic_args)]
+
 use soul0::core::{RParser, DcParser, FacelockEngine, ProofGenerator, verify_proof};
 use soul0::types::{Turn, ConversationWindow, FacelockState, ProofReason};
 use std::thread::sleep;
@@ -5,6 +5,8 @@
 //! - Contains: seen, blind_spots, horizon
 //! - Proper linking to proof via hash
 
+#![allow(clippy::assertions_on_constants, clippy::needless_borrows_for_generic_args)]
+
 use soul0::core::{RParser, DcParser, FacelockEngine, ProofGenerator, SnapshotGenerator};
 use soul0::types::{Turn, ConversationWindow, FacelockState, BlindSpotCategory, SnapshotReason, DcResult, DcSignals};
 use std::thread::sleep;
@@ -137,3 +137,332 @@ async fn test_snapshot_not_found_without_lock() {
     
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
+
+#[tokio::test]
+async fn test_custom_scorer_used_by_session() {
+    use soul0::core::{create_router_with_scorers, DcParser, RScorer, Scorers};
+    use soul0::types::{RSignals, RValue};
+    use std::sync::Arc;
+    
+    #[derive(Debug)]
+    struct FixedScorer;
+    
+    impl RScorer for FixedScorer {
+        fn parse(&self, text: &str) -> RValue {
+            RValue::new(0.42, RSignals::zero(), 1.0, text.split_whitespace().count())
+        }
+    }
+    
+    let scorers = Scorers::new(Arc::new(FixedScorer), Arc::new(DcParser::new()));
+    let app = create_router_with_scorers("./test_snapshots".to_string(), scorers);
+    
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/session/new")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"observers": ["A", "B"]}"#))
+                .unwrap()
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let session_id = json["session_id"].as_str().unwrap().to_string();
+    
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/session/{}/turn", session_id))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"speaker": "A", "text": "Stillness."}"#))
+                .unwrap()
+        )
+        .await
+        .unwrap();
+    
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert!((json["r"].as_f64().unwrap() - 0.42).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_timeline_records_every_turn() {
+    let app = create_test_router();
+    
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/session/new")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"observers": ["A", "B"]}"#))
+                .unwrap()
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let session_id = json["session_id"].as_str().unwrap().to_string();
+    
+    let turns = [
+        ("A", "How was your day at the garden?"),
+        ("B", "The garden was lovely, the roses are blooming."),
+        ("A", "Did you see the football match yesterday?"),
+    ];
+    let mut last = Value::Null;
+    for (speaker, text) in turns {
+        let body = serde_json::json!({ "speaker": speaker, "text": text }).to_string();
+        let response = app.clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/session/{}/turn", session_id))
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        last = serde_json::from_slice(&body).unwrap();
+    }
+    
+    // Two pairs in the window, each with its share of ΔC
+    assert_eq!(last["dc_pairs"].as_array().unwrap().len(), 2);
+    
+    // ΔC at every window scale, the short one first and equal to `dc`
+    let scales = last["dc_scales"].as_array().unwrap();
+    let names: Vec<&str> = scales.iter().map(|s| s["scale"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["short", "medium", "session"]);
+    assert_eq!(scales[0]["dc"]["value"], last["dc"]);
+    assert_eq!(scales[2]["turn_count"], 3);
+    
+    // Status polls report the same scales, primary first
+    let response = app.clone()
+        .oneshot(Request::builder().uri(format!("/session/{}", session_id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let status: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(status["dc_scales"], last["dc_scales"]);
+    assert_eq!(status["dc"], last["dc"]);
+    
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/session/{}/timeline", session_id))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await
+        .unwrap();
+    
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let points = json["points"].as_array().unwrap();
+    assert_eq!(points.len(), 3);
+    assert!(points[0]["dc"].is_null());
+    assert_eq!(points[2]["speaker"], "A");
+}
+
+#[tokio::test]
+async fn test_recorded_session_replays_clean() {
+    use soul0::core::{create_router_with_audit, read_audit_log, replay, Scorers};
+    use soul0::types::ReplaySpeed;
+    
+    let audit_dir = std::env::temp_dir().join("soul0_slice5_audit");
+    let app = create_router_with_audit(
+        "./test_snapshots".to_string(),
+        Scorers::default(),
+        Some(audit_dir.to_string_lossy().into_owned()),
+    );
+    
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/session/new")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"observers": ["A", "B"], "merge_gap_secs": 2.0}"#))
+                .unwrap()
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let session_id = json["session_id"].as_str().unwrap().to_string();
+    
+    let turns = [
+        ("A", "How was your day at the garden?"),
+        ("B", "The garden was lovely, the roses are blooming."),
+        ("A", "The roses in the garden sound lovely."),
+    ];
+    for (speaker, text) in turns {
+        let body = serde_json::json!({ "speaker": speaker, "text": text }).to_string();
+        app.clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/session/{}/turn", session_id))
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+    }
+    
+    let path = audit_dir.join(format!("{}.ndjson", session_id));
+    let recording = read_audit_log(path.to_str().unwrap()).unwrap();
+    assert_eq!(recording.mode, "api");
+    assert_eq!(recording.config.merge_gap_ms, Some(2000));
+    assert_eq!(recording.turns.len(), 3);
+    assert!(recording.turns.iter().all(|t| t.state.is_some() && t.dc.is_some()));
+    assert!(recording.turns[2].dc.as_ref().unwrap().value.is_some());
+    
+    let report = replay(&recording, &Scorers::default(), ReplaySpeed::Instant, |_| [0u8; 64], |_, _| {});
+    assert!(report.is_clean(), "{:?}", report.diffs);
+    assert_eq!(report.turns, 3);
+}
+
+#[tokio::test]
+async fn test_snapshot_query_endpoint() {
+    use std::sync::Arc;
+    use soul0::core::{create_router_with_store, Scorers, SnapshotStore, SqliteStore, DEFAULT_SNAPSHOT_LIMIT};
+    use soul0::types::Snapshot;
+    
+    let store = Arc::new(SqliteStore::open(":memory:").unwrap());
+    let bulk: Vec<(String, i64, &str)> = (0..DEFAULT_SNAPSHOT_LIMIT as i64)
+        .map(|i| (format!("bulk{}", i), 100 + i, "bulk"))
+        .collect();
+    let named = [("old", 1_000, "garden"), ("new", 2_000, "garden"), ("other", 3_000, "work")];
+    for (id, timestamp, theme) in named.iter().map(|&(id, t, theme)| (id.to_string(), t, theme)).chain(bulk) {
+        let snapshot: Snapshot = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "timestamp_unix": timestamp,
+            "session_id": vec![1; 16],
+            "proof_hash": vec![2; 32],
+            "r_final": 0.1,
+            "dc_final": 0.05,
+            "lock_duration_secs": 8,
+            "seen": { "themes": [theme], "emotion": null, "keywords": [], "summary": null },
+            "blind_spots": [],
+            "horizon": [],
+            "observers": ["A", "B"],
+            "turn_count": 4
+        })).unwrap();
+        store.save(&snapshot).unwrap();
+    }
+    let app = create_router_with_store(store, Scorers::default(), None);
+    
+    let response = app.clone()
+        .oneshot(Request::builder().uri("/snapshots?theme=garden&observer=A").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let ids: Vec<&str> = json.as_array().unwrap().iter().map(|s| s["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["new", "old"]);
+    
+    let response = app.clone()
+        .oneshot(Request::builder().uri("/snapshots?since=2000&limit=1").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json[0]["id"], "other");
+    assert_eq!(json.as_array().unwrap().len(), 1);
+    
+    // Without a limit the newest DEFAULT_SNAPSHOT_LIMIT come back; a larger one is honoured up to the cap
+    for (uri, count) in [("/snapshots", DEFAULT_SNAPSHOT_LIMIT), ("/snapshots?limit=1000000", DEFAULT_SNAPSHOT_LIMIT + 3)] {
+        let response = app.clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), count, "{}", uri);
+        assert_eq!(json[0]["id"], "other");
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_merge_gap_is_rejected() {
+    for gap in ["-1.0", "1e300", "86401"] {
+        let response = create_test_router()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/session/new")
+                    .header("content-type", "application/json")
+                    .body(Body::from(format!(r#"{{"merge_gap_secs": {}}}"#, gap)))
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "merge_gap_secs {}", gap);
+    }
+}
+
+#[tokio::test]
+async fn test_unbounded_window_is_rejected() {
+    for body in [r#"{"window": {}}"#, r#"{"scales": [{"name": "all", "policy": {}}]}"#] {
+        let response = create_test_router()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/session/new")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", body);
+    }
+}
+
+#[tokio::test]
+async fn test_window_limits_are_capped() {
+    let app = create_test_router();
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/session/new")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"window": {"max_age_secs": 600, "max_pairs": 1000000}}"#))
+                .unwrap()
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let session_id = json["session_id"].as_str().unwrap();
+    
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/session/{}/turn", session_id))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"speaker": "A", "text": "Hello there"}"#))
+                .unwrap()
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let policy = &json["dc_scales"][0]["policy"];
+    let max = soul0::core::api::MAX_WINDOW_TURNS;
+    assert_eq!(policy["max_age_secs"], 600);
+    assert_eq!(policy["max_turns"], max);
+    assert_eq!(policy["max_pairs"], max);
+}